@@ -7,9 +7,13 @@
 //! Toda regla o dato compartido pasa por validación antes de aceptarse.
 //! Esto previene "improvisación de lógica" peligrosa.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use crate::uniform::UnifyValue;
 
+/// Límite de historial para contextos de larga vida (p. ej. el de cada
+/// petición o usuario); por defecto el historial no tiene límite
+pub const REQUEST_HISTORY_LIMIT: usize = 1024;
+
 /// Contexto compartido entre todos los componentes del sistema
 #[derive(Debug, Clone)]
 pub struct SharedContext {
@@ -22,8 +26,15 @@ pub struct SharedContext {
     /// Validador de constraints
     validator: ConstraintValidator,
 
-    /// Historial de cambios para rollback
-    history: Vec<ContextChange>,
+    /// Historial de cambios para rollback (los más antiguos se descartan
+    /// al pasar de `history_limit`)
+    history: VecDeque<ContextChange>,
+
+    /// Máximo de cambios guardados (sin límite por defecto)
+    history_limit: Option<usize>,
+
+    /// Cambios descartados del historial (los checkpoints son absolutos)
+    discarded: usize,
 
     /// Modo estricto: rechaza cualquier violación
     strict_mode: bool,
@@ -170,7 +181,9 @@ impl SharedContext {
             bindings: HashMap::new(),
             rules: Vec::new(),
             validator: ConstraintValidator::default(),
-            history: Vec::new(),
+            history: VecDeque::new(),
+            history_limit: None,
+            discarded: 0,
             strict_mode: true,
         }
     }
@@ -204,12 +217,10 @@ impl SharedContext {
                 return Err(ValidationError::ImmutableBinding(key.to_string()));
             }
             // Guardar para rollback
-            self.history.push(ContextChange::BindingModified(
-                key.to_string(),
-                existing.clone(),
-            ));
+            let change = ContextChange::BindingModified(key.to_string(), existing.clone());
+            self.record_change(change);
         } else {
-            self.history.push(ContextChange::BindingAdded(key.to_string()));
+            self.record_change(ContextChange::BindingAdded(key.to_string()));
         }
 
         // Validar según fuente
@@ -243,7 +254,7 @@ impl SharedContext {
             return Err(ValidationError::ImmutableBinding(key.to_string()));
         }
 
-        self.history.push(ContextChange::BindingAdded(key.to_string()));
+        self.record_change(ContextChange::BindingAdded(key.to_string()));
 
         let shared = SharedValue {
             value,
@@ -264,7 +275,7 @@ impl SharedContext {
         // Validar contra constraints
         self.validator.validate_rule(&rule, &self.rules)?;
 
-        self.history.push(ContextChange::RuleAdded(rule.id.clone()));
+        self.record_change(ContextChange::RuleAdded(rule.id.clone()));
         self.rules.push(rule);
         Ok(())
     }
@@ -284,32 +295,65 @@ impl SharedContext {
 
     // === ROLLBACK ===
 
+    /// Guarda un cambio; si el historial pasa del límite se olvida el más antiguo
+    fn record_change(&mut self, change: ContextChange) {
+        self.history.push_back(change);
+        self.trim_history();
+    }
+
+    fn trim_history(&mut self) {
+        let Some(limit) = self.history_limit else {
+            return;
+        };
+        while self.history.len() > limit {
+            self.history.pop_front();
+            self.discarded += 1;
+        }
+    }
+
+    /// Máximo de cambios que se pueden deshacer (sin límite por defecto);
+    /// acota la memoria de contextos de larga vida. Un checkpoint anterior
+    /// a los cambios descartados ya no se restaura por completo
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = Some(limit);
+        self.trim_history();
+    }
+
+    /// Cambios guardados para rollback
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
     /// Crea checkpoint
     pub fn checkpoint(&self) -> usize {
-        self.history.len()
+        self.discarded + self.history.len()
     }
 
-    /// Rollback a checkpoint
-    pub fn rollback(&mut self, checkpoint: usize) {
-        while self.history.len() > checkpoint {
-            if let Some(change) = self.history.pop() {
-                match change {
-                    ContextChange::BindingAdded(key) => {
-                        self.bindings.remove(&key);
-                    }
-                    ContextChange::BindingModified(key, old) => {
-                        self.bindings.insert(key, old);
-                    }
-                    ContextChange::RuleAdded(id) => {
-                        self.rules.retain(|r| r.id != id);
-                    }
-                    ContextChange::RuleRemoved(id, rule) => {
-                        // Re-insertar con mismo id
-                        self.rules.push(SharedRule { id, ..rule });
-                    }
+    /// Rollback a checkpoint. Retorna `false` si parte de sus cambios ya
+    /// se descartaron del historial (`set_history_limit`): entonces solo se
+    /// deshace lo que queda
+    pub fn rollback(&mut self, checkpoint: usize) -> bool {
+        while self.discarded + self.history.len() > checkpoint {
+            let Some(change) = self.history.pop_back() else {
+                return false;
+            };
+            match change {
+                ContextChange::BindingAdded(key) => {
+                    self.bindings.remove(&key);
+                }
+                ContextChange::BindingModified(key, old) => {
+                    self.bindings.insert(key, old);
+                }
+                ContextChange::RuleAdded(id) => {
+                    self.rules.retain(|r| r.id != id);
+                }
+                ContextChange::RuleRemoved(id, rule) => {
+                    // Re-insertar con mismo id
+                    self.rules.push(SharedRule { id, ..rule });
                 }
             }
         }
+        true
     }
 
     // === QUERIES ===
@@ -334,9 +378,9 @@ impl Default for SharedContext {
     }
 }
 
-impl ConstraintValidator {
+impl Default for ConstraintValidator {
     /// Crea validador con constraints por defecto
-    pub fn default() -> Self {
+    fn default() -> Self {
         let mut validator = Self {
             invariants: Vec::new(),
             evidence_required: HashMap::new(),
@@ -351,7 +395,9 @@ impl ConstraintValidator {
 
         validator
     }
+}
 
+impl ConstraintValidator {
     /// Añade invariante
     pub fn add_invariant(&mut self, constraint: Constraint) {
         self.invariants.push(constraint);
@@ -374,44 +420,38 @@ impl ConstraintValidator {
         existing: &[SharedRule],
     ) -> Result<(), ValidationError> {
         // 1. Verificar predicados protegidos (solo si es improvisación)
-        if rule.source == Source::Improvised {
-            if self.protected_predicates.contains(&rule.predicate) {
-                return Err(ValidationError::ProtectedPredicate(rule.predicate.clone()));
-            }
+        if rule.source == Source::Improvised
+            && self.protected_predicates.contains(&rule.predicate)
+        {
+            return Err(ValidationError::ProtectedPredicate(rule.predicate.clone()));
         }
 
         // 2. Verificar invariantes
         for inv in &self.invariants {
             match &inv.check {
-                ConstraintType::NoTautology => {
-                    if is_tautology(rule) {
-                        return Err(ValidationError::TautologyDetected);
-                    }
+                ConstraintType::NoTautology if is_tautology(rule) => {
+                    return Err(ValidationError::TautologyDetected);
                 }
-                ConstraintType::Forbidden(pred) => {
-                    if &rule.predicate == pred {
-                        return Err(ValidationError::InvariantViolation(inv.name.clone()));
-                    }
+                ConstraintType::Forbidden(pred) if &rule.predicate == pred => {
+                    return Err(ValidationError::InvariantViolation(inv.name.clone()));
                 }
-                ConstraintType::FixedArity(pred, expected) => {
-                    if &rule.predicate == pred && rule.arity != *expected {
-                        return Err(ValidationError::ArityMismatch(
-                            pred.clone(),
-                            *expected,
-                            rule.arity,
-                        ));
-                    }
+                ConstraintType::FixedArity(pred, expected)
+                    if &rule.predicate == pred && rule.arity != *expected =>
+                {
+                    return Err(ValidationError::ArityMismatch(
+                        pred.clone(),
+                        *expected,
+                        rule.arity,
+                    ));
                 }
-                ConstraintType::NoContradiction(pred1, pred2) => {
-                    // Verificar que no contradice reglas existentes
-                    if &rule.predicate == pred1 {
-                        for ex in existing {
-                            if &ex.predicate == pred2 {
-                                // Simplificado: detectar contradicción obvia
-                                return Err(ValidationError::ContradictionDetected(
-                                    format!("{} vs {}", pred1, pred2),
-                                ));
-                            }
+                // Verificar que no contradice reglas existentes
+                ConstraintType::NoContradiction(pred1, pred2) if &rule.predicate == pred1 => {
+                    for ex in existing {
+                        if &ex.predicate == pred2 {
+                            // Simplificado: detectar contradicción obvia
+                            return Err(ValidationError::ContradictionDetected(
+                                format!("{} vs {}", pred1, pred2),
+                            ));
                         }
                     }
                 }
@@ -503,6 +543,34 @@ mod tests {
         assert!(ctx.get("temp").is_none());
     }
 
+    #[test]
+    fn test_history_limit() {
+        let mut ctx = SharedContext::new();
+        ctx.set_history_limit(3);
+
+        ctx.set("a", UnifyValue::Num(1.0), Source::User, 1.0).unwrap();
+        let cp = ctx.checkpoint();
+        for i in 0..10 {
+            ctx.set("b", UnifyValue::Num(i as f64), Source::User, 1.0).unwrap();
+        }
+        assert_eq!(ctx.history_len(), 3);
+        assert_eq!(ctx.checkpoint(), 11);
+
+        // Solo se deshacen los cambios que quedan en el historial, y se avisa
+        assert!(!ctx.rollback(cp));
+        assert_eq!(ctx.get("b").unwrap().value, UnifyValue::Num(6.0));
+        assert!(ctx.get("a").is_some());
+
+        // Sin límite (por defecto) el rollback es completo
+        let mut ctx = SharedContext::new();
+        let cp = ctx.checkpoint();
+        for i in 0..2000 {
+            ctx.set("b", UnifyValue::Num(i as f64), Source::User, 1.0).unwrap();
+        }
+        assert!(ctx.rollback(cp));
+        assert!(ctx.get("b").is_none());
+    }
+
     #[test]
     fn test_tautology_detection() {
         let rule = SharedRule {
@@ -0,0 +1,500 @@
+//! # Noisy Channel Module
+//!
+//! Modelo de errores de escritura P(escrito | intención) aprendido de pares
+//! `error corrección`, al estilo de Kernighan, Church y Gale (1990):
+//!
+//! ```text
+//! # error      corrección
+//! smor         amor
+//! rmoa         roma
+//! koliseo      coliseo
+//! ```
+//!
+//! Cada par se alinea con Damerau-Levenshtein y se cuentan sustituciones,
+//! borrados e inserciones (según la letra anterior) y transposiciones. Al
+//! puntuar, la alineación más probable se busca con los costos aprendidos
+//! (-ln P de cada operación).
+//!
+//! Las palabras se normalizan como en `CharMatcher` (minúsculas, sin
+//! acentos); `^` marca el inicio de palabra.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use super::normalize_word;
+
+/// Versión del formato en disco que entiende este parser
+pub const CHANNEL_FORMAT_VERSION: u32 = 1;
+
+/// Marca de inicio de palabra (contexto de la primera letra)
+const WORD_START: char = '^';
+
+/// Tamaño de alfabeto para el suavizado
+const ALPHABET_SIZE: f64 = 27.0;
+
+/// Operación de edición, de la palabra pretendida a la escrita
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Edit {
+    /// (pretendida, escrita)
+    Substitution(char, char),
+    /// (letra anterior, letra omitida)
+    Deletion(char, char),
+    /// (letra anterior, letra añadida)
+    Insertion(char, char),
+    /// (x, y): se escribió "yx" por "xy"
+    Transposition(char, char),
+}
+
+/// Modelo de canal ruidoso a nivel de caracteres
+#[derive(Debug, Clone)]
+pub struct ErrorModel {
+    /// Apariciones de cada letra en las palabras pretendidas (`^` = palabras)
+    chars: HashMap<char, u64>,
+    /// Apariciones de cada par de letras consecutivas
+    bigrams: HashMap<(char, char), u64>,
+    /// Letras copiadas sin error
+    copies: u64,
+    /// Conteo de cada operación de edición
+    edits: HashMap<Edit, u64>,
+    /// Pares de entrenamiento vistos
+    pairs: usize,
+    /// Suavizado aditivo (add-k)
+    pub smoothing: f64,
+}
+
+/// Error de lectura de pares o del modelo
+#[derive(Debug)]
+pub enum ChannelError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChannelError::IoError(e) => write!(f, "IO error: {}", e),
+            ChannelError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+impl ErrorModel {
+    /// Modelo sin entrenar
+    pub fn new() -> Self {
+        Self {
+            chars: HashMap::new(),
+            bigrams: HashMap::new(),
+            copies: 0,
+            edits: HashMap::new(),
+            pairs: 0,
+            smoothing: 0.5,
+        }
+    }
+
+    /// Entrena con pares (error, corrección)
+    pub fn train<'a, I: IntoIterator<Item = (&'a str, &'a str)>>(pairs: I) -> Self {
+        let mut model = Self::new();
+        for (typo, intended) in pairs {
+            model.add_pair(typo, intended);
+        }
+        model
+    }
+
+    /// Entrena desde un archivo de pares `error corrección` (uno por línea)
+    pub fn from_pairs_file(path: &Path) -> Result<Self, ChannelError> {
+        let content = fs::read_to_string(path).map_err(|e| ChannelError::IoError(e.to_string()))?;
+        Self::from_pairs(&content)
+    }
+
+    /// Entrena desde texto con pares `error corrección`
+    pub fn from_pairs(content: &str) -> Result<Self, ChannelError> {
+        let mut model = Self::new();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [typo, intended] => model.add_pair(typo, intended),
+                _ => return Err(ChannelError::ParseError(line_no + 1, "se esperaba: error corrección".to_string())),
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Añade un par (error, corrección) a los conteos
+    pub fn add_pair(&mut self, typo: &str, intended: &str) {
+        let typo: Vec<char> = normalize_word(typo).chars().collect();
+        let intended: Vec<char> = normalize_word(intended).chars().collect();
+        if intended.is_empty() {
+            return;
+        }
+
+        *self.chars.entry(WORD_START).or_insert(0) += 1;
+        let mut prev = WORD_START;
+        for &c in &intended {
+            *self.chars.entry(c).or_insert(0) += 1;
+            *self.bigrams.entry((prev, c)).or_insert(0) += 1;
+            prev = c;
+        }
+
+        for edit in align(&typo, &intended) {
+            match edit {
+                Some(edit) => *self.edits.entry(edit).or_insert(0) += 1,
+                None => self.copies += 1,
+            }
+        }
+        self.pairs += 1;
+    }
+
+    /// Pares de entrenamiento vistos
+    pub fn pairs(&self) -> usize {
+        self.pairs
+    }
+
+    /// Veces que se vio una operación
+    pub fn edit_count(&self, edit: Edit) -> u64 {
+        self.edits.get(&edit).copied().unwrap_or(0)
+    }
+
+    /// P(operación | contexto), suavizada
+    pub fn edit_probability(&self, edit: Edit) -> f64 {
+        let context = match edit {
+            Edit::Substitution(x, _) => self.char_count(x),
+            Edit::Deletion(prev, x) | Edit::Transposition(prev, x) => {
+                self.bigrams.get(&(prev, x)).copied().unwrap_or(0)
+            }
+            Edit::Insertion(prev, _) => self.char_count(prev),
+        };
+        let k = self.smoothing;
+        (self.edit_count(edit) as f64 + k) / (context as f64 + k * ALPHABET_SIZE)
+    }
+
+    /// P(copiar una letra sin error)
+    pub fn copy_probability(&self) -> f64 {
+        let total: u64 = self.chars.iter()
+            .filter(|(&c, _)| c != WORD_START)
+            .map(|(_, &n)| n)
+            .sum();
+        (self.copies as f64 + 1.0) / (total as f64 + 2.0)
+    }
+
+    fn char_count(&self, c: char) -> u64 {
+        self.chars.get(&c).copied().unwrap_or(0)
+    }
+
+    /// ln P(escrito | pretendida) por la alineación más probable
+    pub fn log_prob(&self, typo: &str, intended: &str) -> f64 {
+        let t: Vec<char> = normalize_word(typo).chars().collect();
+        let w: Vec<char> = normalize_word(intended).chars().collect();
+        let (m, n) = (w.len(), t.len());
+
+        let copy = self.copy_probability().ln();
+        let cost = |edit: Edit| -self.edit_probability(edit).ln();
+        let prev = |i: usize| if i == 0 { WORD_START } else { w[i - 1] };
+
+        // dp[i][j]: costo de producir t[..j] desde w[..i]
+        let mut dp = vec![vec![f64::INFINITY; n + 1]; m + 1];
+        dp[0][0] = 0.0;
+
+        for i in 0..=m {
+            for j in 0..=n {
+                let mut best = dp[i][j];
+                if i > 0 {
+                    best = best.min(dp[i - 1][j] + cost(Edit::Deletion(prev(i - 1), w[i - 1])));
+                }
+                if j > 0 {
+                    best = best.min(dp[i][j - 1] + cost(Edit::Insertion(prev(i), t[j - 1])));
+                }
+                if i > 0 && j > 0 {
+                    let step = if w[i - 1] == t[j - 1] {
+                        -copy
+                    } else {
+                        cost(Edit::Substitution(w[i - 1], t[j - 1]))
+                    };
+                    best = best.min(dp[i - 1][j - 1] + step);
+                }
+                if i > 1 && j > 1 && w[i - 1] == t[j - 2] && w[i - 2] == t[j - 1] && w[i - 1] != w[i - 2] {
+                    best = best.min(dp[i - 2][j - 2] + cost(Edit::Transposition(w[i - 2], w[i - 1])));
+                }
+                dp[i][j] = best;
+            }
+        }
+
+        -dp[m][n]
+    }
+
+    /// Score de similitud (0.0 - 1.0): probabilidad media por letra
+    /// (media geométrica de P(escrito | pretendida)) relativa a copiar sin
+    /// error, así una palabra idéntica puntúa 1.0
+    pub fn score(&self, typo: &str, intended: &str) -> f64 {
+        let len = normalize_word(typo).chars().count()
+            .max(normalize_word(intended).chars().count())
+            .max(1);
+        let per_letter = (self.log_prob(typo, intended) / len as f64).exp();
+        (per_letter / self.copy_probability()).min(1.0)
+    }
+
+    /// Guarda el modelo en disco
+    pub fn save(&self, path: &Path) -> Result<(), ChannelError> {
+        fs::write(path, self.to_text()).map_err(|e| ChannelError::IoError(e.to_string()))
+    }
+
+    /// Lee un modelo guardado con `save`
+    pub fn load(path: &Path) -> Result<Self, ChannelError> {
+        let content = fs::read_to_string(path).map_err(|e| ChannelError::IoError(e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Serializa los conteos (orden determinista)
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "@channel {}\npairs {}\ncopies {}\nsmoothing {}\n",
+            CHANNEL_FORMAT_VERSION, self.pairs, self.copies, self.smoothing
+        );
+
+        let mut chars: Vec<_> = self.chars.iter().collect();
+        chars.sort();
+        for (c, n) in chars {
+            out.push_str(&format!("char {} {}\n", c, n));
+        }
+
+        let mut bigrams: Vec<_> = self.bigrams.iter().collect();
+        bigrams.sort();
+        for ((a, b), n) in bigrams {
+            out.push_str(&format!("bigram {} {} {}\n", a, b, n));
+        }
+
+        let mut edits: Vec<_> = self.edits.iter().collect();
+        edits.sort();
+        for (edit, n) in edits {
+            let (name, a, b) = match *edit {
+                Edit::Substitution(a, b) => ("sub", a, b),
+                Edit::Deletion(a, b) => ("del", a, b),
+                Edit::Insertion(a, b) => ("ins", a, b),
+                Edit::Transposition(a, b) => ("trans", a, b),
+            };
+            out.push_str(&format!("{} {} {} {}\n", name, a, b, n));
+        }
+        out
+    }
+
+    /// Parsea el formato de `to_text`
+    pub fn parse(content: &str) -> Result<Self, ChannelError> {
+        let mut model = Self::new();
+        let mut version = None;
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line_no = line_no + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let error = |msg: &str| ChannelError::ParseError(line_no, msg.to_string());
+            let number = |s: &str| s.parse::<u64>().map_err(|_| error("conteo inválido"));
+            let letter = |s: &str| {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(error("se esperaba una letra")),
+                }
+            };
+
+            if version.is_none() {
+                match fields.as_slice() {
+                    ["@channel", v] => {
+                        let v = number(v)? as u32;
+                        if v > CHANNEL_FORMAT_VERSION {
+                            return Err(error(&format!("versión {} no soportada", v)));
+                        }
+                        version = Some(v);
+                        continue;
+                    }
+                    _ => return Err(error("falta la línea @channel")),
+                }
+            }
+
+            match fields.as_slice() {
+                ["pairs", n] => model.pairs = number(n)? as usize,
+                ["copies", n] => model.copies = number(n)?,
+                ["smoothing", k] => {
+                    model.smoothing = match k.parse::<f64>() {
+                        Ok(k) if k > 0.0 && k.is_finite() => k,
+                        _ => return Err(error("suavizado inválido")),
+                    };
+                }
+                ["char", c, n] => {
+                    model.chars.insert(letter(c)?, number(n)?);
+                }
+                ["bigram", a, b, n] => {
+                    model.bigrams.insert((letter(a)?, letter(b)?), number(n)?);
+                }
+                [kind, a, b, n] => {
+                    let (a, b) = (letter(a)?, letter(b)?);
+                    let edit = match *kind {
+                        "sub" => Edit::Substitution(a, b),
+                        "del" => Edit::Deletion(a, b),
+                        "ins" => Edit::Insertion(a, b),
+                        "trans" => Edit::Transposition(a, b),
+                        other => return Err(error(&format!("operación desconocida: {}", other))),
+                    };
+                    model.edits.insert(edit, number(n)?);
+                }
+                _ => return Err(error("línea mal formada")),
+            }
+        }
+
+        if version.is_none() {
+            return Err(ChannelError::ParseError(0, "falta la línea @channel".to_string()));
+        }
+        Ok(model)
+    }
+}
+
+impl Default for ErrorModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Alineación Damerau-Levenshtein de `intended` a `typo`: una entrada por
+/// operación (None = letra copiada)
+fn align(typo: &[char], intended: &[char]) -> Vec<Option<Edit>> {
+    let (m, n) = (intended.len(), typo.len());
+    let mut dp = vec![vec![0usize; n + 1]; m + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=m {
+        for j in 1..=n {
+            let cost = usize::from(intended[i - 1] != typo[j - 1]);
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && intended[i - 1] == typo[j - 2] && intended[i - 2] == typo[j - 1] {
+                dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    // Recorrido inverso
+    let prev = |i: usize| if i == 0 { WORD_START } else { intended[i - 1] };
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (m, n);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && intended[i - 1] == typo[j - 1] && dp[i][j] == dp[i - 1][j - 1] {
+            ops.push(None);
+            i -= 1;
+            j -= 1;
+        } else if i > 1 && j > 1 && intended[i - 1] == typo[j - 2] && intended[i - 2] == typo[j - 1]
+            && intended[i - 1] != intended[i - 2] && dp[i][j] == dp[i - 2][j - 2] + 1
+        {
+            ops.push(Some(Edit::Transposition(intended[i - 2], intended[i - 1])));
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && j > 0 && dp[i][j] == dp[i - 1][j - 1] + 1 {
+            ops.push(Some(Edit::Substitution(intended[i - 1], typo[j - 1])));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && dp[i][j] == dp[i - 1][j] + 1 {
+            ops.push(Some(Edit::Deletion(prev(i - 1), intended[i - 1])));
+            i -= 1;
+        } else {
+            ops.push(Some(Edit::Insertion(prev(i), typo[j - 1])));
+            j -= 1;
+        }
+    }
+
+    ops.reverse();
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAIRS: &str = "# error corrección\n\
+        smor amor\n\
+        snor amor\n\
+        sala casa\n\
+        koliseo coliseo\n\
+        rmoa roma\n\
+        cqsa casa\n\
+        aor amor\n";
+
+    #[test]
+    fn test_alignment() {
+        let ops = |t: &str, w: &str| -> Vec<Edit> {
+            let t: Vec<char> = t.chars().collect();
+            let w: Vec<char> = w.chars().collect();
+            align(&t, &w).into_iter().flatten().collect()
+        };
+
+        assert_eq!(ops("smor", "amor"), vec![Edit::Substitution('a', 's')]);
+        assert_eq!(ops("rmoa", "roma"), vec![Edit::Transposition('o', 'm')]);
+        assert_eq!(ops("aor", "amor"), vec![Edit::Deletion('a', 'm')]);
+        assert_eq!(ops("amorx", "amor"), vec![Edit::Insertion('r', 'x')]);
+        assert_eq!(ops("xamor", "amor"), vec![Edit::Insertion('^', 'x')]);
+        assert!(ops("amor", "amor").is_empty());
+    }
+
+    #[test]
+    fn test_learned_confusions() {
+        let model = ErrorModel::from_pairs(PAIRS).unwrap();
+        assert_eq!(model.pairs(), 7);
+        assert_eq!(model.edit_count(Edit::Substitution('a', 's')), 2);
+        assert_eq!(model.edit_count(Edit::Substitution('c', 'k')), 1);
+
+        // Confusión vista (a→s) más probable que una no vista (a→z)
+        assert!(model.edit_probability(Edit::Substitution('a', 's'))
+            > model.edit_probability(Edit::Substitution('a', 'z')));
+
+        // "smor" viene más probablemente de "amor" que de "mora"
+        assert!(model.log_prob("smor", "amor") > model.log_prob("smor", "mora"));
+        // La transposición cuesta una operación, no dos sustituciones
+        assert!(model.score("rmoa", "roma") > model.score("rmoa", "rama"));
+        assert!((model.score("amor", "amor") - 1.0).abs() < 1e-9);
+        assert!(model.score("smor", "amor") > model.score("smor", "mora"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let model = ErrorModel::from_pairs(PAIRS).unwrap();
+        let text = model.to_text();
+        assert!(text.starts_with("@channel 1\n"));
+        assert!(text.contains("trans o m 1\n"));
+
+        let loaded = ErrorModel::parse(&text).unwrap();
+        assert_eq!(loaded.pairs(), model.pairs());
+        assert!((loaded.log_prob("smor", "amor") - model.log_prob("smor", "amor")).abs() < 1e-12);
+
+        // El suavizado también se conserva
+        let mut smoothed = ErrorModel::from_pairs(PAIRS).unwrap();
+        smoothed.smoothing = 2.0;
+        let loaded = ErrorModel::parse(&smoothed.to_text()).unwrap();
+        assert_eq!(loaded.smoothing, 2.0);
+        for (typo, intended) in [("smor", "amor"), ("rmoa", "roma"), ("smor", "mora"), ("casa", "casa")] {
+            assert!((loaded.score(typo, intended) - smoothed.score(typo, intended)).abs() < 1e-12);
+        }
+        assert!((loaded.score("smor", "amor") - model.score("smor", "amor")).abs() > 1e-6);
+        assert!(matches!(ErrorModel::parse("@channel 1\nsmoothing 0\n"), Err(ChannelError::ParseError(2, _))));
+
+        assert!(matches!(ErrorModel::parse("sub a s 1\n"), Err(ChannelError::ParseError(1, _))));
+        assert!(matches!(ErrorModel::parse("@channel 1\nswap a s 1\n"), Err(ChannelError::ParseError(2, _))));
+        assert!(matches!(ErrorModel::from_pairs("smor\n"), Err(ChannelError::ParseError(1, _))));
+    }
+}
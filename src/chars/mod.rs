@@ -5,8 +5,14 @@
 //! - Cada letra tiene un peso (100% / longitud)
 //! - Letras coincidentes suman su peso
 //! - También considera posición y conjunto de caracteres
+//!
+//! Como alternativa a la heurística ponderada, `ScoringMode::NoisyChannel`
+//! puntúa con un modelo de errores aprendido (ver `channel`).
+
+pub mod channel;
 
 use std::collections::{HashMap, HashSet};
+use channel::ErrorModel;
 
 /// Motor de matching de caracteres
 #[derive(Debug, Clone)]
@@ -19,6 +25,61 @@ pub struct CharMatcher {
 
     /// Configuración
     config: CharMatchConfig,
+
+    /// Modelo de errores para `ScoringMode::NoisyChannel`
+    error_model: Option<ErrorModel>,
+
+    /// Frecuencias de uso (desempate entre candidatos con igual score)
+    frequencies: HashMap<String, u64>,
+}
+
+/// Palabras candidatas fuera del diccionario (p. ej. las de los
+/// diccionarios de usuario), indexadas una vez por petición
+#[derive(Debug, Clone, Default)]
+pub struct ExtraWords {
+    /// Palabras tal como se devuelven
+    words: Vec<String>,
+    /// Forma normalizada de cada palabra
+    normalized: Vec<String>,
+    keys: HashSet<String>,
+    /// Índice invertido: letra -> posiciones en `words`
+    letter_index: HashMap<char, Vec<usize>>,
+}
+
+impl ExtraWords {
+    pub fn new(words: Vec<String>) -> Self {
+        let normalized: Vec<String> = words.iter().map(|w| normalize_word(w)).collect();
+        let mut letter_index: HashMap<char, Vec<usize>> = HashMap::new();
+        for (i, key) in normalized.iter().enumerate() {
+            for c in key.chars().collect::<HashSet<_>>() {
+                letter_index.entry(c).or_default().push(i);
+            }
+        }
+        let keys = normalized.iter().cloned().collect();
+        Self { words, normalized, keys, letter_index }
+    }
+
+    /// ¿Está la palabra (normalizada) entre las extra?
+    pub fn contains(&self, normalized: &str) -> bool {
+        self.keys.contains(normalized)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Cómo se puntúa un candidato
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringMode {
+    /// Suma ponderada de Jaccard, posición, longitud y Levenshtein
+    Heuristic,
+    /// P(escrito | candidato) del modelo de errores (heurística si no hay modelo)
+    NoisyChannel,
 }
 
 /// Configuración del matcher
@@ -41,6 +102,9 @@ pub struct CharMatchConfig {
 
     /// Umbral mínimo de similitud
     pub min_similarity: f64,
+
+    /// Modo de puntuación
+    pub scoring: ScoringMode,
 }
 
 impl Default for CharMatchConfig {
@@ -52,6 +116,7 @@ impl Default for CharMatchConfig {
             weight_levenshtein: 0.30,
             max_candidates: 15,        // Más candidatos para considerar
             min_similarity: 0.25,      // Reducido - permite más candidatos semánticos
+            scoring: ScoringMode::Heuristic,
         }
     }
 }
@@ -87,6 +152,8 @@ impl CharMatcher {
             dictionary: HashSet::new(),
             letter_index: HashMap::new(),
             config: CharMatchConfig::default(),
+            error_model: None,
+            frequencies: HashMap::new(),
         }
     }
 
@@ -98,6 +165,17 @@ impl CharMatcher {
         }
     }
 
+    /// Instala un modelo de errores y puntúa con él
+    pub fn set_error_model(&mut self, model: ErrorModel) {
+        self.error_model = Some(model);
+        self.config.scoring = ScoringMode::NoisyChannel;
+    }
+
+    /// Modelo de errores instalado
+    pub fn error_model(&self) -> Option<&ErrorModel> {
+        self.error_model.as_ref()
+    }
+
     /// Carga diccionario de palabras
     pub fn load_dictionary<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, words: I) {
         for word in words {
@@ -121,19 +199,48 @@ impl CharMatcher {
         for c in normalized.chars() {
             self.letter_index
                 .entry(c)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(normalized.clone());
         }
     }
 
+    /// Fija la frecuencia de uso de una palabra
+    pub fn set_frequency(&mut self, word: &str, count: u64) {
+        self.frequencies.insert(normalize_word(word), count);
+    }
+
+    /// Frecuencia de uso de una palabra (0 si no se conoce)
+    pub fn frequency(&self, word: &str) -> u64 {
+        self.frequencies.get(&normalize_word(word)).copied().unwrap_or(0)
+    }
+
     /// Verifica si una palabra está en el diccionario
     pub fn is_valid(&self, word: &str) -> bool {
         let normalized = normalize_word(word);
         self.dictionary.contains(&normalized)
     }
 
+    /// ¿Palabra desconocida a una sola edición de una del diccionario?
+    /// ("colizeo" → "coliseo")
+    pub fn has_close_word(&self, word: &str) -> bool {
+        let normalized = normalize_word(word);
+        !self.dictionary.contains(&normalized)
+            && self.find_candidates(&normalized).iter().any(|c| levenshtein_distance(&normalized, &c.word) <= 1)
+    }
+
     /// Encuentra candidatos para una palabra (posiblemente mal escrita)
     pub fn find_candidates(&self, input: &str) -> Vec<MatchResult> {
+        self.find_candidates_with(input, &ExtraWords::default(), |_| false)
+    }
+
+    /// Como `find_candidates`, pero suma las palabras `extra` (se devuelven
+    /// tal como vienen) y descarta las que cumplan `exclude`
+    pub fn find_candidates_with<F: Fn(&str) -> bool>(
+        &self,
+        input: &str,
+        extra: &ExtraWords,
+        exclude: F,
+    ) -> Vec<MatchResult> {
         let normalized = normalize_word(input);
 
         if normalized.is_empty() {
@@ -141,7 +248,7 @@ impl CharMatcher {
         }
 
         // Si ya está en diccionario, retornar con score 1.0
-        if self.dictionary.contains(&normalized) {
+        if self.dictionary.contains(&normalized) && !exclude(&normalized) {
             return vec![MatchResult {
                 word: normalized,
                 score: 1.0,
@@ -175,15 +282,37 @@ impl CharMatcher {
             .map(|(word, _)| word)
             .collect();
 
+        // Lo mismo para las palabras extra, con su propio índice
+        let mut extra_scores: HashMap<usize, usize> = HashMap::new();
+        for c in &input_chars {
+            for &i in extra.letter_index.get(c).into_iter().flatten() {
+                *extra_scores.entry(i).or_insert(0) += 1;
+            }
+        }
+        let extra_candidates = extra_scores
+            .into_iter()
+            .filter(|(_, count)| *count >= min_shared.max(1))
+            .map(|(i, _)| (&extra.words[i], &extra.normalized[i]));
+
         // Calcular scores para cada candidato
         let mut results: Vec<MatchResult> = candidates
             .iter()
+            .filter(|candidate| !exclude(candidate) && !extra.contains(candidate))
             .map(|candidate| self.calculate_score(&normalized, candidate))
+            .chain(extra_candidates.filter(|(w, _)| !exclude(w)).map(|(word, key)| MatchResult {
+                word: word.clone(),
+                ..self.calculate_score(&normalized, key)
+            }))
             .filter(|r| r.score >= self.config.min_similarity)
             .collect();
 
-        // Ordenar por score descendente
-        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        // Ordenar por score descendente; empates: más frecuente, luego alfabético
+        results.sort_by(|a, b| {
+            b.score.partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| self.frequency(&b.word).cmp(&self.frequency(&a.word)))
+                .then_with(|| a.word.cmp(&b.word))
+        });
 
         // Limitar cantidad
         results.truncate(self.config.max_candidates);
@@ -200,10 +329,15 @@ impl CharMatcher {
             levenshtein: levenshtein_similarity(input, candidate),
         };
 
-        let score = self.config.weight_jaccard * breakdown.jaccard
-            + self.config.weight_positional * breakdown.positional
-            + self.config.weight_length * breakdown.length
-            + self.config.weight_levenshtein * breakdown.levenshtein;
+        let score = match (&self.config.scoring, &self.error_model) {
+            (ScoringMode::NoisyChannel, Some(model)) => model.score(input, candidate),
+            _ => {
+                self.config.weight_jaccard * breakdown.jaccard
+                    + self.config.weight_positional * breakdown.positional
+                    + self.config.weight_length * breakdown.length
+                    + self.config.weight_levenshtein * breakdown.levenshtein
+            }
+        };
 
         MatchResult {
             word: candidate.to_string(),
@@ -318,7 +452,7 @@ fn levenshtein_similarity(a: &str, b: &str) -> f64 {
 }
 
 /// Distancia de Levenshtein
-fn levenshtein_distance(a: &str, b: &str) -> usize {
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
     let a: Vec<char> = a.chars().collect();
     let b: Vec<char> = b.chars().collect();
 
@@ -335,11 +469,11 @@ fn levenshtein_distance(a: &str, b: &str) -> usize {
     // Matriz de DP
     let mut dp = vec![vec![0usize; n + 1]; m + 1];
 
-    for i in 0..=m {
-        dp[i][0] = i;
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=n {
-        dp[0][j] = j;
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     for i in 1..=m {
@@ -430,4 +564,53 @@ mod tests {
         assert_eq!(normalize_word("ROMA"), "roma");
         assert_eq!(normalize_word("España"), "espana");
     }
+
+    #[test]
+    fn test_noisy_channel_mode() {
+        let mut matcher = CharMatcher::new();
+        matcher.load_dictionary(vec!["amor", "roma", "ramo", "mora"]);
+
+        // Heurística: amor/roma/ramo/mora comparten letras con "rmoa"
+        let heuristic = matcher.find_candidates("rmoa");
+        assert!(heuristic.len() >= 3);
+
+        // Con el canal aprendido, la transposición m↔o apunta a "roma"
+        matcher.set_error_model(ErrorModel::from_pairs("rmoa roma\nmroa mora\n").unwrap());
+        let channel = matcher.find_candidates("rmoa");
+        assert_eq!(channel[0].word, "roma");
+        assert!(channel[1..].iter().all(|c| c.score < channel[0].score));
+    }
+
+    #[test]
+    fn test_frequency_tie_break() {
+        let mut matcher = CharMatcher::new();
+        matcher.load_dictionary(vec!["pesa", "besa"]);
+
+        // "tesa" está a la misma distancia de ambas: orden alfabético
+        let candidates = matcher.find_candidates("tesa");
+        assert_eq!(candidates[0].score, candidates[1].score);
+        assert_eq!(candidates[0].word, "besa");
+
+        // Con frecuencias gana la más usada
+        matcher.set_frequency("pesa", 900);
+        matcher.set_frequency("besa", 300);
+        assert_eq!(matcher.find_candidates("tesa")[0].word, "pesa");
+    }
+
+    #[test]
+    fn test_extra_words() {
+        let mut matcher = CharMatcher::new();
+        matcher.load_dictionary(vec!["amor", "roma"]);
+        let extra = ExtraWords::new(vec!["Sprint".to_string(), "xyz".to_string(), "amor".to_string()]);
+        assert!(extra.contains("sprint") && !extra.contains("Sprint"));
+
+        // Se devuelven tal como vienen; sin letras en común no se puntúan
+        let candidates = matcher.find_candidates_with("sprnt", &extra, |_| false);
+        assert_eq!(candidates[0].word, "Sprint");
+        assert!(candidates.iter().all(|c| c.word != "xyz"));
+
+        // Repetida en el diccionario: una sola vez
+        let candidates = matcher.find_candidates_with("amro", &extra, |_| false);
+        assert_eq!(candidates.iter().filter(|c| c.word == "amor").count(), 1);
+    }
 }
@@ -18,6 +18,14 @@
 
 use std::collections::{HashMap, HashSet};
 use crate::dictionary::{SpanishDictionary, PartOfSpeech};
+use crate::grammar::clauses::{self, ClauseKind};
+use crate::grammar::SpanishGrammar;
+use crate::grammar::mood;
+use crate::protect::{Segment, SpanProtector};
+use crate::tao::{Interrogative, SentenceMood};
+
+/// Artículos (y contracciones) que anteceden a un sustantivo
+const ARTICLES: [&str; 10] = ["el", "la", "los", "las", "un", "una", "unos", "unas", "al", "del"];
 
 /// Comando parseado desde lenguaje natural
 #[derive(Debug, Clone)]
@@ -26,6 +34,8 @@ pub struct ParsedCommand {
     pub original: String,
     /// Acción principal solicitada
     pub action: CommandAction,
+    /// Modalidad: las preguntas son solicitudes de información
+    pub mood: SentenceMood,
     /// Quién solicita (normalmente USER)
     pub requester: SemanticRole,
     /// Quién ejecuta (normalmente AGENT)
@@ -42,6 +52,17 @@ pub struct ParsedCommand {
     pub confidence: f64,
 }
 
+/// Cláusula de un comando con su propio análisis
+#[derive(Debug, Clone)]
+pub struct ParsedClause {
+    /// Principal, completiva, relativa, adverbial o coordinada
+    pub kind: ClauseKind,
+    /// Cláusula de la que depende (índice en el resultado)
+    pub parent: Option<usize>,
+    /// Comando de la cláusula (original = texto de la cláusula)
+    pub command: ParsedCommand,
+}
+
 /// Acción del comando
 #[derive(Debug, Clone, PartialEq)]
 pub enum CommandAction {
@@ -75,6 +96,11 @@ pub enum CommandAction {
     Compute {
         verb: String,
     },
+    /// Solicitud de información (¿dónde está...?, ¿qué es...?)
+    Query {
+        /// Interrogativo; None en preguntas de sí o no
+        interrogative: Option<Interrogative>,
+    },
     /// Desconocida
     Unknown,
 }
@@ -284,6 +310,10 @@ pub struct CommandParser {
     known_nouns: HashSet<String>,
     /// Frecuencias de palabras (para priorización)
     frequencies: HashMap<String, u64>,
+    /// Gramática para la modalidad de la oración
+    grammar: SpanishGrammar,
+    /// Tramos protegidos (URLs, código...) que no cuentan para la modalidad
+    protector: SpanProtector,
     /// Estadísticas
     pub stats: ParserStats,
 }
@@ -315,6 +345,8 @@ impl CommandParser {
             common_attributes: HashMap::new(),
             known_nouns: HashSet::new(),
             frequencies: HashMap::new(),
+            grammar: SpanishGrammar::new(),
+            protector: SpanProtector::new(),
             stats: ParserStats::default(),
         };
 
@@ -357,21 +389,21 @@ impl CommandParser {
                 }
 
                 // Verbos - agregar como verbos de acción si no están ya
-                if entry.pos.contains(&PartOfSpeech::Verb) {
-                    if !self.action_verbs.contains_key(word) {
-                        // Categorizar por definición
-                        let category = self.categorize_verb_from_definition(&entry.definitions);
-                        self.action_verbs.insert(word.clone(), category);
-                    }
+                if entry.pos.contains(&PartOfSpeech::Verb)
+                    && !self.action_verbs.contains_key(word)
+                {
+                    // Categorizar por definición
+                    let category = self.categorize_verb_from_definition(&entry.definitions);
+                    self.action_verbs.insert(word.clone(), category);
                 }
 
                 // Adjetivos como posibles atributos
-                if entry.pos.contains(&PartOfSpeech::Adjective) {
-                    if !self.common_attributes.contains_key(word) {
-                        // Inferir atributo desde la palabra
-                        let attr = self.infer_attribute_type(word, &entry.definitions);
-                        self.common_attributes.insert(word.clone(), attr);
-                    }
+                if entry.pos.contains(&PartOfSpeech::Adjective)
+                    && !self.common_attributes.contains_key(word)
+                {
+                    // Inferir atributo desde la palabra
+                    let attr = self.infer_attribute_type(word, &entry.definitions);
+                    self.common_attributes.insert(word.clone(), attr);
                 }
             }
 
@@ -637,14 +669,18 @@ impl CommandParser {
         // 1. Encontrar verbos y analizarlos
         let verbs = self.find_verbs(&tokens);
 
-        // 2. Determinar acción principal
-        let action = self.determine_action(&verbs, &tokens);
+        // 2. Modalidad y acción principal
+        let mood = self.determine_mood(text, &verbs);
+        let action = self.determine_action(&verbs, &tokens, &mood);
 
         // 3. Determinar roles (requester, executor)
         let (requester, executor) = self.determine_roles(&verbs);
 
-        // 4. Encontrar target
-        let target = self.find_target(&tokens);
+        // 4. Encontrar target (en preguntas, el tema consultado)
+        let mut target = self.find_target(&tokens);
+        if matches!(action, CommandAction::Query { .. }) && matches!(target, CommandTarget::None) {
+            target = self.find_query_topic(&tokens);
+        }
 
         // 5. Encontrar goal/propósito
         let goal = self.find_goal(&tokens);
@@ -658,6 +694,7 @@ impl CommandParser {
         ParsedCommand {
             original: text.to_string(),
             action,
+            mood,
             requester,
             executor,
             target,
@@ -668,6 +705,46 @@ impl CommandParser {
         }
     }
 
+    /// Parsea cada cláusula por separado: "Requiero que me diseñes un producto
+    /// que sustituya al propofol" → principal, completiva y relativa
+    pub fn parse_clauses(&self, text: &str) -> Vec<ParsedClause> {
+        let tokens = self.tokenize(&text.to_lowercase());
+        let finite: Vec<bool> = (0..tokens.len()).map(|i| self.is_finite_verb(&tokens, i)).collect();
+        let nominal: Vec<bool> = (0..tokens.len()).map(|i| {
+            self.is_noun(&tokens[i])
+                || (i > 0 && ARTICLES.contains(&tokens[i - 1].as_str()) && !finite[i])
+        }).collect();
+
+        clauses::segment(&tokens, &finite, &nominal).clauses.into_iter()
+            .map(|clause| ParsedClause {
+                command: self.parse(&clause.text(&tokens)),
+                kind: clause.kind,
+                parent: clause.parent,
+            })
+            .collect()
+    }
+
+    /// Verbo conjugado: de solicitud, conjugación conocida, o forma verbal
+    /// tras "que"/clítico ("que sustituya", "me ayude")
+    fn is_finite_verb(&self, tokens: &[String], i: usize) -> bool {
+        let token = tokens[i].as_str();
+        if self.request_verbs.contains_key(token) || self.conjugations.contains_key(token) {
+            return true;
+        }
+        if self.is_noun(token) || ARTICLES.contains(&token) || token.chars().count() < 3 {
+            return false;
+        }
+
+        let after_verb_marker = i > 0 && matches!(
+            tokens[i - 1].as_str(),
+            "que" | "me" | "te" | "se" | "nos" | "os" | "le" | "les" | "lo" | "los" | "no"
+        );
+        let verb_ending = ["a", "e", "o", "as", "es", "an", "en", "amos", "emos", "imos"]
+            .iter()
+            .any(|e| token.ends_with(e));
+        after_verb_marker && verb_ending
+    }
+
     /// Tokeniza el texto
     fn tokenize(&self, text: &str) -> Vec<String> {
         text.split_whitespace()
@@ -726,7 +803,7 @@ impl CommandParser {
             if token.ends_with("es") && tokens.get(i.saturating_sub(1)).map(|s| s.as_str()) == Some("que") {
                 // Probable subjuntivo
                 let base = &token[..token.len()-2];
-                for (lemma, _) in &self.action_verbs {
+                for lemma in self.action_verbs.keys() {
                     if lemma.starts_with(base) {
                         verbs.push(VerbAnalysis {
                             conjugated: token.clone(),
@@ -745,27 +822,62 @@ impl CommandParser {
             }
 
             // Detectar infinitivos
-            if token.ends_with("ar") || token.ends_with("er") || token.ends_with("ir") {
-                if self.action_verbs.contains_key(token) {
-                    verbs.push(VerbAnalysis {
-                        conjugated: token.clone(),
-                        lemma: token.clone(),
-                        person: 0, // infinitivo no tiene persona
-                        number: GrammaticalNumber::Singular,
-                        mode: VerbMode::Infinitive,
-                        tense: VerbTense::Present,
-                        position: i,
-                        semantic_role: VerbSemanticRole::Purpose,
-                    });
-                }
+            if (token.ends_with("ar") || token.ends_with("er") || token.ends_with("ir"))
+                && self.action_verbs.contains_key(token)
+            {
+                verbs.push(VerbAnalysis {
+                    conjugated: token.clone(),
+                    lemma: token.clone(),
+                    person: 0, // infinitivo no tiene persona
+                    number: GrammaticalNumber::Singular,
+                    mode: VerbMode::Infinitive,
+                    tense: VerbTense::Present,
+                    position: i,
+                    semantic_role: VerbSemanticRole::Purpose,
+                });
             }
         }
 
         verbs
     }
 
+    /// Determina la modalidad con la gramática (sin los tramos protegidos);
+    /// los imperativos que reconoce el parser ("ayúdame") también cuentan
+    fn determine_mood(&self, text: &str, verbs: &[VerbAnalysis]) -> SentenceMood {
+        let raw: Vec<String> = self.protector.segment(text).into_iter()
+            .flat_map(|segment| match segment {
+                Segment::Text(words) => words.split_whitespace().map(String::from).collect(),
+                Segment::Protected(_) => Vec::new(),
+            })
+            .collect();
+
+        let detected = mood::detect_mood(&raw, &self.grammar.classify_sentence(&raw));
+        let imperative = verbs.iter().any(|v| v.mode == VerbMode::Imperative);
+        match detected {
+            SentenceMood::Declarative | SentenceMood::Exclamative if imperative => SentenceMood::Imperative,
+            mood => mood,
+        }
+    }
+
     /// Determina la acción principal del comando
-    fn determine_action(&self, verbs: &[VerbAnalysis], tokens: &[String]) -> CommandAction {
+    fn determine_action(&self, verbs: &[VerbAnalysis], tokens: &[String], mood: &SentenceMood) -> CommandAction {
+        // Pregunta parcial: siempre solicitud de información
+        if let SentenceMood::Question(kind) = mood {
+            return CommandAction::Query { interrogative: Some(*kind) };
+        }
+
+        let action = self.determine_verb_action(verbs, tokens);
+
+        // Pregunta de sí o no sin verbo de acción ("¿es seguro el propofol?")
+        if *mood == SentenceMood::YesNoQuestion && action == CommandAction::Unknown {
+            return CommandAction::Query { interrogative: None };
+        }
+
+        action
+    }
+
+    /// Acción según los verbos de solicitud, de acción o imperativos
+    fn determine_verb_action(&self, verbs: &[VerbAnalysis], tokens: &[String]) -> CommandAction {
         // Buscar verbo de solicitud primero (1a persona)
         for verb in verbs {
             if verb.semantic_role == VerbSemanticRole::Request {
@@ -880,6 +992,35 @@ impl CommandParser {
         CommandTarget::None
     }
 
+    /// Tema de una pregunta: palabras de contenido tras el interrogativo y el verbo
+    /// ("¿dónde está el coliseo romano?" → "coliseo_romano")
+    fn find_query_topic(&self, tokens: &[String]) -> CommandTarget {
+        let function_words = [
+            "el", "la", "los", "las", "lo", "un", "una", "unos", "unas", "del", "al",
+            "a", "de", "en", "con", "por", "para", "sobre", "desde", "hasta",
+            "es", "son", "está", "están", "era", "fue", "hay", "sea", "se",
+            "me", "te", "le", "nos", "les", "que",
+        ];
+
+        let content: Vec<&String> = tokens.iter()
+            .skip_while(|t| Interrogative::from_word(t).is_some() || function_words.contains(&t.as_str()))
+            .filter(|t| !self.conjugations.contains_key(*t) && !self.request_verbs.contains_key(*t))
+            .collect();
+
+        // Sin palabras funcionales en los extremos: "capital de francia"
+        let start = content.iter().position(|t| !function_words.contains(&t.as_str()));
+        let end = content.iter().rposition(|t| !function_words.contains(&t.as_str()));
+        let topic = match (start, end) {
+            (Some(s), Some(e)) => content[s..=e].iter().map(|t| t.as_str()).collect::<Vec<_>>().join("_"),
+            _ => return CommandTarget::None,
+        };
+
+        CommandTarget::Known {
+            category: self.infer_category(&topic),
+            name: topic,
+        }
+    }
+
     /// Infiere categoría de un sustantivo
     fn infer_category(&self, word: &str) -> Option<String> {
         let categories: HashMap<&str, &str> = [
@@ -1117,6 +1258,19 @@ impl ParsedCommand {
                     ],
                 });
             }
+            CommandAction::Query { interrogative } => {
+                let kind = interrogative.map(|i| i.as_str()).unwrap_or("whether");
+                predicates.push(PirsPredicate {
+                    name: "query".to_string(),
+                    args: vec![
+                        PirsArg::Atom("user".to_string()),
+                        PirsArg::Atom("agent".to_string()),
+                        PirsArg::Term(kind.to_string(), vec![
+                            PirsArg::Variable("Target".to_string()),
+                        ]),
+                    ],
+                });
+            }
             CommandAction::Unknown => {}
         }
 
@@ -1295,6 +1449,27 @@ impl PirsArg {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_clauses() {
+        let parser = CommandParser::new();
+        let clauses = parser.parse_clauses("Requiero que me diseñes un producto que sustituya al propofol");
+
+        assert_eq!(clauses.len(), 3);
+        assert_eq!(clauses[0].kind, ClauseKind::Main);
+        assert!(matches!(clauses[0].command.action, CommandAction::Request { .. }));
+
+        assert_eq!(clauses[1].kind, ClauseKind::Complement);
+        assert_eq!(clauses[1].parent, Some(0));
+        assert_eq!(clauses[1].command.original, "que me diseñes un producto");
+
+        assert_eq!(clauses[2].kind, ClauseKind::Relative);
+        assert_eq!(clauses[2].parent, Some(1));
+        assert_eq!(clauses[2].command.original, "que sustituya al propofol");
+
+        // Sin subordinadas, una sola cláusula
+        assert_eq!(parser.parse_clauses("Busco compuestos más seguros que el fentanilo").len(), 1);
+    }
+
     #[test]
     fn test_parse_request() {
         let parser = CommandParser::new();
@@ -1334,7 +1509,7 @@ mod tests {
         let parser = CommandParser::new();
         let cmd = parser.parse("Necesito algo súper seguro y más barato");
 
-        assert!(cmd.constraints.len() >= 1);
+        assert!(!cmd.constraints.is_empty());
 
         // Verificar que encontró el superlativo
         let has_superlative = cmd.constraints.iter().any(|c|
@@ -1387,4 +1562,41 @@ mod tests {
         // La confianza debe ser > 0
         assert!(cmd.confidence > 0.0);
     }
+
+    #[test]
+    fn test_questions_are_information_requests() {
+        let parser = CommandParser::new();
+
+        let cmd = parser.parse("¿Dónde está el Coliseo romano?");
+        assert_eq!(cmd.mood, SentenceMood::Question(Interrogative::Where));
+        assert_eq!(cmd.action, CommandAction::Query { interrogative: Some(Interrogative::Where) });
+        assert!(matches!(&cmd.target, CommandTarget::Known { name, .. } if name == "coliseo_romano"));
+        assert!(cmd.to_prolog_string().contains("query(user, agent, where(Target))"));
+
+        // Sin signos, el interrogativo con tilde basta
+        let cmd = parser.parse("qué es el propofol");
+        assert_eq!(cmd.action, CommandAction::Query { interrogative: Some(Interrogative::What) });
+
+        let cmd = parser.parse("¿Es seguro el propofol?");
+        assert_eq!(cmd.mood, SentenceMood::YesNoQuestion);
+        assert_eq!(cmd.action, CommandAction::Query { interrogative: None });
+
+        // Los comandos no cambian
+        let cmd = parser.parse("Requiero que me diseñes un producto");
+        assert_eq!(cmd.mood, SentenceMood::Declarative);
+        assert!(matches!(cmd.action, CommandAction::Request { .. }));
+    }
+
+    #[test]
+    fn test_mood_from_grammar() {
+        let parser = CommandParser::new();
+
+        assert_eq!(parser.parse("No me lo digas").mood, SentenceMood::Imperative);
+        assert_eq!(parser.parse("Dámelo ahora").mood, SentenceMood::Imperative);
+        assert_eq!(parser.parse("¡Qué bonito!").mood, SentenceMood::Exclamative);
+
+        // El "?" de una URL no convierte la oración en pregunta
+        assert_eq!(parser.parse("mira https://x.com/a?b=c").mood, SentenceMood::Declarative);
+        assert_eq!(parser.parse("¿Conoces https://x.com/a?b=c?").mood, SentenceMood::YesNoQuestion);
+    }
 }
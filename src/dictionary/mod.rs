@@ -130,7 +130,7 @@ pub enum Region {
 }
 
 /// Diccionario completo del español
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SpanishDictionary {
     /// Entradas por palabra normalizada
     entries: HashMap<String, Vec<DictionaryEntry>>,
@@ -138,6 +138,8 @@ pub struct SpanishDictionary {
     valid_words: HashSet<String>,
     /// Frecuencias de palabras
     frequencies: HashMap<String, u64>,
+    /// Frecuencia máxima (escala del prior)
+    max_frequency: u64,
     /// Formas conjugadas -> lema
     conjugations: HashMap<String, String>,
     /// Estadísticas
@@ -161,6 +163,7 @@ impl SpanishDictionary {
             entries: HashMap::new(),
             valid_words: HashSet::new(),
             frequencies: HashMap::new(),
+            max_frequency: 0,
             conjugations: HashMap::new(),
             stats: DictionaryStats::default(),
         }
@@ -194,7 +197,7 @@ impl SpanishDictionary {
 
         // Parse JSON manualmente (sin serde para zero-deps)
         let content: String = reader.lines()
-            .filter_map(|l| l.ok())
+            .map_while(Result::ok)
             .collect::<Vec<_>>()
             .join("\n");
 
@@ -204,7 +207,7 @@ impl SpanishDictionary {
         for entry in entries {
             let normalized = normalize_word(&entry.word);
             self.valid_words.insert(normalized.clone());
-            self.entries.entry(normalized).or_insert_with(Vec::new).push(entry);
+            self.entries.entry(normalized).or_default().push(entry);
             self.stats.rae_entries += 1;
         }
 
@@ -232,6 +235,7 @@ impl SpanishDictionary {
                     let normalized = normalize_word(&word);
 
                     self.frequencies.insert(normalized.clone(), count);
+                    self.max_frequency = self.max_frequency.max(count);
                     self.valid_words.insert(normalized.clone());
 
                     // Parse conjugations
@@ -279,6 +283,28 @@ impl SpanishDictionary {
         0
     }
 
+    /// Prior de frecuencia (0.0 - 1.0) en escala logarítmica:
+    /// ln(f + 2) / ln(f_max + 2). Las palabras sin frecuencia reciben un
+    /// valor pequeño pero no nulo.
+    pub fn frequency_prior(&self, word: &str) -> f64 {
+        let scale = (self.max_frequency as f64 + 2.0).ln();
+        (self.frequency(word) as f64 + 2.0).ln() / scale
+    }
+
+    /// ¿Hay datos de frecuencia cargados?
+    pub fn has_frequencies(&self) -> bool {
+        !self.frequencies.is_empty()
+    }
+
+    /// Fijar la frecuencia de una palabra (lema o forma)
+    pub fn set_frequency(&mut self, word: &str, count: u64) {
+        let normalized = normalize_word(word);
+        self.frequencies.insert(normalized.clone(), count);
+        self.max_frequency = self.max_frequency.max(count);
+        self.valid_words.insert(normalized);
+        self.stats.total_entries = self.valid_words.len();
+    }
+
     /// Obtener lema de una forma conjugada
     pub fn get_lemma(&self, word: &str) -> Option<String> {
         let normalized = normalize_word(word);
@@ -324,7 +350,7 @@ impl SpanishDictionary {
             semantic_category: None,
         };
         self.valid_words.insert(normalized.clone());
-        self.entries.entry(normalized).or_insert_with(Vec::new).push(entry);
+        self.entries.entry(normalized).or_default().push(entry);
         self.stats.total_entries = self.valid_words.len();
     }
 }
@@ -472,10 +498,8 @@ fn parse_rae_json(content: &str) -> Result<Vec<DictionaryEntry>, DictionaryError
             ':' => {
                 // Key ya está en current_key
             }
-            ',' => {
-                if !in_array {
-                    current_key.clear();
-                }
+            ',' if !in_array => {
+                current_key.clear();
             }
             _ => {}
         }
@@ -512,4 +536,20 @@ mod tests {
         assert!(dict.is_valid("Casa"));
         assert!(!dict.is_valid("xyz"));
     }
+
+    #[test]
+    fn test_frequency_prior() {
+        let mut dict = SpanishDictionary::new();
+        assert!(!dict.has_frequencies());
+
+        dict.set_frequency("casa", 50_000);
+        dict.set_frequency("caca", 40);
+        dict.add_word("cafa", vec![PartOfSpeech::Noun], Region::Standard);
+
+        assert!((dict.frequency_prior("casa") - 1.0).abs() < 1e-9);
+        assert!(dict.frequency_prior("caca") < dict.frequency_prior("casa"));
+        // Sin frecuencia: pequeño pero no nulo
+        assert!(dict.frequency_prior("cafa") > 0.0);
+        assert!(dict.frequency_prior("cafa") < dict.frequency_prior("caca"));
+    }
 }
@@ -0,0 +1,168 @@
+//! # Batch Processing
+//!
+//! Procesa muchas oraciones repartidas entre hilos (`std::thread::scope`).
+//! Cada hilo toma un tramo contiguo de la entrada y los resultados se unen
+//! en orden, así que la salida es idéntica a procesar en secuencia.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{RequestContext, SemanticDisambiguator};
+use crate::ProcessedSentence;
+
+/// Estadísticas de un lote
+#[derive(Debug, Clone, Default)]
+pub struct BatchStats {
+    /// Oraciones procesadas
+    pub sentences: usize,
+    /// Oraciones con al menos una corrección
+    pub corrected_sentences: usize,
+    /// Correcciones aplicadas en total
+    pub corrections: usize,
+    /// Hilos usados
+    pub threads: usize,
+    /// Tiempo total
+    pub elapsed: Duration,
+}
+
+impl BatchStats {
+    /// Oraciones por segundo
+    pub fn sentences_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.sentences as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+impl SemanticDisambiguator {
+    /// Procesa un lote de oraciones con todos los núcleos disponibles,
+    /// conservando el orden de la entrada
+    pub fn process_batch(&self, sentences: &[&str]) -> Vec<ProcessedSentence> {
+        let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        self.process_batch_with(sentences, threads).0
+    }
+
+    /// Procesa un lote con `threads` hilos como máximo; devuelve también
+    /// las estadísticas de rendimiento
+    pub fn process_batch_with(
+        &self,
+        sentences: &[&str],
+        threads: usize,
+    ) -> (Vec<ProcessedSentence>, BatchStats) {
+        self.process_batch_in(sentences, threads, &RequestContext::new())
+    }
+
+    /// Como `process_batch_with`, con el estado de un cliente o usuario
+    /// (capas de diccionario, feedback). Cada hilo trabaja sobre su propia
+    /// copia de `request` (la capa del cliente se comparte por `Arc`)
+    pub fn process_batch_in(
+        &self,
+        sentences: &[&str],
+        threads: usize,
+        request: &RequestContext,
+    ) -> (Vec<ProcessedSentence>, BatchStats) {
+        let start = Instant::now();
+        let threads = threads.clamp(1, sentences.len().max(1));
+        let chunk_size = sentences.len().div_ceil(threads).max(1);
+        let process_chunk = |chunk: &[&str]| {
+            let mut request = request.clone();
+            chunk.iter().map(|s| self.process_with(s, &mut request)).collect::<Vec<_>>()
+        };
+
+        // Hilos realmente lanzados: uno por tramo
+        let (results, workers): (Vec<ProcessedSentence>, usize) = if threads == 1 {
+            (process_chunk(sentences), 1)
+        } else {
+            thread::scope(|scope| {
+                let workers: Vec<_> = sentences
+                    .chunks(chunk_size)
+                    .map(|chunk| scope.spawn(move || process_chunk(chunk)))
+                    .collect();
+                let spawned = workers.len();
+
+                let results = workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().expect("hilo del lote falló"))
+                    .collect();
+                (results, spawned)
+            })
+        };
+
+        let stats = BatchStats {
+            sentences: results.len(),
+            corrected_sentences: results.iter().filter(|r| !r.corrections.is_empty()).count(),
+            corrections: results.iter().map(|r| r.corrections.len()).sum(),
+            threads: workers,
+            elapsed: start.elapsed(),
+        };
+
+        (results, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_matches_sequential() {
+        let d = SemanticDisambiguator::new();
+        let sentences: Vec<&str> = [
+            "Visité el Coliseo romano en smor",
+            "te quiero smor",
+            "la casa es azul",
+            "viajé a smor",
+            "el perro come",
+        ]
+        .iter()
+        .cycle()
+        .take(23)
+        .copied()
+        .collect();
+
+        // Resultado completo: correcciones, confianzas y entidades
+        let sequential: Vec<ProcessedSentence> = sentences.iter().map(|s| d.process(s)).collect();
+        for (threads, spawned) in [(1, 1), (2, 2), (4, 4), (5, 5), (7, 6), (64, 23)] {
+            let (results, stats) = d.process_batch_with(&sentences, threads);
+            assert_eq!(results, sequential);
+            assert_eq!(stats.sentences, 23);
+            assert_eq!(stats.threads, spawned);
+        }
+    }
+
+    #[test]
+    fn test_batch_stats() {
+        let d = SemanticDisambiguator::new();
+        let (results, stats) = d.process_batch_with(&["la casa es azul", "te quiero smor"], 2);
+
+        assert_eq!(results[0].original, "la casa es azul");
+        assert_eq!(stats.corrected_sentences, 1);
+        assert_eq!(stats.corrections, results[1].corrections.len());
+        assert!(d.process_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_batch_with_request_state() {
+        use std::sync::Arc;
+        use crate::UserDictionary;
+
+        let d = SemanticDisambiguator::new();
+        let mut tenant = UserDictionary::new("acme");
+        tenant.add("despliegue");
+        let request = RequestContext::for_tenant(Arc::new(tenant));
+
+        let sentences = ["el despleigue es azul", "la casa es azul", "el despliegue es azul"];
+        let (results, _) = d.process_batch_in(&sentences, 3, &request);
+        let sequential: Vec<ProcessedSentence> = sentences
+            .iter()
+            .map(|s| d.process_with(s, &mut request.clone()))
+            .collect();
+        assert_eq!(results, sequential);
+        assert_eq!(results[0].corrected, "el despliegue es azul");
+        assert!(results[2].corrections.is_empty());
+        assert_ne!(d.process_batch(&sentences)[0].corrected, "el despliegue es azul");
+    }
+}
@@ -1,6 +1,8 @@
 //! # Semantic Disambiguator
 //!
 //! Motor principal de desambiguación semántica.
+//! Los recursos de solo lectura viven en `DisambiguationModel` (ver
+//! `model`), compartible entre motores con `Arc`.
 //! Integra todos los componentes:
 //! - UNIFORM kernel para unificación
 //! - APPLOG para contexto compartido
@@ -9,14 +11,112 @@
 //! - SpanishGrammar para validación gramatical
 //! - SemanticDB para análisis de contexto
 //! - SpanishDictionary para diccionario completo RAE/LATAM
+//! - NgramModel (opcional) para P(candidato | contexto)
+//! - EntityRecognizer para no corregir nombres propios
+//! - SpanProtector para no tocar URLs, correos, números, código...
+//! - LayeredDictionary para palabras de cliente y de sesión
+//! - FeedbackStore (por usuario, en RequestContext) para aprender de
+//!   correcciones aceptadas o rechazadas
+
+pub mod batch;
+pub mod model;
+
+use std::sync::Arc;
 
 use crate::{Config, ProcessedSentence, Correction, CorrectionExplanation};
-use crate::chars::CharMatcher;
+use crate::chars::{ExtraWords, MatchResult};
+use model::DisambiguationModel;
+use crate::chars::channel::ErrorModel;
 use crate::grammar::SpanishGrammar;
+use crate::grammar::clitics::Region;
+use crate::grammar::lexicon::Lexicon;
+use crate::grammar::tagger::PosTagger;
 use crate::semantic::SemanticDB;
-use crate::applog::{SharedContext, Source};
+use crate::applog::{SharedContext, Source, ValidationError, REQUEST_HISTORY_LIMIT};
 use crate::uniform::UnifyValue;
 use crate::dictionary::SpanishDictionary;
+use crate::ngram::NgramModel;
+use crate::ner::{EntityKind, EntityRecognizer};
+use crate::protect::{Segment, SpanProtector};
+use crate::feedback::{FeedbackEvent, FeedbackStore, PairStats};
+use crate::userdict::{DictionaryLayer, LayeredDictionary, LayerStats, UserDictionary, WordAction};
+
+/// Rechazos (sin ninguna aceptación) tras los que una palabra del usuario
+/// entra en su diccionario personal
+pub const PERSONAL_WORD_REJECTIONS: u32 = 3;
+
+/// Candidato con el desglose de sus scores
+struct ScoredCandidate {
+    word: String,
+    total: f64,
+    char_score: f64,
+    grammar_score: f64,
+    context_score: f64,
+    lm_score: f64,
+    frequency_score: f64,
+    feedback_score: f64,
+}
+
+/// Contexto de una oración, común a todas sus anomalías
+struct SentenceContext<'a> {
+    tokens: &'a [String],
+    content_words: &'a [String],
+    themes: &'a [(String, f64)],
+    feedback: &'a FeedbackStore,
+}
+
+/// Estado de una petición o de un usuario: lo que `process_with` escribe
+/// mientras trabaja y lo que aprende `record_feedback`. El motor no cambia
+/// al procesar, así que se puede compartir entre hilos
+/// (`Arc<SemanticDisambiguator>`) con un `RequestContext` por usuario.
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// Variables de la petición (`current_theme`, escrito por
+    /// `Source::Semantic`) y contadores de feedback (`Source::User`)
+    pub shared: SharedContext,
+    /// Feedback del usuario sobre correcciones anteriores
+    pub feedback: FeedbackStore,
+    /// Capas de diccionario de esta petición (si no, las del motor). La
+    /// capa del cliente va en un `Arc`: no se copia por petición
+    pub layers: Option<LayeredDictionary>,
+}
+
+impl RequestContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Petición con las capas de un cliente o sesión propias
+    pub fn with_layers(layers: LayeredDictionary) -> Self {
+        Self { layers: Some(layers), ..Self::default() }
+    }
+
+    /// Petición de un cliente: comparte su capa (`Arc`, sin copiarla) y
+    /// empieza con la sesión vacía
+    pub fn for_tenant(tenant: Arc<UserDictionary>) -> Self {
+        Self::with_layers(LayeredDictionary::with_tenant(tenant))
+    }
+
+    /// Recupera el feedback guardado en un contexto compartido (p. ej. el
+    /// de una sesión anterior)
+    pub fn restore_feedback(&mut self, context: &SharedContext) -> Result<(), ValidationError> {
+        self.feedback = FeedbackStore::from_context(context);
+        for (key, value) in context.bindings_from(Source::User) {
+            self.shared.set(key, value.value.clone(), Source::User, value.confidence)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for RequestContext {
+    fn default() -> Self {
+        // El feedback de un usuario crece sin fin: el historial de rollback
+        // de su contexto se acota
+        let mut shared = SharedContext::new();
+        shared.set_history_limit(REQUEST_HISTORY_LIMIT);
+        Self { shared, feedback: FeedbackStore::new(), layers: None }
+    }
+}
 
 /// Motor de desambiguación semántica
 #[derive(Debug)]
@@ -24,38 +124,18 @@ pub struct SemanticDisambiguator {
     /// Configuración
     config: Config,
 
-    /// Matcher de caracteres
-    char_matcher: CharMatcher,
-
-    /// Gramática española
-    grammar: SpanishGrammar,
-
-    /// Base de datos semántica
-    semantic_db: SemanticDB,
+    /// Recursos de solo lectura (compartibles entre motores)
+    model: Arc<DisambiguationModel>,
 
-    /// Contexto compartido (APPLOG)
-    shared_context: SharedContext,
+    /// Capas de usuario (cliente y sesión) sobre el diccionario base
+    layers: LayeredDictionary,
 
-    /// Diccionario completo (opcional, para carga desde archivo)
-    dictionary: Option<SpanishDictionary>,
 }
 
 impl SemanticDisambiguator {
     /// Crea desambiguador con configuración por defecto
     pub fn new() -> Self {
-        let mut disambiguator = Self {
-            config: Config::default(),
-            char_matcher: CharMatcher::new(),
-            grammar: SpanishGrammar::new(),
-            semantic_db: SemanticDB::new(),
-            shared_context: SharedContext::new(),
-            dictionary: None,
-        };
-
-        // Cargar diccionario inicial
-        disambiguator.load_default_dictionary();
-
-        disambiguator
+        Self::with_model(Arc::new(DisambiguationModel::new()))
     }
 
     /// Crea con configuración personalizada
@@ -67,22 +147,7 @@ impl SemanticDisambiguator {
 
     /// Crea con diccionario externo (RAE/LATAM)
     pub fn with_dictionary(dict: SpanishDictionary) -> Self {
-        let mut disambiguator = Self {
-            config: Config::default(),
-            char_matcher: CharMatcher::new(),
-            grammar: SpanishGrammar::new(),
-            semantic_db: SemanticDB::new(),
-            shared_context: SharedContext::new(),
-            dictionary: Some(dict),
-        };
-
-        // Cargar palabras del diccionario al CharMatcher
-        disambiguator.load_from_spanish_dictionary();
-
-        // Cargar también palabras gramaticales básicas
-        disambiguator.load_grammar_words();
-
-        disambiguator
+        Self::with_model(Arc::new(DisambiguationModel::with_dictionary(dict)))
     }
 
     /// Crea con diccionario y configuración
@@ -92,164 +157,65 @@ impl SemanticDisambiguator {
         d
     }
 
-    /// Carga palabras desde SpanishDictionary al CharMatcher
-    fn load_from_spanish_dictionary(&mut self) {
-        if let Some(ref dict) = self.dictionary {
-            // Cargar todas las palabras válidas al CharMatcher
-            let words: Vec<String> = dict.all_words().cloned().collect();
-            self.char_matcher.load_dictionary(words.iter().map(|s| s.as_str()));
-
-            // Añadir sustantivos a la gramática para los que tenemos información
-            use crate::grammar::{NounInfo, Gender, Number, NounCategory};
-            use crate::dictionary::PartOfSpeech;
-
-            for word in dict.all_words() {
-                for entry in dict.get_entries(word) {
-                    // Si es sustantivo, añadirlo a la gramática
-                    if entry.pos.contains(&PartOfSpeech::Noun) {
-                        // Inferir género del artículo en definiciones o de la terminación
-                        let gender = if word.ends_with('a') || word.ends_with("ión") || word.ends_with("dad") {
-                            Gender::Feminine
-                        } else {
-                            Gender::Masculine
-                        };
-
-                        self.grammar.add_noun(&entry.original, NounInfo {
-                            gender,
-                            number: Number::Singular,
-                            category: NounCategory::Thing,
-                            can_be_subject: true,
-                            can_be_object: true,
-                        });
-                    }
-
-                    // Si es adjetivo, añadirlo
-                    if entry.pos.contains(&PartOfSpeech::Adjective) {
-                        self.grammar.add_adjective(&entry.original);
-                    }
-                }
-            }
+    /// Crea sobre un modelo ya cargado (p. ej. compartido con otros motores)
+    pub fn with_model(model: Arc<DisambiguationModel>) -> Self {
+        Self {
+            config: Config::default(),
+            model,
+            layers: LayeredDictionary::new(),
         }
     }
 
-    /// Carga palabras gramaticales básicas (artículos, preposiciones, etc.)
-    fn load_grammar_words(&mut self) {
-        let grammar_words = [
-            "el", "la", "los", "las", "un", "una", "unos", "unas",
-            "yo", "tú", "él", "ella", "nosotros", "me", "te", "le", "se",
-            "a", "ante", "bajo", "con", "contra", "de", "desde", "en",
-            "entre", "hacia", "hasta", "para", "por", "según", "sin",
-            "sobre", "tras", "y", "e", "o", "u", "pero", "sino", "que",
-            "porque", "aunque", "si", "cuando", "donde", "como",
-            "muy", "bien", "mal", "mucho", "poco", "siempre", "nunca",
-            "ya", "todavía", "aquí", "allí", "ahora", "después", "antes",
-            "también", "tampoco", "sí", "no",
-        ];
+    /// Recursos de solo lectura del motor
+    pub fn model(&self) -> &Arc<DisambiguationModel> {
+        &self.model
+    }
 
-        self.char_matcher.load_dictionary(grammar_words.iter().copied());
+    /// Modelo mutable; si está compartido con otros motores se copia antes
+    pub fn model_mut(&mut self) -> &mut DisambiguationModel {
+        Arc::make_mut(&mut self.model)
     }
 
     /// Obtiene frecuencia de una palabra (si hay diccionario)
     pub fn word_frequency(&self, word: &str) -> u64 {
-        if let Some(ref dict) = self.dictionary {
-            dict.frequency(word)
-        } else {
-            0
-        }
+        self.model.word_frequency(word)
     }
 
     /// Verifica si el motor tiene diccionario externo cargado
     pub fn has_external_dictionary(&self) -> bool {
-        self.dictionary.is_some()
+        self.model.dictionary.is_some()
     }
 
     /// Estadísticas del diccionario
     pub fn dictionary_stats(&self) -> Option<&crate::dictionary::DictionaryStats> {
-        self.dictionary.as_ref().map(|d| &d.stats)
-    }
-
-    /// Carga diccionario por defecto
-    fn load_default_dictionary(&mut self) {
-        // Palabras del vocabulario semántico
-        let semantic_words = [
-            "roma", "coliseo", "paris", "madrid", "amor", "odio", "paz",
-            "ramo", "mora", "casa", "rosita", "azul", "romano",
-        ];
-
-        self.char_matcher.load_dictionary(semantic_words.iter().copied());
-
-        // Palabras gramaticales
-        let grammar_words = [
-            "el", "la", "los", "las", "un", "una", "unos", "unas",
-            "yo", "tú", "él", "ella", "nosotros", "me", "te", "le", "se",
-            "a", "ante", "bajo", "con", "contra", "de", "desde", "en",
-            "entre", "hacia", "hasta", "para", "por", "según", "sin",
-            "sobre", "tras", "y", "e", "o", "u", "pero", "sino", "que",
-            "porque", "aunque", "si", "cuando", "donde", "como",
-            "muy", "bien", "mal", "mucho", "poco", "siempre", "nunca",
-            "ya", "todavía", "aquí", "allí", "ahora", "después", "antes",
-            "también", "tampoco", "sí", "no",
-            "gusta", "gustan", "gustó", "soy", "eres", "es", "somos", "son",
-            "estoy", "estás", "está", "estamos", "están",
-            "visito", "visitas", "visita", "visité", "visitó",
-            "corro", "corres", "corre", "corremos", "corren",
-            "voy", "vas", "va", "vamos", "van", "fui", "fue",
-        ];
-
-        self.char_matcher.load_dictionary(grammar_words.iter().copied());
-
-        // Añadir sustantivos a la gramática
-        use crate::grammar::{NounInfo, Gender, Number, NounCategory};
-
-        self.grammar.add_noun("roma", NounInfo {
-            gender: Gender::Feminine,
-            number: Number::Singular,
-            category: NounCategory::Place,
-            can_be_subject: true,
-            can_be_object: true,
-        });
-
-        self.grammar.add_noun("coliseo", NounInfo {
-            gender: Gender::Masculine,
-            number: Number::Singular,
-            category: NounCategory::Place,
-            can_be_subject: false,
-            can_be_object: true,
-        });
+        self.model.dictionary_stats()
+    }
 
-        self.grammar.add_noun("casa", NounInfo {
-            gender: Gender::Feminine,
-            number: Number::Singular,
-            category: NounCategory::Thing,
-            can_be_subject: true,
-            can_be_object: true,
-        });
+    /// Procesa una oración completa
+    pub fn process(&self, sentence: &str) -> ProcessedSentence {
+        self.process_with(sentence, &mut RequestContext::new())
+    }
 
-        self.grammar.add_noun("amor", NounInfo {
-            gender: Gender::Masculine,
-            number: Number::Singular,
-            category: NounCategory::Concept,
-            can_be_subject: true,
-            can_be_object: true,
-        });
+    /// Procesa una oración guardando el estado de la petición en `request`
+    pub fn process_with(&self, sentence: &str, request: &mut RequestContext) -> ProcessedSentence {
+        let layers = request.layers.as_ref().unwrap_or(&self.layers);
 
-        // Añadir adjetivos
-        self.grammar.add_adjective("azul");
-        self.grammar.add_adjective("romano");
-        self.grammar.add_adjective("grande");
-        self.grammar.add_adjective("pequeño");
-    }
+        // 1. Tokenizar (los tramos protegidos son un token y no se tocan)
+        let (tokens, protected) = self.tokenize(sentence);
 
-    /// Procesa una oración completa
-    pub fn process(&mut self, sentence: &str) -> ProcessedSentence {
-        // 1. Tokenizar
-        let tokens = self.tokenize(sentence);
+        // Entidades con nombre (antes de perder las mayúsculas)
+        let entities = self.model.recognizer.recognize_with(&tokens, &self.model.semantic_db, |word| {
+            !self.is_known_in(layers, word) && self.model.char_matcher.has_close_word(word)
+        });
+        let in_entity = |i: usize| entities.iter().any(|e| e.contains(i));
 
-        // 2. Detectar anomalías (palabras no en diccionario)
+        // 2. Detectar anomalías (palabras no en diccionario ni en una entidad)
         let anomalies: Vec<(usize, String)> = tokens
             .iter()
             .enumerate()
-            .filter(|(_, t)| !self.char_matcher.is_valid(t) && !self.is_punctuation(t))
+            .filter(|(i, t)| {
+                !self.is_known_in(layers, t) && !self.is_punctuation(t) && !in_entity(*i) && !protected[*i]
+            })
             .map(|(i, t)| (i, t.clone()))
             .collect();
 
@@ -260,40 +226,61 @@ impl SemanticDisambiguator {
                 corrected: sentence.to_string(),
                 confidence: 1.0,
                 corrections: Vec::new(),
+                entities,
             };
         }
 
-        // 4. Extraer contexto (palabras conocidas)
+        // 4. Extraer contexto (palabras conocidas y entidades reconocidas)
+        let recognized = |i: usize| {
+            entities.iter().any(|e| e.contains(i) && e.kind != EntityKind::Unknown)
+        };
         let context_words: Vec<String> = tokens
             .iter()
-            .filter(|t| self.char_matcher.is_valid(t))
-            .cloned()
+            .enumerate()
+            .filter(|(i, t)| self.is_known_in(layers, t) || recognized(*i))
+            .map(|(_, t)| t.clone())
             .collect();
 
-        // 5. Inferir tema del contexto
-        let theme = self.semantic_db.infer_theme(&context_words);
-        if let Some((theme_name, _)) = &theme {
-            // Guardar en contexto compartido
-            let _ = self.shared_context.set(
+        // 5. Inferir distribución de temas del contexto (con los tags de las entidades)
+        let mut theme_words = context_words.clone();
+        theme_words.extend(entities.iter().flat_map(|e| e.tags.iter().cloned()));
+        let themes = self.model.semantic_db.infer_themes(&theme_words);
+        if let Some((theme_name, probability)) = themes.first() {
+            // Guardar el más probable en el contexto de la petición
+            let _ = request.shared.set(
                 "current_theme",
                 UnifyValue::Atom(theme_name.clone()),
                 Source::Semantic,
-                0.8,
+                *probability,
             );
         }
 
-        // 6. Para cada anomalía, desambiguar
+        // Palabras de contenido para la similitud de vectores
+        let content_words: Vec<String> = context_words
+            .iter()
+            .filter(|w| !self.model.grammar.is_closed_class(w))
+            .cloned()
+            .collect();
+
+        // 6. Para cada anomalía, desambiguar (palabras de usuario indexadas una vez)
+        let extra_words = ExtraWords::new(layers.added_words());
+        let context = SentenceContext {
+            tokens: &tokens,
+            content_words: &content_words,
+            themes: &themes,
+            feedback: &request.feedback,
+        };
         let mut corrected_tokens = tokens.clone();
         let mut corrections = Vec::new();
 
         for (idx, anomaly) in anomalies {
-            let (correction, conf, explanation) = self.disambiguate_word(
+            // Candidatos por caracteres (sin las palabras retiradas o ignoradas)
+            let candidates = self.model.char_matcher.find_candidates_with(
                 &anomaly,
-                idx,
-                &tokens,
-                &context_words,
-                theme.as_ref().map(|(t, _)| t.as_str()),
+                &extra_words,
+                |w| layers.resolve(w).is_some_and(|(_, action)| action != WordAction::Add),
             );
+            let (correction, conf, explanation) = self.disambiguate_word(candidates, &anomaly, idx, &context);
 
             if conf >= self.config.min_confidence {
                 corrected_tokens[idx] = correction.clone();
@@ -319,21 +306,19 @@ impl SemanticDisambiguator {
             corrected: corrected_tokens.join(" "),
             confidence: global_confidence,
             corrections,
+            entities,
         }
     }
 
     /// Desambigua una palabra individual
     fn disambiguate_word(
         &self,
+        candidates: Vec<MatchResult>,
         word: &str,
         position: usize,
-        sentence: &[String],
-        _context_words: &[String],
-        theme: Option<&str>,
+        context: &SentenceContext,
     ) -> (String, f64, CorrectionExplanation) {
-        // 1. Obtener candidatos por caracteres
-        let candidates = self.char_matcher.find_candidates(word);
-
+        let sentence = context.tokens;
         if candidates.is_empty() {
             return (
                 word.to_string(),
@@ -342,75 +327,173 @@ impl SemanticDisambiguator {
                     char_score: 0.0,
                     grammar_score: 0.0,
                     context_score: 0.0,
+                    lm_score: 0.0,
+                    frequency_score: 0.0,
+                    feedback_score: 0.0,
                     candidates: Vec::new(),
                     reason: "No se encontraron candidatos".to_string(),
                 },
             );
         }
 
+        // Modelo de lenguaje: P(candidato | contexto izquierdo y derecho)
+        let lm_scores = self.language_model_scores(&candidates, position, sentence);
+
+        // Prior de frecuencia (solo con datos de frecuencia del diccionario)
+        let frequencies = self.model.dictionary.as_ref().filter(|d| d.has_frequencies());
+
+        // Validez de la oración con la palabra original en su mejor lectura
+        let original_validity = self.model.grammar.best_reading_validity(word, position, sentence);
+
         // 2. Calcular scores combinados para cada candidato
-        let mut scored_candidates: Vec<(String, f64, f64, f64, f64)> = Vec::new();
+        let mut scored_candidates: Vec<ScoredCandidate> = Vec::new();
 
-        for candidate in &candidates {
+        for (i, candidate) in candidates.iter().enumerate() {
             let char_score = candidate.score;
 
-            let grammar_score = self.grammar.is_valid_at_position(
+            // Gramática: cuánto mejora la oración respecto a la original
+            // (0.5 = igual), no su validez absoluta
+            let candidate_validity = self.model.grammar.best_reading_validity(
                 &candidate.word,
                 position,
                 sentence,
             );
+            let grammar_score = (0.5 + candidate_validity - original_validity).clamp(0.0, 1.0);
 
-            let context_score = if let Some(t) = theme {
-                self.semantic_db.compatibility_score(&candidate.word, t)
-            } else {
-                0.5  // Neutral si no hay tema
-            };
+            // Base semántica; vectores de palabras si no hay conocimiento
+            let context_score = self.model.semantic_db.context_score(&candidate.word, context.themes, context.content_words);
 
             // Score combinado: α·char + β·grammar + γ·context
-            let total = self.config.alpha * char_score
-                      + self.config.beta * grammar_score
-                      + self.config.gamma * context_score;
+            let mut total = self.config.alpha * char_score
+                          + self.config.beta * grammar_score
+                          + self.config.gamma * context_score;
+
+            // Señales opcionales, renormalizando:
+            // (α·char + β·grammar + γ·context + δ·lm + ε·freq) / (α+β+γ+δ+ε)
+            let lm_score = lm_scores.as_ref().map(|scores| scores[i]);
+            let frequency_score = frequencies.map(|dict| dict.frequency_prior(&candidate.word));
+            let optional = [(self.config.delta, lm_score), (self.config.epsilon, frequency_score)];
+            if optional.iter().any(|(_, score)| score.is_some()) {
+                let mut weights = self.config.alpha + self.config.beta + self.config.gamma;
+                for (weight, score) in optional {
+                    if let Some(score) = score {
+                        total += weight * score;
+                        weights += weight;
+                    }
+                }
+                total /= weights;
+            }
+
+            // Feedback del usuario: desplaza el score como mucho ±ζ
+            let feedback_score = context.feedback.adjustment(word, &candidate.word);
+            total = (total + self.config.zeta * feedback_score).clamp(0.0, 1.0);
 
-            scored_candidates.push((
-                candidate.word.clone(),
+            scored_candidates.push(ScoredCandidate {
+                word: candidate.word.clone(),
                 total,
                 char_score,
                 grammar_score,
                 context_score,
-            ));
+                lm_score: lm_score.unwrap_or(0.0),
+                frequency_score: frequency_score.unwrap_or(0.0),
+                feedback_score,
+            });
         }
 
         // 3. Ordenar por score total
-        scored_candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored_candidates.sort_by(|a, b| b.total.partial_cmp(&a.total).unwrap_or(std::cmp::Ordering::Equal));
 
         // 4. Seleccionar el mejor
         let best = &scored_candidates[0];
-        let (best_word, best_total, best_char, best_grammar, best_context) = best;
+
+        let mut reason = format!(
+            "Elegido '{}' porque: caracteres={:.0}%, gramática={:.0}%, contexto={:.0}%",
+            best.word,
+            best.char_score * 100.0,
+            best.grammar_score * 100.0,
+            best.context_score * 100.0,
+        );
+        if lm_scores.is_some() {
+            reason.push_str(&format!(", n-gramas={:.0}%", best.lm_score * 100.0));
+        }
+        if frequencies.is_some() {
+            reason.push_str(&format!(", frecuencia={:.0}%", best.frequency_score * 100.0));
+        }
+        if best.feedback_score != 0.0 {
+            reason.push_str(&format!(", feedback={:+.0}%", best.feedback_score * 100.0));
+        }
 
         // 5. Crear explicación
         let explanation = CorrectionExplanation {
-            char_score: *best_char,
-            grammar_score: *best_grammar,
-            context_score: *best_context,
+            char_score: best.char_score,
+            grammar_score: best.grammar_score,
+            context_score: best.context_score,
+            lm_score: best.lm_score,
+            frequency_score: best.frequency_score,
+            feedback_score: best.feedback_score,
             candidates: scored_candidates
                 .iter()
                 .take(5)
-                .map(|(w, s, _, _, _)| (w.clone(), *s))
+                .map(|c| (c.word.clone(), c.total))
                 .collect(),
-            reason: format!(
-                "Elegido '{}' porque: caracteres={:.0}%, gramática={:.0}%, contexto={:.0}%",
-                best_word,
-                best_char * 100.0,
-                best_grammar * 100.0,
-                best_context * 100.0,
-            ),
+            reason,
         };
 
-        (best_word.clone(), *best_total, explanation)
+        (best.word.clone(), best.total, explanation)
     }
 
-    /// Tokeniza una oración
-    fn tokenize(&self, sentence: &str) -> Vec<String> {
+    /// Scores del modelo de lenguaje por candidato, relativos al más
+    /// probable (1.0); None si no hay modelo cargado
+    fn language_model_scores(
+        &self,
+        candidates: &[MatchResult],
+        position: usize,
+        sentence: &[String],
+    ) -> Option<Vec<f64>> {
+        let model = self.model.language_model.as_ref()?;
+
+        let words = |tokens: &[String]| -> Vec<String> {
+            tokens.iter().filter(|t| !self.is_punctuation(t)).cloned().collect()
+        };
+        let left = words(&sentence[..position]);
+        let right = words(&sentence[position + 1..]);
+        let left: Vec<&str> = left.iter().map(|w| w.as_str()).collect();
+        let right: Vec<&str> = right.iter().map(|w| w.as_str()).collect();
+
+        let log_probs: Vec<f64> = candidates
+            .iter()
+            .map(|c| model.log_prob_in_context(&left, &c.word, &right))
+            .collect();
+        let best = log_probs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Some(log_probs.iter().map(|lp| (lp - best).exp()).collect())
+    }
+
+    /// Tokeniza una oración respetando los tramos protegidos; devuelve
+    /// también qué tokens son protegidos
+    fn tokenize(&self, sentence: &str) -> (Vec<String>, Vec<bool>) {
+        let mut tokens = Vec::new();
+        let mut protected = Vec::new();
+
+        for segment in self.model.protector.segment(sentence) {
+            match segment {
+                Segment::Text(text) => {
+                    let words = self.tokenize_text(text);
+                    protected.extend(std::iter::repeat_n(false, words.len()));
+                    tokens.extend(words);
+                }
+                Segment::Protected(span) => {
+                    tokens.push(span.text);
+                    protected.push(true);
+                }
+            }
+        }
+
+        (tokens, protected)
+    }
+
+    /// Tokeniza texto sin tramos protegidos
+    fn tokenize_text(&self, sentence: &str) -> Vec<String> {
         let mut tokens = Vec::new();
         let mut current = String::new();
 
@@ -450,9 +533,94 @@ impl SemanticDisambiguator {
         token.len() == 1 && !token.chars().next().unwrap().is_alphanumeric()
     }
 
-    /// Añade palabras al diccionario
+    /// Añade palabras al diccionario base (para todos los clientes; ver
+    /// `set_tenant_dictionary` y `session_dictionary_mut`)
     pub fn add_to_dictionary<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, words: I) {
-        self.char_matcher.load_dictionary(words);
+        self.model_mut().add_words(words);
+    }
+
+    /// Palabra válida para el motor, según la capa más alta que la mencione
+    pub fn is_known(&self, word: &str) -> bool {
+        self.is_known_in(&self.layers, word)
+    }
+
+    fn is_known_in(&self, layers: &LayeredDictionary, word: &str) -> bool {
+        match layers.resolve(word) {
+            Some((_, WordAction::Add | WordAction::Ignore)) => true,
+            Some((_, WordAction::Remove)) => false,
+            None => self.model.char_matcher.is_valid(word),
+        }
+    }
+
+    /// Instala el diccionario del cliente (reemplaza al anterior)
+    pub fn set_tenant_dictionary(&mut self, tenant: impl Into<Arc<UserDictionary>>) {
+        self.layers.set_tenant(tenant);
+    }
+
+    /// Acceso mutable al diccionario del cliente
+    pub fn tenant_dictionary_mut(&mut self) -> &mut UserDictionary {
+        self.layers.layer_mut(DictionaryLayer::Tenant).expect("capa de cliente")
+    }
+
+    /// Acceso mutable al diccionario de la sesión
+    pub fn session_dictionary_mut(&mut self) -> &mut UserDictionary {
+        self.layers.layer_mut(DictionaryLayer::Session).expect("capa de sesión")
+    }
+
+    /// Descarta las palabras de la sesión
+    pub fn clear_session_dictionary(&mut self) {
+        self.layers.clear_session();
+    }
+
+    /// Capas de diccionario (cliente y sesión)
+    pub fn dictionary_layers(&self) -> &LayeredDictionary {
+        &self.layers
+    }
+
+    /// Registra si el usuario aceptó o rechazó una corrección, en el
+    /// estado de ese usuario (`request`): actualiza el ajuste del par, lo
+    /// guarda en `request.shared` (`Source::User`) y su diccionario
+    /// personal (capa de sesión de `request.layers`, que se crea a partir
+    /// de las del motor si no tenía): una corrección aceptada que no era
+    /// palabra conocida se añade, y una palabra cuyas correcciones se
+    /// rechazan siempre (al menos `PERSONAL_WORD_REJECTIONS` veces) pasa a
+    /// ser válida.
+    pub fn record_feedback(
+        &self,
+        request: &mut RequestContext,
+        event: FeedbackEvent,
+    ) -> Result<PairStats, ValidationError> {
+        let (original, suggested, accepted) = (event.original.clone(), event.suggested.clone(), event.accepted);
+        let stats = request.feedback.record(event);
+        request.feedback.persist_pair(&mut request.shared, &original, &suggested)?;
+
+        let personal_word = if accepted {
+            Some(suggested)
+        } else {
+            let totals = request.feedback.totals_for(&original);
+            (totals.accepted == 0 && totals.rejected >= PERSONAL_WORD_REJECTIONS).then_some(original)
+        };
+
+        if let Some(word) = personal_word {
+            let layers = request.layers.get_or_insert_with(|| self.layers.clone());
+            if !self.is_known_in(layers, &word) {
+                layers.layer_mut(DictionaryLayer::Session).expect("capa de sesión").add(&word);
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Estadísticas por capa: base, cliente y sesión
+    pub fn layer_stats(&self) -> Vec<LayerStats> {
+        let base = LayerStats {
+            layer: DictionaryLayer::Base,
+            name: "base".to_string(),
+            added: self.model.char_matcher.dictionary_size(),
+            removed: 0,
+            ignored: 0,
+        };
+        std::iter::once(base).chain(self.layers.stats()).collect()
     }
 
     /// Acceso a la configuración
@@ -465,14 +633,66 @@ impl SemanticDisambiguator {
         self.config = config;
     }
 
-    /// Acceso al contexto compartido
-    pub fn shared_context(&self) -> &SharedContext {
-        &self.shared_context
+    /// Acceso a la gramática (con el vocabulario cargado)
+    pub fn grammar(&self) -> &SpanishGrammar {
+        &self.model.grammar
+    }
+
+    /// Amplía el léxico de clases cerradas: la gramática las clasifica y
+    /// dejan de marcarse como desconocidas
+    pub fn load_lexicon(&mut self, lexicon: &Lexicon) {
+        self.model_mut().load_lexicon(lexicon);
+    }
+
+    /// Instala un etiquetador: la validación gramatical clasifica por contexto
+    pub fn set_tagger(&mut self, tagger: PosTagger) {
+        self.model_mut().set_tagger(tagger);
+    }
+
+    /// Define la variedad regional (leísmo admitido) de la gramática
+    pub fn set_region(&mut self, region: Region) {
+        self.model_mut().set_region(region);
+    }
+
+    /// Acceso a la base semántica
+    pub fn semantic_db(&self) -> &SemanticDB {
+        &self.model.semantic_db
+    }
+
+    /// Acceso mutable a la base semántica (cargar conocimiento o WordNet)
+    pub fn semantic_db_mut(&mut self) -> &mut SemanticDB {
+        self.model_mut().semantic_db_mut()
+    }
+
+    /// Puntúa los candidatos por caracteres con un modelo de errores
+    /// (canal ruidoso) en vez de la heurística ponderada
+    pub fn set_error_model(&mut self, model: ErrorModel) {
+        self.model_mut().set_error_model(model);
+    }
+
+    /// Instala un modelo de lenguaje de n-gramas como señal de ranking
+    pub fn set_language_model(&mut self, model: NgramModel) {
+        self.model_mut().set_language_model(model);
+    }
+
+    /// Modelo de lenguaje instalado
+    pub fn language_model(&self) -> Option<&NgramModel> {
+        self.model.language_model.as_ref()
+    }
+
+    /// Acceso mutable al detector de tramos protegidos (añadir patrones)
+    pub fn protector_mut(&mut self) -> &mut SpanProtector {
+        self.model_mut().protector_mut()
+    }
+
+    /// Acceso mutable al reconocedor de entidades (ampliar el gazetteer)
+    pub fn recognizer_mut(&mut self) -> &mut EntityRecognizer {
+        self.model_mut().recognizer_mut()
     }
 
     /// Tamaño del diccionario
     pub fn dictionary_size(&self) -> usize {
-        self.char_matcher.dictionary_size()
+        self.model.char_matcher.dictionary_size()
     }
 }
 
@@ -490,10 +710,10 @@ mod tests {
     fn test_tokenize() {
         let d = SemanticDisambiguator::new();
 
-        let tokens = d.tokenize("Visité el Coliseo romano");
+        let (tokens, _) = d.tokenize("Visité el Coliseo romano");
         assert_eq!(tokens, vec!["Visité", "el", "Coliseo", "romano"]);
 
-        let tokens = d.tokenize("¿Cómo estás?");
+        let (tokens, _) = d.tokenize("¿Cómo estás?");
         assert_eq!(tokens, vec!["¿", "Cómo", "estás", "?"]);
     }
 
@@ -510,7 +730,7 @@ mod tests {
 
     #[test]
     fn test_disambiguate_smor() {
-        let mut d = SemanticDisambiguator::new();
+        let d = SemanticDisambiguator::new();
 
         // Contexto de arquitectura romana
         let result = d.process("Visité el Coliseo romano en smor");
@@ -530,7 +750,7 @@ mod tests {
 
     #[test]
     fn test_flexible_spanish() {
-        let mut d = SemanticDisambiguator::new();
+        let d = SemanticDisambiguator::new();
 
         // Oración válida aunque orden diferente
         let result1 = d.process("la casa azul me gusta");
@@ -539,4 +759,300 @@ mod tests {
         let result2 = d.process("me gusta la casa azul");
         assert!(result2.corrections.is_empty());
     }
+
+    #[test]
+    fn test_language_model_signal() {
+        let mut d = SemanticDisambiguator::with_config(Config { min_confidence: 0.5, ..Config::default() });
+        // Sin tema ni modelo, "smor" se queda en "amor" (umbral bajo: la
+        // gramática no distingue los candidatos y solo se compara el ranking)
+        let before = d.process("voy a smor");
+        assert_eq!(before.corrections[0].corrected, "amor");
+        assert_eq!(before.corrections[0].explanation.lm_score, 0.0);
+
+        d.set_language_model(NgramModel::train(3, "voy a roma. vamos a roma en tren. fui a roma. el amor es azul."));
+        let after = d.process("voy a smor");
+        let correction = &after.corrections[0];
+        assert_eq!(correction.corrected, "roma");
+        assert_eq!(correction.explanation.lm_score, 1.0);
+        assert!(correction.explanation.reason.contains("n-gramas"));
+    }
+
+    #[test]
+    fn test_frequency_prior() {
+        let mut dict = SpanishDictionary::new();
+        dict.set_frequency("pesa", 5_000);
+        dict.set_frequency("besa", 3);
+
+        let mut d = SemanticDisambiguator::with_dictionary(dict);
+        d.set_config(Config { min_confidence: 0.5, ..Config::default() });
+        let result = d.process("tesa");
+        let correction = &result.corrections[0];
+
+        assert_eq!(correction.corrected, "pesa");
+        assert!((correction.explanation.frequency_score - 1.0).abs() < 1e-9);
+        assert!(correction.explanation.reason.contains("frecuencia"));
+
+        let (_, besa) = correction.explanation.candidates.iter().find(|(w, _)| w == "besa").unwrap();
+        assert!(*besa < correction.confidence);
+    }
+
+    #[test]
+    fn test_proper_names_not_corrected() {
+        let d = SemanticDisambiguator::new();
+
+        // "Xochitl" no está en ningún diccionario, pero es un nombre propio
+        let result = d.process("Rosita visita a Xochitl en smor");
+        assert!(result.corrections.iter().all(|c| c.original != "Xochitl"));
+        assert!(result.corrected.contains("Xochitl"));
+        assert!(result.entities.iter().any(|e| e.text == "Xochitl"));
+
+        // El Partenón (gazetteer) aporta tema aunque no esté en el diccionario
+        let result = d.process("Visité el Partenón y el Foro Romano en smor");
+        assert_eq!(result.entities.len(), 2);
+        assert_eq!(result.corrections[0].corrected, "roma");
+    }
+
+    #[test]
+    fn test_capitals_do_not_shield_typos() {
+        let d = SemanticDisambiguator::new();
+
+        // Errata con mayúscula de un nombre conocido
+        let result = d.process("visité el Colizeo romano");
+        assert_eq!(result.corrected, "visité el coliseo romano");
+        assert!(result.entities.is_empty());
+
+        // Oración en mayúsculas: se corrige como cualquier otra
+        let result = d.process("VISITÉ EL COLISEO ROMANO EN SMOR");
+        assert!(result.corrections.iter().any(|c| c.original == "SMOR" && c.corrected == "roma"));
+    }
+
+    #[test]
+    fn test_protected_spans() {
+        let mut d = SemanticDisambiguator::with_config(Config { min_confidence: 0.5, ..Config::default() });
+
+        let (tokens, protected) = d.tokenize("mira https://x.com y yo@mail.com");
+        assert_eq!(tokens, vec!["mira", "https://x.com", "y", "yo@mail.com"]);
+        assert_eq!(protected, vec![false, true, false, true]);
+
+        let result = d.process("visita https://roma.example.com/smor a las 18:30 en smor");
+        assert_eq!(result.corrections.len(), 1);
+        assert_eq!(result.corrections[0].original, "smor");
+        assert!(result.corrected.starts_with("visita https://roma.example.com/smor a las 18:30"));
+
+        d.protector_mut().add_pattern("clave", r"ZX-\d+").unwrap();
+        let result = d.process("la casa ZX-99 azul");
+        assert!(result.corrections.is_empty());
+    }
+
+    #[test]
+    fn test_dictionary_layers() {
+        let mut d = SemanticDisambiguator::with_config(Config { min_confidence: 0.5, ..Config::default() });
+        let mut tenant = UserDictionary::new("acme");
+        tenant.add("despliegue");
+        tenant.remove("amor");
+        d.set_tenant_dictionary(tenant);
+        d.session_dictionary_mut().ignore("xyzzy");
+
+        // Palabras del cliente: válidas y candidatas a corrección
+        assert!(d.process("la casa es azul").corrections.is_empty());
+        assert!(d.process("el despliegue es azul").corrections.is_empty());
+        let result = d.process("el despleigue es azul");
+        assert_eq!(result.corrections[0].corrected, "despliegue");
+
+        // Palabra eliminada: ya no se propone; ignorada: no se marca
+        let result = d.process("mucho smor");
+        assert!(result.corrections.iter().all(|c| c.corrected != "amor"));
+        assert!(!d.is_known("amor"));
+        assert!(d.process("la casa xyzzy").corrections.is_empty());
+
+        let stats = d.layer_stats();
+        assert_eq!(stats.len(), 3);
+        assert_eq!((stats[1].added, stats[1].removed, stats[2].ignored), (1, 1, 1));
+
+        // Otro cliente no ve esas palabras
+        d.set_tenant_dictionary(UserDictionary::new("otro"));
+        d.clear_session_dictionary();
+        assert!(!d.is_known("xyzzy"));
+        assert!(!d.is_known("despliegue"));
+        assert!(d.is_known("amor"));
+    }
+
+    #[test]
+    fn test_feedback_shifts_scoring() {
+        let d = SemanticDisambiguator::new();
+        let mut user = RequestContext::new();
+        let sentence = "Visité el Coliseo romano en smor";
+        let before = d.process_with(sentence, &mut user);
+        let first = before.corrections[0].corrected.clone();
+        let second = before.corrections[0].explanation.candidates[1].0.clone();
+
+        // Un rechazo baja al candidato, pero no lo elimina
+        let stats = d.record_feedback(&mut user, FeedbackEvent::rejected("smor", &first, &["coliseo", "romano"])).unwrap();
+        assert_eq!(stats.rejected, 1);
+        let after = d.process_with(sentence, &mut user);
+        let explanation = &after.corrections[0].explanation;
+        assert!(explanation.candidates.iter().any(|(w, _)| *w == first));
+
+        // Aceptar otra corrección la sube, solo para este usuario
+        for _ in 0..5 {
+            d.record_feedback(&mut user, FeedbackEvent::accepted("smor", &second, &[])).unwrap();
+        }
+        let after = d.process_with(sentence, &mut user);
+        assert_eq!(after.corrections[0].corrected, second);
+        assert!(after.corrections[0].explanation.feedback_score > 0.0);
+        assert_eq!(d.process(sentence).corrections[0].corrected, first);
+
+        // Persistido en el contexto del usuario con Source::User
+        let key = format!("feedback:smor:{}", crate::dictionary::normalize_word(&second));
+        assert_eq!(user.shared.get(&key).unwrap().source, Source::User);
+        let mut other = RequestContext::new();
+        d.process_with(sentence, &mut other);
+        assert!(other.shared.get(&key).is_none());
+
+        let mut restored = RequestContext::new();
+        restored.restore_feedback(&user.shared).unwrap();
+        assert_eq!(restored.feedback.pair("smor", &second).accepted, 5);
+    }
+
+    #[test]
+    fn test_feedback_updates_personal_dictionary() {
+        let d = SemanticDisambiguator::new();
+        let mut user = RequestContext::new();
+        for _ in 0..PERSONAL_WORD_REJECTIONS {
+            d.record_feedback(&mut user, FeedbackEvent::rejected("xochi", "coche", &[])).unwrap();
+        }
+        d.record_feedback(&mut user, FeedbackEvent::accepted("zzz", "despliegue", &[])).unwrap();
+
+        let layers = user.layers.as_ref().unwrap();
+        assert_eq!(layers.stats()[1].added, 2);
+        assert!(d.process_with("la casa xochi", &mut user).corrections.is_empty());
+        assert!(!d.is_known("xochi") && !d.is_known("despliegue"));
+
+        // Con capas propias del cliente, lo aprendido va a su sesión
+        let mut tenant = UserDictionary::new("acme");
+        tenant.add("sprint");
+        let mut user = RequestContext::for_tenant(Arc::new(tenant));
+        d.record_feedback(&mut user, FeedbackEvent::accepted("zzz", "despliegue", &[])).unwrap();
+        let result = d.process_with("el despleigue es azul", &mut user);
+        assert!(result.corrections.iter().any(|c| c.corrected == "despliegue"));
+        assert_eq!(user.layers.as_ref().unwrap().resolve("sprint"), Some((DictionaryLayer::Tenant, WordAction::Add)));
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SemanticDisambiguator>();
+
+        let d = std::sync::Arc::new(SemanticDisambiguator::new());
+        let expected = d.process("Visité el Coliseo romano en smor").corrected;
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let d = std::sync::Arc::clone(&d);
+                std::thread::spawn(move || d.process("Visité el Coliseo romano en smor").corrected)
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_request_context() {
+        let d = SemanticDisambiguator::with_config(Config { min_confidence: 0.5, ..Config::default() });
+
+        let mut request = RequestContext::new();
+        d.process_with("Visité el Coliseo romano en smor", &mut request);
+        assert_eq!(request.shared.get("current_theme").unwrap().source, Source::Semantic);
+
+        // El historial de rollback de la petición está acotado
+        for i in 0..REQUEST_HISTORY_LIMIT + 10 {
+            request.shared.set("n", crate::uniform::UnifyValue::Num(i as f64), Source::User, 1.0).unwrap();
+        }
+        assert_eq!(request.shared.history_len(), REQUEST_HISTORY_LIMIT);
+
+        // Capas propias de la petición (cliente compartido), sin tocar las del motor
+        let mut tenant = UserDictionary::new("acme");
+        tenant.add("despliegue");
+        let tenant = Arc::new(tenant);
+        let mut request = RequestContext::for_tenant(Arc::clone(&tenant));
+        assert!(Arc::ptr_eq(request.layers.as_ref().unwrap().tenant(), &tenant));
+        assert!(d.process_with("el despliegue es azul", &mut request).corrections.is_empty());
+        let result = d.process_with("el despleigue es azul", &mut request);
+        assert_eq!(result.corrections[0].corrected, "despliegue");
+        let result = d.process("el despleigue es azul");
+        assert!(result.corrections.iter().all(|c| c.corrected != "despliegue"));
+    }
+
+    #[test]
+    fn test_shared_model() {
+        let model = Arc::new(DisambiguationModel::new());
+        let a = SemanticDisambiguator::with_model(Arc::clone(&model));
+        let mut b = SemanticDisambiguator::with_model(Arc::clone(&model));
+        assert!(Arc::ptr_eq(a.model(), b.model()));
+
+        // Modificar un motor copia su modelo; el otro no cambia
+        b.add_to_dictionary(["smor"]);
+        assert!(b.model().is_valid("smor"));
+        assert!(!a.model().is_valid("smor"));
+        assert!(Arc::ptr_eq(a.model(), &model));
+    }
+
+    #[test]
+    fn test_grammar_hooks() {
+        use crate::grammar::conllu::{parse_conllu, Upos};
+
+        let config = Config { min_confidence: 0.3, ..Config::default() };
+        let candidates = |d: &SemanticDisambiguator, sentence: &str| {
+            d.process(sentence).corrections[0].explanation.candidates.clone()
+        };
+
+        // Léxico: las palabras nuevas son válidas y la gramática las clasifica
+        let mut d = SemanticDisambiguator::with_config(config.clone());
+        assert!(!d.is_known("acá"));
+        d.load_lexicon(&Lexicon::parse("@version 1\nadverb  acá\n").unwrap());
+        assert!(d.is_known("acá"));
+        assert!(d.process("la casa es azul acá").corrections.is_empty());
+        assert!(matches!(d.grammar().classify_token("acá"), crate::TokenType::Adverb));
+
+        // Región: el leísmo admitido cambia el score de "le"/"les"
+        let mut american = SemanticDisambiguator::with_config(config.clone());
+        american.set_region(Region::American);
+        let score = |c: &[(String, f64)], w: &str| c.iter().find(|(x, _)| x == w).unwrap().1;
+        let general = candidates(&d, "yo lw visité");
+        let america = candidates(&american, "yo lw visité");
+        assert!(score(&america, "le") < score(&general, "le"));
+        assert_eq!(score(&america, "lo"), score(&general, "lo"));
+
+        // Etiquetador: la validación gramatical clasifica por contexto
+        let mut text = String::new();
+        for row in [["yo", "PRON", "la", "PRON", "visité", "VERB"], ["la", "DET", "casa", "NOUN", "es", "AUX"]] {
+            for (i, pair) in row.chunks(2).enumerate() {
+                text.push_str(&format!("{}\t{}\t_\t{}\t_\t_\t0\tdep\t_\t_\n", i + 1, pair[0], pair[1]));
+            }
+            text.push('\n');
+        }
+        let mut tagged = SemanticDisambiguator::with_config(config);
+        let before = candidates(&tagged, "yo la visité en smor");
+        tagged.set_tagger(PosTagger::train(&parse_conllu(&text).unwrap(), 10));
+        assert_eq!(tagged.grammar().tag(&["yo".into(), "la".into(), "visité".into()])[1].pos, Upos::Pron);
+        assert_ne!(candidates(&tagged, "yo la visité en smor"), before);
+    }
+
+    #[test]
+    fn test_correct_sentences_unchanged() {
+        let d = SemanticDisambiguator::new();
+        for sentence in [
+            "la niña canta en el parque",
+            "Juan y María corren",
+            "el agua la agua",
+            "mi casa es azul",
+            "el niño juega en la casa de Rosita",
+        ] {
+            let result = d.process(sentence);
+            assert!(result.corrections.is_empty(), "{}: {:?}", sentence, result.corrections);
+            assert_eq!(result.corrected, sentence);
+        }
+        assert!(d.process("mi casa es azul").entities.is_empty());
+    }
 }
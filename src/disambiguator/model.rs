@@ -0,0 +1,301 @@
+//! # Disambiguation Model
+//!
+//! Recursos de solo lectura del motor: vocabulario de caracteres,
+//! gramática, base semántica, diccionario, modelo de lenguaje, entidades y
+//! tramos protegidos. Se carga una vez y se comparte (`Arc`) entre motores
+//! y peticiones; lo que cambia por cliente o usuario (capas, feedback)
+//! vive fuera.
+
+use crate::chars::CharMatcher;
+use crate::chars::channel::ErrorModel;
+use crate::dictionary::{DictionaryStats, SpanishDictionary};
+use crate::grammar::SpanishGrammar;
+use crate::grammar::clitics::Region;
+use crate::grammar::lexicon::Lexicon;
+use crate::grammar::tagger::PosTagger;
+use crate::ner::EntityRecognizer;
+use crate::ngram::NgramModel;
+use crate::protect::SpanProtector;
+use crate::semantic::SemanticDB;
+
+/// Recursos lingüísticos compartidos por todas las peticiones
+#[derive(Debug, Clone)]
+pub struct DisambiguationModel {
+    /// Matcher de caracteres
+    pub(super) char_matcher: CharMatcher,
+
+    /// Gramática española
+    pub(super) grammar: SpanishGrammar,
+
+    /// Base de datos semántica
+    pub(super) semantic_db: SemanticDB,
+
+    /// Diccionario completo (opcional, para carga desde archivo)
+    pub(super) dictionary: Option<SpanishDictionary>,
+
+    /// Modelo de lenguaje de n-gramas (opcional)
+    pub(super) language_model: Option<NgramModel>,
+
+    /// Reconocedor de entidades con nombre
+    pub(super) recognizer: EntityRecognizer,
+
+    /// Detector de tramos protegidos
+    pub(super) protector: SpanProtector,
+}
+
+impl DisambiguationModel {
+    /// Modelo con el vocabulario básico
+    pub fn new() -> Self {
+        let mut model = Self::empty(None);
+
+        // Cargar diccionario inicial
+        model.load_default_dictionary();
+
+        model
+    }
+
+    /// Modelo con diccionario externo (RAE/LATAM)
+    pub fn with_dictionary(dict: SpanishDictionary) -> Self {
+        let mut model = Self::empty(Some(dict));
+
+        // Cargar palabras del diccionario al CharMatcher
+        model.load_from_spanish_dictionary();
+
+        // Cargar también palabras gramaticales básicas
+        model.load_grammar_words();
+
+        model
+    }
+
+    fn empty(dictionary: Option<SpanishDictionary>) -> Self {
+        Self {
+            char_matcher: CharMatcher::new(),
+            grammar: SpanishGrammar::new(),
+            semantic_db: SemanticDB::new(),
+            dictionary,
+            language_model: None,
+            recognizer: EntityRecognizer::new(),
+            protector: SpanProtector::new(),
+        }
+    }
+
+    /// Carga palabras desde SpanishDictionary al CharMatcher
+    fn load_from_spanish_dictionary(&mut self) {
+        if let Some(ref dict) = self.dictionary {
+            // Cargar todas las palabras válidas al CharMatcher
+            let words: Vec<String> = dict.all_words().cloned().collect();
+            self.char_matcher.load_dictionary(words.iter().map(|s| s.as_str()));
+
+            // Añadir sustantivos y adjetivos con sus formas flexionadas
+            use crate::grammar::NounCategory;
+            use crate::dictionary::PartOfSpeech;
+
+            let mut inflected: Vec<String> = Vec::new();
+
+            for word in dict.all_words() {
+                for entry in dict.get_entries(word) {
+                    // Género y número se derivan del módulo de flexión
+                    if entry.pos.contains(&PartOfSpeech::Noun) {
+                        inflected.extend(self.grammar.add_noun_forms(&entry.original, NounCategory::Thing));
+                    }
+
+                    if entry.pos.contains(&PartOfSpeech::Adjective) {
+                        inflected.extend(self.grammar.add_adjective_forms(&entry.original));
+                    }
+                }
+            }
+
+            // Plurales y femeninos pasan a ser palabras válidas
+            self.char_matcher.load_dictionary(inflected.iter().map(|s| s.as_str()));
+
+            // Frecuencias para desempatar candidatos
+            for word in dict.all_words() {
+                let frequency = dict.frequency(word);
+                if frequency > 0 {
+                    self.char_matcher.set_frequency(word, frequency);
+                }
+            }
+        }
+    }
+
+    /// Carga palabras gramaticales básicas (artículos, preposiciones, etc.)
+    pub(super) fn load_grammar_words(&mut self) {
+        let grammar_words = self.grammar.closed_class_words();
+        self.char_matcher.load_dictionary(grammar_words);
+    }
+
+    /// Carga diccionario por defecto
+    fn load_default_dictionary(&mut self) {
+        // Palabras del vocabulario semántico
+        let semantic_words = [
+            "roma", "coliseo", "paris", "madrid", "amor", "odio", "paz",
+            "ramo", "mora", "casa", "rosita", "azul", "romano",
+        ];
+
+        self.char_matcher.load_dictionary(semantic_words.iter().copied());
+
+        // Palabras gramaticales (léxico de clases cerradas)
+        self.load_grammar_words();
+
+        // Formas de los verbos base
+        let verb_forms = [
+            "gusta", "gustan", "gustó", "soy", "eres", "es", "somos", "son",
+            "estoy", "estás", "está", "estamos", "están",
+            "visito", "visitas", "visita", "visité", "visitó",
+            "corro", "corres", "corre", "corremos", "corren",
+            "voy", "vas", "va", "vamos", "van", "fui", "fue",
+        ];
+
+        self.char_matcher.load_dictionary(verb_forms.iter().copied());
+
+        // Añadir sustantivos a la gramática
+        use crate::grammar::{NounInfo, Gender, Number, NounCategory};
+
+        self.grammar.add_noun("roma", NounInfo {
+            gender: Gender::Feminine,
+            number: Number::Singular,
+            category: NounCategory::Place,
+            can_be_subject: true,
+            can_be_object: true,
+        });
+
+        self.grammar.add_noun("coliseo", NounInfo {
+            gender: Gender::Masculine,
+            number: Number::Singular,
+            category: NounCategory::Place,
+            can_be_subject: false,
+            can_be_object: true,
+        });
+
+        // Sustantivos comunes con sus plurales (solo en la gramática:
+        // el vocabulario de caracteres del diccionario básico es fijo)
+        self.grammar.add_noun_forms("casa", NounCategory::Thing);
+        self.grammar.add_noun_forms("amor", NounCategory::Concept);
+
+        // Añadir adjetivos con sus formas flexionadas
+        for adjective in ["azul", "romano", "grande", "pequeño"] {
+            self.grammar.add_adjective_forms(adjective);
+        }
+    }
+
+    /// Añade palabras al vocabulario base
+    pub fn add_words<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, words: I) {
+        self.char_matcher.load_dictionary(words);
+    }
+
+    /// Obtiene frecuencia de una palabra (si hay diccionario)
+    pub fn word_frequency(&self, word: &str) -> u64 {
+        if let Some(ref dict) = self.dictionary {
+            dict.frequency(word)
+        } else {
+            0
+        }
+    }
+
+    /// Diccionario externo, si se cargó
+    pub fn dictionary(&self) -> Option<&SpanishDictionary> {
+        self.dictionary.as_ref()
+    }
+
+    /// Estadísticas del diccionario
+    pub fn dictionary_stats(&self) -> Option<&DictionaryStats> {
+        self.dictionary.as_ref().map(|d| &d.stats)
+    }
+
+    /// Tamaño del vocabulario de caracteres
+    pub fn dictionary_size(&self) -> usize {
+        self.char_matcher.dictionary_size()
+    }
+
+    /// Palabra del vocabulario base
+    pub fn is_valid(&self, word: &str) -> bool {
+        self.char_matcher.is_valid(word)
+    }
+
+    /// Gramática (con el vocabulario cargado)
+    pub fn grammar(&self) -> &SpanishGrammar {
+        &self.grammar
+    }
+
+    /// Amplía el léxico de clases cerradas de la gramática y del vocabulario
+    pub fn load_lexicon(&mut self, lexicon: &Lexicon) {
+        self.grammar.load_lexicon(lexicon);
+        self.load_grammar_words();
+    }
+
+    /// Instala un etiquetador en la gramática
+    pub fn set_tagger(&mut self, tagger: PosTagger) {
+        self.grammar.set_tagger(tagger);
+    }
+
+    /// Define la variedad regional de la gramática
+    pub fn set_region(&mut self, region: Region) {
+        self.grammar.set_region(region);
+    }
+
+    /// Base semántica
+    pub fn semantic_db(&self) -> &SemanticDB {
+        &self.semantic_db
+    }
+
+    /// Base semántica, mutable (cargar conocimiento o WordNet)
+    pub fn semantic_db_mut(&mut self) -> &mut SemanticDB {
+        &mut self.semantic_db
+    }
+
+    /// Puntúa los candidatos por caracteres con un modelo de errores
+    pub fn set_error_model(&mut self, model: ErrorModel) {
+        self.char_matcher.set_error_model(model);
+    }
+
+    /// Instala un modelo de lenguaje de n-gramas
+    pub fn set_language_model(&mut self, model: NgramModel) {
+        self.language_model = Some(model);
+    }
+
+    /// Modelo de lenguaje instalado
+    pub fn language_model(&self) -> Option<&NgramModel> {
+        self.language_model.as_ref()
+    }
+
+    /// Reconocedor de entidades, mutable (ampliar el gazetteer)
+    pub fn recognizer_mut(&mut self) -> &mut EntityRecognizer {
+        &mut self.recognizer
+    }
+
+    /// Detector de tramos protegidos, mutable (añadir patrones)
+    pub fn protector_mut(&mut self) -> &mut SpanProtector {
+        &mut self.protector
+    }
+}
+
+impl Default for DisambiguationModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_vocabulary() {
+        let model = DisambiguationModel::new();
+        assert!(model.is_valid("roma") && model.is_valid("visité") && model.is_valid("el"));
+        assert!(!model.is_valid("smor"));
+        assert!(model.dictionary().is_none());
+    }
+
+    #[test]
+    fn test_shared_copy_on_write() {
+        use std::sync::Arc;
+
+        let shared = Arc::new(DisambiguationModel::new());
+        let mut own = Arc::clone(&shared);
+        Arc::make_mut(&mut own).add_words(["despliegue"]);
+
+        assert!(own.is_valid("despliegue"));
+        assert!(!shared.is_valid("despliegue"));
+    }
+}
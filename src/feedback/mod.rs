@@ -0,0 +1,260 @@
+//! # Feedback Module
+//!
+//! Aprendizaje en línea de las correcciones que el usuario acepta o
+//! rechaza. Por cada par (original → sugerida) se cuentan aceptaciones y
+//! rechazos; el ajuste resultante desplaza el score del candidato:
+//!
+//! ```text
+//! ajuste = (aceptadas - rechazadas) / (aceptadas + rechazadas + 2)   ∈ (-1, 1)
+//! total += ζ · ajuste
+//! ```
+//!
+//! El ajuste nunca supera ζ en valor absoluto, así que unos pocos rechazos
+//! solo bajan al candidato, no lo eliminan. Los contadores se guardan en
+//! `SharedContext` con `Source::User` (clave `feedback:original:sugerida`).
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::applog::{SharedContext, Source, ValidationError};
+use crate::dictionary::normalize_word;
+use crate::uniform::UnifyValue;
+
+/// Prefijo de las claves de feedback en `SharedContext`
+pub const FEEDBACK_KEY_PREFIX: &str = "feedback:";
+
+/// Pseudo-conteo del ajuste: con pocos eventos el ajuste es pequeño
+const PRIOR_EVENTS: f64 = 2.0;
+
+/// Eventos que guarda el historial; los más antiguos se descartan (los
+/// contadores por par no se pierden)
+pub const MAX_EVENTS: usize = 1000;
+
+/// Una corrección aceptada o rechazada por el usuario
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackEvent {
+    /// Palabra que escribió el usuario
+    pub original: String,
+    /// Corrección propuesta (o la que escribió el usuario al aceptar)
+    pub suggested: String,
+    pub accepted: bool,
+    /// Palabras de la oración
+    pub context: Vec<String>,
+}
+
+impl FeedbackEvent {
+    /// El usuario aceptó la corrección
+    pub fn accepted(original: &str, suggested: &str, context: &[&str]) -> Self {
+        Self::new(original, suggested, true, context)
+    }
+
+    /// El usuario rechazó la corrección
+    pub fn rejected(original: &str, suggested: &str, context: &[&str]) -> Self {
+        Self::new(original, suggested, false, context)
+    }
+
+    fn new(original: &str, suggested: &str, accepted: bool, context: &[&str]) -> Self {
+        Self {
+            original: original.to_string(),
+            suggested: suggested.to_string(),
+            accepted,
+            context: context.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+}
+
+/// Contadores de un par original → sugerida
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PairStats {
+    pub accepted: u32,
+    pub rejected: u32,
+}
+
+impl PairStats {
+    /// Ajuste en (-1, 1): positivo si se acepta más de lo que se rechaza
+    pub fn adjustment(&self) -> f64 {
+        let accepted = self.accepted as f64;
+        let rejected = self.rejected as f64;
+        (accepted - rejected) / (accepted + rejected + PRIOR_EVENTS)
+    }
+}
+
+/// Historial de feedback y contadores por par
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackStore {
+    pairs: HashMap<(String, String), PairStats>,
+    events: VecDeque<FeedbackEvent>,
+}
+
+impl FeedbackStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra un evento; devuelve los contadores actualizados del par
+    pub fn record(&mut self, event: FeedbackEvent) -> PairStats {
+        let stats = self.pairs.entry(pair_key(&event.original, &event.suggested)).or_default();
+        if event.accepted {
+            stats.accepted += 1;
+        } else {
+            stats.rejected += 1;
+        }
+        let stats = *stats;
+        self.events.push_back(event);
+        if self.events.len() > MAX_EVENTS {
+            self.events.pop_front();
+        }
+        stats
+    }
+
+    /// Contadores de un par
+    pub fn pair(&self, original: &str, suggested: &str) -> PairStats {
+        self.pairs.get(&pair_key(original, suggested)).copied().unwrap_or_default()
+    }
+
+    /// Ajuste de un par (0.0 sin feedback)
+    pub fn adjustment(&self, original: &str, suggested: &str) -> f64 {
+        self.pair(original, suggested).adjustment()
+    }
+
+    /// Totales de una palabra original, sumando todas sus sugerencias
+    pub fn totals_for(&self, original: &str) -> PairStats {
+        let original = normalize_word(original);
+        self.pairs
+            .iter()
+            .filter(|((o, _), _)| *o == original)
+            .fold(PairStats::default(), |acc, (_, s)| PairStats {
+                accepted: acc.accepted + s.accepted,
+                rejected: acc.rejected + s.rejected,
+            })
+    }
+
+    /// Últimos eventos registrados (como mucho `MAX_EVENTS`), en orden
+    pub fn events(&self) -> impl Iterator<Item = &FeedbackEvent> {
+        self.events.iter()
+    }
+
+    /// Saca los eventos registrados (p. ej. para guardarlos); los
+    /// contadores se conservan
+    pub fn drain_events(&mut self) -> Vec<FeedbackEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Guarda los contadores de un par en el contexto compartido
+    pub fn persist_pair(
+        &self,
+        context: &mut SharedContext,
+        original: &str,
+        suggested: &str,
+    ) -> Result<(), ValidationError> {
+        let (original, suggested) = pair_key(original, suggested);
+        let stats = self.pair(&original, &suggested);
+        let value = UnifyValue::Struct(
+            "feedback".to_string(),
+            vec![
+                UnifyValue::Atom(original.clone()),
+                UnifyValue::Atom(suggested.clone()),
+                UnifyValue::Num(stats.accepted as f64),
+                UnifyValue::Num(stats.rejected as f64),
+            ],
+        );
+        let key = format!("{}{}:{}", FEEDBACK_KEY_PREFIX, original, suggested);
+        context.set(&key, value, Source::User, 1.0)
+    }
+
+    /// Reconstruye los contadores guardados en un contexto compartido
+    pub fn from_context(context: &SharedContext) -> Self {
+        let mut store = Self::new();
+
+        for (key, shared) in context.bindings_from(Source::User) {
+            if !key.starts_with(FEEDBACK_KEY_PREFIX) {
+                continue;
+            }
+            if let UnifyValue::Struct(functor, args) = &shared.value {
+                if let [UnifyValue::Atom(o), UnifyValue::Atom(s), UnifyValue::Num(a), UnifyValue::Num(r)] = args.as_slice() {
+                    if functor == "feedback" {
+                        store.pairs.insert(
+                            (o.clone(), s.clone()),
+                            PairStats { accepted: *a as u32, rejected: *r as u32 },
+                        );
+                    }
+                }
+            }
+        }
+
+        store
+    }
+
+    /// Número de pares con feedback
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+fn pair_key(original: &str, suggested: &str) -> (String, String) {
+    (normalize_word(original), normalize_word(suggested))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjustment_is_capped() {
+        let mut store = FeedbackStore::new();
+        assert_eq!(store.adjustment("smor", "amor"), 0.0);
+
+        store.record(FeedbackEvent::rejected("smor", "amor", &["te", "quiero"]));
+        let one = store.adjustment("smor", "amor");
+        assert!((one + 1.0 / 3.0).abs() < 1e-9);
+
+        for _ in 0..50 {
+            store.record(FeedbackEvent::rejected("smor", "amor", &[]));
+        }
+        let many = store.adjustment("SMOR", "ámor");
+        assert!(many < one && many > -1.0);
+        assert_eq!(store.events().count(), 51);
+    }
+
+    #[test]
+    fn test_event_log_is_capped() {
+        let mut store = FeedbackStore::new();
+        for i in 0..MAX_EVENTS + 10 {
+            store.record(FeedbackEvent::rejected(["uno", "dos", "tres"][i % 3], "amor", &[]));
+        }
+        assert_eq!(store.events().count(), MAX_EVENTS);
+        assert_eq!(store.events().next().unwrap().original, "dos");
+        assert_eq!(store.drain_events().len(), MAX_EVENTS);
+        assert_eq!(store.events().count(), 0);
+        assert_eq!(store.totals_for("uno").rejected, 337);
+    }
+
+    #[test]
+    fn test_totals_for_original() {
+        let mut store = FeedbackStore::new();
+        store.record(FeedbackEvent::accepted("smor", "amor", &[]));
+        store.record(FeedbackEvent::rejected("smor", "roma", &[]));
+        store.record(FeedbackEvent::rejected("smor", "roma", &[]));
+
+        assert_eq!(store.totals_for("smor"), PairStats { accepted: 1, rejected: 2 });
+        assert!(store.adjustment("smor", "amor") > 0.0);
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn test_round_trip_through_shared_context() {
+        let mut store = FeedbackStore::new();
+        store.record(FeedbackEvent::accepted("smor", "amor", &[]));
+        store.record(FeedbackEvent::accepted("smor", "amor", &[]));
+
+        let mut context = SharedContext::new();
+        store.persist_pair(&mut context, "smor", "amor").unwrap();
+        assert_eq!(context.get("feedback:smor:amor").unwrap().source, Source::User);
+
+        let restored = FeedbackStore::from_context(&context);
+        assert_eq!(restored.pair("smor", "amor"), PairStats { accepted: 2, rejected: 0 });
+    }
+}
@@ -0,0 +1,370 @@
+//! # Agreement Module
+//!
+//! Verificación de concordancia gramatical:
+//! - Sintagma nominal: determinante, sustantivo y adjetivos concuerdan
+//!   en género y número ("la casa azul", no "la coliseo" ni "la casa azules")
+//! - Sujeto y verbo concuerdan en persona y número ("yo visito", no "yo visita")
+//!
+//! Cada discordancia produce un `GrammarIssue` posicionado.
+
+use super::checker::{RULE_GENDER_AGREEMENT, RULE_NUMBER_AGREEMENT, RULE_STRESSED_A, RULE_SUBJECT_VERB};
+use super::inflection::Inflector;
+use super::{Conjugation, Gender, GrammarIssue, IssueSeverity, Number, Person, PronounCase, TokenType, VerbInfo};
+
+/// Detecta todas las discordancias de una oración clasificada
+pub(super) fn check_agreement(
+    tokens: &[String],
+    types: &[TokenType],
+    inflector: &Inflector,
+) -> Vec<GrammarIssue> {
+    let mut issues = check_noun_phrases(tokens, types, inflector);
+    issues.extend(check_subject_verb(tokens, types, inflector));
+    issues.sort_by_key(|issue| issue.position);
+    issues
+}
+
+/// Concordancia dentro de cada sintagma nominal: [Art] [Adj]* Sust [Adj]*
+fn check_noun_phrases(tokens: &[String], types: &[TokenType], inflector: &Inflector) -> Vec<GrammarIssue> {
+    let mut issues = Vec::new();
+
+    for (i, tt) in types.iter().enumerate() {
+        let noun = match tt {
+            TokenType::Noun(info) => info,
+            _ => continue,
+        };
+        let noun_word = tokens[i].to_lowercase();
+        let invariable_number = inflector.is_number_invariable(&noun_word);
+
+        // Modificadores a la izquierda (contiguos)
+        let mut left = i;
+        while left > 0 && matches!(types[left - 1], TokenType::Article(_) | TokenType::Adjective) {
+            left -= 1;
+        }
+
+        // Adjetivos a la derecha (contiguos)
+        let mut right = i + 1;
+        while right < types.len() && matches!(types[right], TokenType::Adjective) {
+            right += 1;
+        }
+
+        for j in (left..i).chain(i + 1..right) {
+            let word = tokens[j].to_lowercase();
+            let (gender, number, kind) = match &types[j] {
+                TokenType::Article(info) => (info.gender.clone(), info.number.clone(), "artículo"),
+                TokenType::Adjective => {
+                    let analysis = inflector.analyze_adjective(&word);
+                    (analysis.gender, analysis.number, "adjetivo")
+                }
+                _ => continue,
+            };
+
+            // Forma que concordaría con el sustantivo
+            let suggestion = match &types[j] {
+                TokenType::Article(info) => Some(article_form(
+                    info.definite,
+                    &noun.gender,
+                    &noun.number,
+                    j + 1 == i && inflector.takes_masculine_article(&noun_word),
+                )),
+                _ => agreeing_adjective(&word, &noun.gender, &noun.number, inflector),
+            };
+
+            // "el agua", "un águila": artículo masculino ante "a" tónica
+            let before_stressed_a = kind == "artículo"
+                && j + 1 == i
+                && number == Number::Singular
+                && inflector.takes_masculine_article(&noun_word);
+
+            if before_stressed_a {
+                if !matches!(word.as_str(), "el" | "un") {
+                    issues.push(GrammarIssue {
+                        position: j,
+                        severity: IssueSeverity::Error,
+                        message: format!("Ante '{}' (a tónica) se usa 'el'/'un' en singular", noun_word),
+                        rule: RULE_STRESSED_A,
+                        span: None,
+                        suggestion: suggestion.clone(),
+                    });
+                }
+            } else if !genders_agree(&gender, &noun.gender) {
+                issues.push(GrammarIssue {
+                    position: j,
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "Discordancia de género: {} '{}' ({}) con sustantivo '{}' ({})",
+                        kind, word, gender_name(&gender), noun_word, gender_name(&noun.gender)
+                    ),
+                    rule: RULE_GENDER_AGREEMENT,
+                    span: None,
+                    suggestion: suggestion.clone(),
+                });
+            }
+
+            if !invariable_number && number != noun.number {
+                issues.push(GrammarIssue {
+                    position: j,
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "Discordancia de número: {} '{}' ({}) con sustantivo '{}' ({})",
+                        kind, word, number_name(&number), noun_word, number_name(&noun.number)
+                    ),
+                    rule: RULE_NUMBER_AGREEMENT,
+                    span: None,
+                    suggestion,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Concordancia sujeto–verbo en persona y número
+fn check_subject_verb(tokens: &[String], types: &[TokenType], inflector: &Inflector) -> Vec<GrammarIssue> {
+    let mut issues = Vec::new();
+
+    let verb_pos = match types.iter().position(|t| matches!(t, TokenType::Verb(_))) {
+        Some(p) => p,
+        None => return issues,
+    };
+    let verb_word = tokens[verb_pos].to_lowercase();
+    let (verb, conjugation) = match &types[verb_pos] {
+        TokenType::Verb(info) => match info.conjugations.get(&verb_word) {
+            Some(c) => (info, c),
+            None => return issues,
+        },
+        _ => return issues,
+    };
+
+    let subject = match find_subject(tokens, types, verb_pos, inflector) {
+        Some(s) => s,
+        None => return issues,
+    };
+
+    let person_ok = subject.person == conjugation.person;
+    let number_ok = subject.number.as_ref().is_none_or(|n| *n == conjugation.number);
+
+    if !person_ok || !number_ok {
+        issues.push(GrammarIssue {
+            position: verb_pos,
+            severity: IssueSeverity::Error,
+            message: format!(
+                "Discordancia sujeto-verbo: '{}' ({} persona) con verbo '{}' ({} persona {})",
+                tokens[subject.position].to_lowercase(),
+                person_name(&subject.person),
+                verb_word,
+                person_name(&conjugation.person),
+                number_name(&conjugation.number),
+            ),
+            rule: RULE_SUBJECT_VERB,
+            span: None,
+            suggestion: agreeing_conjugation(verb, &subject, conjugation),
+        });
+    }
+
+    issues
+}
+
+/// Sujeto identificado para la concordancia con el verbo
+struct Subject {
+    position: usize,
+    person: Person,
+    /// None si el sustantivo es invariable en número
+    number: Option<Number>,
+}
+
+/// Busca el sujeto explícito del primer verbo
+fn find_subject(tokens: &[String], types: &[TokenType], verb_pos: usize, inflector: &Inflector) -> Option<Subject> {
+    // 1. Pronombre sujeto antes del verbo o inmediatamente después ("¿vas tú?")
+    let pronoun_positions = (0..verb_pos).chain(verb_pos + 1..(verb_pos + 2).min(types.len()));
+    for i in pronoun_positions {
+        if let TokenType::Pronoun(info) = &types[i] {
+            if info.case == PronounCase::Subject {
+                return Some(Subject { position: i, person: info.person.clone(), number: Some(info.number.clone()) });
+            }
+        }
+    }
+
+    // 2. Pronombre dativo o "se" pasivo antes del verbo ("me gusta X", "se venden X"):
+    //    el sujeto va después
+    let dative_before = types[..verb_pos].iter().any(|t| {
+        matches!(t, TokenType::Pronoun(info)
+            if matches!(info.case, PronounCase::IndirectObj | PronounCase::DirectObj | PronounCase::Passive))
+    });
+    let range: Vec<usize> = if dative_before {
+        (verb_pos + 1..types.len()).collect()
+    } else {
+        (0..verb_pos).collect()
+    };
+
+    // 3. Primer sustantivo fuera de complemento preposicional
+    let mut first: Option<usize> = None;
+    let mut coordinated = false;
+    let mut in_pp = false;
+    for &i in &range {
+        match &types[i] {
+            TokenType::Preposition => in_pp = true,
+            TokenType::Verb(_) => break,
+            TokenType::Conjunction if first.is_some() => {
+                coordinated = matches!(tokens[i].to_lowercase().as_str(), "y" | "e");
+                in_pp = false;
+            }
+            TokenType::Noun(_) if in_pp => in_pp = false,
+            TokenType::Noun(_) if first.is_none() => first = Some(i),
+            TokenType::Noun(_) if coordinated => {
+                // "Juan y María corren": sujeto coordinado en plural
+                return first.map(|p| Subject { position: p, person: Person::Third, number: Some(Number::Plural) });
+            }
+            _ => {}
+        }
+    }
+
+    first.and_then(|p| match &types[p] {
+        TokenType::Noun(info) => {
+            let number = if inflector.is_number_invariable(&tokens[p]) {
+                None
+            } else {
+                Some(info.number.clone())
+            };
+            Some(Subject { position: p, person: Person::Third, number })
+        }
+        _ => None,
+    })
+}
+
+/// Artículo con el género y número dados ("el" ante femenino con a tónica)
+fn article_form(definite: bool, gender: &Gender, number: &Number, stressed_a: bool) -> String {
+    let feminine = *gender == Gender::Feminine && !(stressed_a && *number == Number::Singular);
+    let form = match (definite, feminine, number) {
+        (true, false, Number::Singular) => "el",
+        (true, true, Number::Singular) => "la",
+        (true, false, Number::Plural) => "los",
+        (true, true, Number::Plural) => "las",
+        (false, false, Number::Singular) => "un",
+        (false, true, Number::Singular) => "una",
+        (false, false, Number::Plural) => "unos",
+        (false, true, Number::Plural) => "unas",
+    };
+    form.to_string()
+}
+
+/// Forma del adjetivo que concuerda con el sustantivo
+fn agreeing_adjective(word: &str, gender: &Gender, number: &Number, inflector: &Inflector) -> Option<String> {
+    let lemma = inflector.analyze_adjective(word).lemma;
+    inflector.adjective_forms(&lemma)
+        .into_iter()
+        .find(|f| genders_agree(&f.gender, gender) && f.number == *number)
+        .map(|f| f.form)
+}
+
+/// Conjugación del mismo verbo y tiempo que concuerda con el sujeto
+fn agreeing_conjugation(verb: &VerbInfo, subject: &Subject, current: &Conjugation) -> Option<String> {
+    let number = subject.number.clone().unwrap_or_else(|| current.number.clone());
+    verb.conjugations
+        .iter()
+        .filter(|(_, c)| c.person == subject.person && c.number == number && c.tense == current.tense)
+        .map(|(form, _)| form.clone())
+        .min()
+}
+
+/// El género neutro (común) concuerda con cualquiera
+fn genders_agree(a: &Gender, b: &Gender) -> bool {
+    *a == Gender::Neutral || *b == Gender::Neutral || a == b
+}
+
+fn gender_name(gender: &Gender) -> &'static str {
+    match gender {
+        Gender::Masculine => "masculino",
+        Gender::Feminine => "femenino",
+        Gender::Neutral => "común",
+    }
+}
+
+fn number_name(number: &Number) -> &'static str {
+    match number {
+        Number::Singular => "singular",
+        Number::Plural => "plural",
+    }
+}
+
+fn person_name(person: &Person) -> &'static str {
+    match person {
+        Person::First => "primera",
+        Person::Second => "segunda",
+        Person::Third => "tercera",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::{Gender, IssueSeverity, NounCategory, NounInfo, Number, SpanishGrammar};
+
+    fn tokens(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn grammar() -> SpanishGrammar {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun("coliseo", NounInfo {
+            gender: Gender::Masculine,
+            number: Number::Singular,
+            category: NounCategory::Place,
+            can_be_subject: false,
+            can_be_object: true,
+        });
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar.add_noun_forms("agua", NounCategory::Thing);
+        grammar.add_adjective_forms("azul");
+        grammar.add_adjective_forms("romano");
+        grammar
+    }
+
+    #[test]
+    fn test_noun_phrase_gender() {
+        let grammar = grammar();
+
+        let good = grammar.analyze(&tokens("visité el coliseo romano"));
+        assert!(good.issues.is_empty());
+
+        let bad = grammar.analyze(&tokens("visité la coliseo"));
+        assert_eq!(bad.issues.len(), 1);
+        assert_eq!(bad.issues[0].position, 1);
+        assert_eq!(bad.issues[0].severity, IssueSeverity::Error);
+        assert!(bad.validity_score < good.validity_score);
+    }
+
+    #[test]
+    fn test_noun_phrase_number() {
+        let grammar = grammar();
+
+        assert!(grammar.analyze(&tokens("me gustan las casas azules")).issues.is_empty());
+
+        let bad = grammar.analyze(&tokens("me gusta la casa azules"));
+        assert_eq!(bad.issues.len(), 1);
+        assert_eq!(bad.issues[0].position, 4);
+    }
+
+    #[test]
+    fn test_stressed_a_article() {
+        let grammar = grammar();
+
+        assert!(grammar.analyze(&tokens("me gusta el agua")).issues.is_empty());
+        assert!(grammar.analyze(&tokens("me gustan las aguas")).issues.is_empty());
+        assert!(!grammar.analyze(&tokens("me gusta la agua")).issues.is_empty());
+    }
+
+    #[test]
+    fn test_subject_verb() {
+        let grammar = grammar();
+
+        assert!(grammar.analyze(&tokens("yo visito el coliseo")).issues.is_empty());
+        assert!(grammar.analyze(&tokens("nosotros corremos")).issues.is_empty());
+
+        let bad = grammar.analyze(&tokens("yo visita el coliseo"));
+        assert_eq!(bad.issues.len(), 1);
+        assert_eq!(bad.issues[0].position, 1);
+
+        // "Me gustan" concuerda con el sujeto pospuesto
+        assert!(!grammar.analyze(&tokens("me gusta las casas")).issues.is_empty());
+    }
+}
@@ -0,0 +1,368 @@
+//! # Grammar Checker Module
+//!
+//! Modo "revisor": señala problemas gramaticales sin modificar el texto.
+//! Cada problema incluye el identificador de la regla, el rango de bytes
+//! en el texto original y, cuando es posible, un reemplazo sugerido.
+//!
+//! ## Ejemplos
+//! - "la casa azules" → concordancia-numero, sugerencia "azul"
+//! - "voy a el parque" → contraccion, sugerencia "al"
+//! - "cómo estás?" → interrogacion-inicial, sugerencia "¿cómo"
+
+use std::collections::HashSet;
+use super::{GrammarIssue, IssueSeverity, SpanishGrammar};
+
+// === IDENTIFICADORES DE REGLAS ===
+
+/// Género entre determinante/adjetivo y sustantivo
+pub const RULE_GENDER_AGREEMENT: &str = "concordancia-genero";
+/// Número entre determinante/adjetivo y sustantivo
+pub const RULE_NUMBER_AGREEMENT: &str = "concordancia-numero";
+/// Persona y número entre sujeto y verbo
+pub const RULE_SUBJECT_VERB: &str = "concordancia-sujeto-verbo";
+/// "el agua", no "la agua"
+pub const RULE_STRESSED_A: &str = "articulo-a-tonica";
+/// "al"/"del" en lugar de "a el"/"de el"
+pub const RULE_CONTRACTION: &str = "contraccion";
+/// Falta el signo de apertura "¿"
+pub const RULE_OPENING_QUESTION: &str = "interrogacion-inicial";
+/// Falta el signo de apertura "¡"
+pub const RULE_OPENING_EXCLAMATION: &str = "exclamacion-inicial";
+/// "le"/"les" como objeto directo (según la región)
+pub const RULE_LEISMO: &str = "leismo";
+
+/// Descripción de una regla del revisor
+#[derive(Debug, Clone)]
+pub struct RuleInfo {
+    pub id: &'static str,
+    pub description: &'static str,
+}
+
+/// Catálogo de reglas disponibles
+pub const RULES: &[RuleInfo] = &[
+    RuleInfo { id: RULE_GENDER_AGREEMENT, description: "Concordancia de género en el sintagma nominal" },
+    RuleInfo { id: RULE_NUMBER_AGREEMENT, description: "Concordancia de número en el sintagma nominal" },
+    RuleInfo { id: RULE_SUBJECT_VERB, description: "Concordancia de persona y número entre sujeto y verbo" },
+    RuleInfo { id: RULE_STRESSED_A, description: "Artículo 'el'/'un' ante sustantivo femenino con a tónica" },
+    RuleInfo { id: RULE_CONTRACTION, description: "Contracciones obligatorias 'al' y 'del'" },
+    RuleInfo { id: RULE_OPENING_QUESTION, description: "Signo de apertura de interrogación '¿'" },
+    RuleInfo { id: RULE_OPENING_EXCLAMATION, description: "Signo de apertura de exclamación '¡'" },
+    RuleInfo { id: RULE_LEISMO, description: "'le'/'les' como objeto directo (leísmo no admitido en la región)" },
+];
+
+/// Revisor gramatical que reporta problemas sin corregir
+#[derive(Debug, Clone)]
+pub struct GrammarChecker {
+    /// Gramática usada para el análisis
+    grammar: SpanishGrammar,
+
+    /// Reglas desactivadas
+    disabled: HashSet<&'static str>,
+}
+
+/// Token del texto original con su rango de bytes
+#[derive(Debug, Clone)]
+pub(super) struct SpannedToken {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl GrammarChecker {
+    /// Crea revisor con la gramática base
+    pub fn new() -> Self {
+        Self::with_grammar(SpanishGrammar::new())
+    }
+
+    /// Crea revisor con una gramática ya cargada (p. ej. desde diccionario)
+    pub fn with_grammar(grammar: SpanishGrammar) -> Self {
+        Self {
+            grammar,
+            disabled: HashSet::new(),
+        }
+    }
+
+    /// Desactiva una regla. Retorna false si la regla no existe
+    pub fn disable_rule(&mut self, rule: &str) -> bool {
+        match RULES.iter().find(|r| r.id == rule) {
+            Some(info) => {
+                self.disabled.insert(info.id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reactiva una regla. Retorna false si la regla no existe
+    pub fn enable_rule(&mut self, rule: &str) -> bool {
+        if RULES.iter().any(|r| r.id == rule) {
+            self.disabled.remove(rule);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Verifica si una regla está activa
+    pub fn is_enabled(&self, rule: &str) -> bool {
+        !self.disabled.contains(rule)
+    }
+
+    /// Gramática usada por el revisor
+    pub fn grammar(&self) -> &SpanishGrammar {
+        &self.grammar
+    }
+
+    /// Revisa un texto y retorna los problemas ordenados por posición.
+    /// `position` es el índice de palabra en el texto completo
+    pub fn check(&self, text: &str) -> Vec<GrammarIssue> {
+        let tokens = tokenize(text);
+        let mut issues = Vec::new();
+
+        // Índice global de palabra para cada token (None = puntuación)
+        let mut word_index = Vec::with_capacity(tokens.len());
+        let mut count = 0;
+        for token in &tokens {
+            if is_word(&token.text) {
+                word_index.push(Some(count));
+                count += 1;
+            } else {
+                word_index.push(None);
+            }
+        }
+
+        for (start, end) in sentence_ranges(&tokens) {
+            let sentence = &tokens[start..end];
+            let words: Vec<usize> = (start..end).filter(|&i| word_index[i].is_some()).collect();
+
+            self.check_opening_marks(sentence, &tokens, &words, &word_index, &mut issues);
+            self.check_contractions(&tokens, &words, &word_index, &mut issues);
+
+            // Análisis gramatical (concordancia) sobre las palabras de la oración
+            let word_texts: Vec<String> = words.iter().map(|&i| tokens[i].text.clone()).collect();
+            let analysis = self.grammar.analyze(&word_texts);
+            for mut issue in analysis.issues {
+                let token = &tokens[words[issue.position]];
+                issue.span = Some((token.start, token.end));
+                issue.position = word_index[words[issue.position]].unwrap_or(0);
+                issues.push(issue);
+            }
+        }
+
+        issues.retain(|issue| self.is_enabled(issue.rule));
+        issues.sort_by_key(|issue| (issue.span.map(|s| s.0).unwrap_or(0), issue.rule));
+        issues
+    }
+
+    /// "¿" y "¡" de apertura
+    fn check_opening_marks(
+        &self,
+        sentence: &[SpannedToken],
+        tokens: &[SpannedToken],
+        words: &[usize],
+        word_index: &[Option<usize>],
+        issues: &mut Vec<GrammarIssue>,
+    ) {
+        let first = match words.first() {
+            Some(&i) => i,
+            None => return,
+        };
+
+        let marks = [("?", "¿", RULE_OPENING_QUESTION, "interrogación"),
+                     ("!", "¡", RULE_OPENING_EXCLAMATION, "exclamación")];
+
+        for (closing, opening, rule, name) in marks {
+            let has_closing = sentence.iter().any(|t| t.text == closing);
+            let has_opening = sentence.iter().any(|t| t.text == opening);
+
+            if has_closing && !has_opening {
+                let token = &tokens[first];
+                issues.push(GrammarIssue {
+                    position: word_index[first].unwrap_or(0),
+                    severity: IssueSeverity::Error,
+                    message: format!("Falta el signo de apertura de {} '{}'", name, opening),
+                    rule,
+                    span: Some((token.start, token.end)),
+                    suggestion: Some(format!("{}{}", opening, token.text)),
+                });
+            }
+        }
+    }
+
+    /// "a el" → "al", "de el" → "del"
+    fn check_contractions(
+        &self,
+        tokens: &[SpannedToken],
+        words: &[usize],
+        word_index: &[Option<usize>],
+        issues: &mut Vec<GrammarIssue>,
+    ) {
+        for pair in words.windows(2) {
+            let (prep, article) = (&tokens[pair[0]], &tokens[pair[1]]);
+
+            // Solo palabras contiguas; "El" en mayúscula suele ser nombre propio
+            if pair[1] != pair[0] + 1 || article.text != "el" {
+                continue;
+            }
+
+            let contraction = match prep.text.to_lowercase().as_str() {
+                "a" => "al",
+                "de" => "del",
+                _ => continue,
+            };
+            let suggestion = if prep.text.starts_with(char::is_uppercase) {
+                let mut chars = contraction.chars();
+                chars.next().map(|c| c.to_uppercase().collect::<String>() + chars.as_str())
+                    .unwrap_or_default()
+            } else {
+                contraction.to_string()
+            };
+
+            issues.push(GrammarIssue {
+                position: word_index[pair[0]].unwrap_or(0),
+                severity: IssueSeverity::Error,
+                message: format!("'{} el' debe contraerse en '{}'", prep.text, suggestion),
+                rule: RULE_CONTRACTION,
+                span: Some((prep.start, article.end)),
+                suggestion: Some(suggestion),
+            });
+        }
+    }
+}
+
+impl Default for GrammarChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tokeniza conservando rangos de bytes; la puntuación va como token aparte
+pub(super) fn tokenize(text: &str) -> Vec<SpannedToken> {
+    let mut tokens = Vec::new();
+    let mut current_start: Option<usize> = None;
+
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            if current_start.is_none() {
+                current_start = Some(i);
+            }
+            continue;
+        }
+
+        if let Some(start) = current_start.take() {
+            tokens.push(SpannedToken { text: text[start..i].to_string(), start, end: i });
+        }
+
+        if !c.is_whitespace() {
+            let end = i + c.len_utf8();
+            tokens.push(SpannedToken { text: text[i..end].to_string(), start: i, end });
+        }
+    }
+
+    if let Some(start) = current_start {
+        tokens.push(SpannedToken { text: text[start..].to_string(), start, end: text.len() });
+    }
+
+    tokens
+}
+
+fn is_word(token: &str) -> bool {
+    token.chars().any(|c| c.is_alphanumeric())
+}
+
+/// Divide los tokens en oraciones terminadas en '.', '?', '!' o '…'
+pub(super) fn sentence_ranges(tokens: &[SpannedToken]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if matches!(token.text.as_str(), "." | "?" | "!" | "…") {
+            // Agrupar "?!" y similares en la misma oración
+            let next_is_terminal = tokens.get(i + 1)
+                .is_some_and(|t| matches!(t.text.as_str(), "." | "?" | "!"));
+            if !next_is_terminal {
+                ranges.push((start, i + 1));
+                start = i + 1;
+            }
+        }
+    }
+
+    if start < tokens.len() {
+        ranges.push((start, tokens.len()));
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::NounCategory;
+
+    fn checker() -> GrammarChecker {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar.add_noun_forms("parque", NounCategory::Place);
+        grammar.add_adjective_forms("azul");
+        GrammarChecker::with_grammar(grammar)
+    }
+
+    #[test]
+    fn test_number_agreement_with_span() {
+        let checker = checker();
+        let text = "Me gusta la casa azules.";
+
+        let issues = checker.check(text);
+        assert_eq!(issues.len(), 1);
+
+        let issue = &issues[0];
+        assert_eq!(issue.rule, RULE_NUMBER_AGREEMENT);
+        let (start, end) = issue.span.unwrap();
+        assert_eq!(&text[start..end], "azules");
+        assert_eq!(issue.suggestion.as_deref(), Some("azul"));
+    }
+
+    #[test]
+    fn test_contraction() {
+        let checker = checker();
+        let text = "Voy a el parque";
+
+        let issues = checker.check(text);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, RULE_CONTRACTION);
+        let (start, end) = issues[0].span.unwrap();
+        assert_eq!(&text[start..end], "a el");
+        assert_eq!(issues[0].suggestion.as_deref(), Some("al"));
+
+        // "El Salvador" no se contrae
+        assert!(checker.check("Voy a El Salvador").is_empty());
+    }
+
+    #[test]
+    fn test_opening_question_mark() {
+        let checker = checker();
+
+        let issues = checker.check("Te gusta la casa? ¡Sí!");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, RULE_OPENING_QUESTION);
+        assert_eq!(issues[0].suggestion.as_deref(), Some("¿Te"));
+
+        assert!(checker.check("¿Te gusta la casa?").is_empty());
+    }
+
+    #[test]
+    fn test_disable_rule() {
+        let mut checker = checker();
+        let text = "Voy a el parque?";
+        assert_eq!(checker.check(text).len(), 2);
+
+        assert!(checker.disable_rule(RULE_CONTRACTION));
+        let issues = checker.check(text);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, RULE_OPENING_QUESTION);
+
+        assert!(checker.enable_rule(RULE_CONTRACTION));
+        assert_eq!(checker.check(text).len(), 2);
+        assert!(!checker.disable_rule("regla-inexistente"));
+    }
+}
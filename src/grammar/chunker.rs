@@ -0,0 +1,297 @@
+//! # Chunker Module
+//!
+//! Análisis sintáctico superficial: agrupa tokens en sintagmas.
+//! - SN: [Art] [Adj]* Sust [Adj]* [de SN]* ("la casa azul de Rosita")
+//! - SP: Prep SN ("en Roma")
+//! - SV: [clíticos] [no] Verbo+ ("me gusta", "no corro")
+//!
+//! Los SN coordinados con "y"/"o" forman un solo sintagma ("Juan y María").
+
+use crate::tao::GrammaticalRole;
+use super::{is_coordinating, PronounCase, TokenType};
+
+/// Tipo de sintagma
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ChunkKind {
+    NounPhrase,
+    PrepositionalPhrase,
+    VerbPhrase,
+    /// Token que no forma sintagma (adverbio, conjunción, adjetivo predicativo...)
+    Single,
+}
+
+/// Sintagma con su rango de tokens y núcleo
+#[derive(Debug, Clone)]
+pub(super) struct Chunk {
+    pub kind: ChunkKind,
+    /// Índices contiguos en la oración
+    pub tokens: Vec<usize>,
+    /// Núcleo: sustantivo del SN/SP, verbo del SV
+    pub head: usize,
+}
+
+/// Agrupa una oración clasificada en sintagmas (tokens desconocidos sueltos se omiten)
+pub(super) fn chunk(tokens: &[String], types: &[TokenType]) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut i = 0;
+
+    while i < types.len() {
+        // Sintagma verbal
+        if let Some((end, head)) = parse_verb_phrase(types, tokens, i) {
+            chunks.push(Chunk { kind: ChunkKind::VerbPhrase, tokens: (i..end).collect(), head });
+            i = end;
+            continue;
+        }
+
+        // Sintagma preposicional
+        if matches!(types[i], TokenType::Preposition) {
+            if let Some((end, head)) = parse_noun_phrase(types, tokens, i + 1, true) {
+                chunks.push(Chunk { kind: ChunkKind::PrepositionalPhrase, tokens: (i..end).collect(), head });
+                i = end;
+                continue;
+            }
+        }
+
+        // Sintagma nominal (con coordinación)
+        if let Some((mut end, head)) = parse_noun_phrase(types, tokens, i, false) {
+            while let Some(next_end) = parse_coordinated(types, tokens, end) {
+                end = next_end;
+            }
+            chunks.push(Chunk { kind: ChunkKind::NounPhrase, tokens: (i..end).collect(), head });
+            i = end;
+            continue;
+        }
+
+        if !matches!(types[i], TokenType::Unknown) {
+            chunks.push(Chunk { kind: ChunkKind::Single, tokens: vec![i], head: i });
+        }
+        i += 1;
+    }
+
+    chunks
+}
+
+/// Rol gramatical de cada sintagma (None para tokens sin rol)
+pub(super) fn assign_roles(types: &[TokenType], chunks: &[Chunk]) -> Vec<Option<GrammaticalRole>> {
+    let first_vp = chunks.iter().position(|c| c.kind == ChunkKind::VerbPhrase);
+
+    // "Me gusta X", "se venden X": con dativo o "se" pasivo en el SV, el sujeto va pospuesto
+    let dative_vp = first_vp.is_some_and(|v| {
+        chunks[v].tokens.iter().any(|&t| matches!(&types[t], TokenType::Pronoun(info)
+            if matches!(info.case, PronounCase::IndirectObj | PronounCase::DirectObj | PronounCase::Passive)))
+    });
+    let mut subject_found = false;
+
+    chunks.iter().enumerate().map(|(ci, chunk)| {
+        let role = match chunk.kind {
+            ChunkKind::VerbPhrase => GrammaticalRole::Verb,
+            ChunkKind::PrepositionalPhrase => GrammaticalRole::Complement,
+            ChunkKind::NounPhrase => {
+                // Sujeto u objeto según posición respecto al verbo
+                let is_subject = match first_vp {
+                    Some(v) if ci < v => true,
+                    Some(_) => dative_vp && !subject_found,
+                    None => false,
+                };
+                if is_subject {
+                    subject_found = true;
+                    GrammaticalRole::Subject
+                } else {
+                    GrammaticalRole::DirectObject
+                }
+            }
+            ChunkKind::Single => match &types[chunk.head] {
+                TokenType::Article(_) => GrammaticalRole::Article,
+                TokenType::Adjective => GrammaticalRole::Adjective,
+                TokenType::Preposition => GrammaticalRole::Preposition,
+                TokenType::Adverb => GrammaticalRole::Adverb,
+                TokenType::Conjunction => GrammaticalRole::Conjunction,
+                TokenType::Pronoun(info) => match info.case {
+                    PronounCase::IndirectObj => GrammaticalRole::IndirectObject,
+                    _ => GrammaticalRole::DirectObject,
+                },
+                _ => return None,
+            },
+        };
+        Some(role)
+    }).collect()
+}
+
+/// [clíticos] [no] Verbo+ → (fin, núcleo)
+fn parse_verb_phrase(types: &[TokenType], tokens: &[String], start: usize) -> Option<(usize, usize)> {
+    let mut j = start;
+
+    while j < types.len() {
+        let is_clitic = matches!(&types[j], TokenType::Pronoun(info) if info.case != PronounCase::Subject);
+        let is_negation = matches!(types[j], TokenType::Adverb) && tokens[j].to_lowercase() == "no";
+        if !(is_clitic || is_negation) {
+            break;
+        }
+        j += 1;
+    }
+
+    if j >= types.len() || !matches!(types[j], TokenType::Verb(_)) {
+        return None;
+    }
+
+    let head = j;
+    while j < types.len() && matches!(types[j], TokenType::Verb(_)) {
+        j += 1;
+    }
+
+    Some((j, head))
+}
+
+/// [Art] [Adj]* Núcleo [Adj]* [de SN]* → (fin, núcleo).
+/// `after_preposition` permite un núcleo desconocido sin determinante ("en smor")
+fn parse_noun_phrase(
+    types: &[TokenType],
+    tokens: &[String],
+    start: usize,
+    after_preposition: bool,
+) -> Option<(usize, usize)> {
+    if start >= types.len() {
+        return None;
+    }
+
+    // Pronombre sujeto: SN de un solo token
+    if matches!(&types[start], TokenType::Pronoun(info) if info.case == PronounCase::Subject) {
+        return Some((start + 1, start));
+    }
+
+    let mut j = start;
+    let mut has_determiner = false;
+    if matches!(types[j], TokenType::Article(_)) {
+        has_determiner = true;
+        j += 1;
+    }
+
+    let mut last_adjective = None;
+    while j < types.len() && matches!(types[j], TokenType::Adjective) {
+        last_adjective = Some(j);
+        j += 1;
+    }
+
+    // Núcleo: sustantivo conocido, o desconocido si el contexto lo licencia
+    let is_head = j < types.len()
+        && match types[j] {
+            TokenType::Noun(_) => true,
+            TokenType::Unknown => {
+                is_word(&tokens[j])
+                    && (has_determiner || last_adjective.is_some() || after_preposition || is_proper_noun(tokens, j))
+            }
+            _ => false,
+        };
+
+    let head = if is_head {
+        j += 1;
+        j - 1
+    } else if has_determiner {
+        // Adjetivo sustantivado: "el grande"
+        last_adjective?
+    } else {
+        return None;
+    };
+
+    // Adjetivos pospuestos
+    while j < types.len() && matches!(types[j], TokenType::Adjective) {
+        j += 1;
+    }
+
+    // Complementos con "de": "la casa de Rosita"
+    while j < types.len()
+        && matches!(types[j], TokenType::Preposition)
+        && tokens[j].to_lowercase() == "de"
+    {
+        match parse_noun_phrase(types, tokens, j + 1, true) {
+            Some((end, _)) => j = end,
+            None => break,
+        }
+    }
+
+    Some((j, head))
+}
+
+/// "y"/"e"/"o"/"u" + SN → nuevo fin del sintagma coordinado
+fn parse_coordinated(types: &[TokenType], tokens: &[String], at: usize) -> Option<usize> {
+    if at >= types.len() || !matches!(types[at], TokenType::Conjunction) {
+        return None;
+    }
+    if !matches!(tokens[at].to_lowercase().as_str(), "y" | "e" | "o" | "u") {
+        return None;
+    }
+    parse_noun_phrase(types, tokens, at + 1, false).map(|(end, _)| end)
+}
+
+fn is_word(token: &str) -> bool {
+    token.chars().any(|c| c.is_alphabetic())
+}
+
+/// Palabra en mayúscula fuera del inicio de oración ("Rosita"). Al inicio
+/// la mayúscula no basta: solo si se coordina con otro nombre ("Juan y María")
+fn is_proper_noun(tokens: &[String], i: usize) -> bool {
+    let capitalized = |t: &String| t.starts_with(char::is_uppercase);
+    if !capitalized(&tokens[i]) {
+        return false;
+    }
+    i > 0 || (tokens.get(1).is_some_and(|t| is_coordinating(t)) && tokens.get(2).is_some_and(capitalized))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{NounCategory, SpanishGrammar};
+
+    fn chunks_of(grammar: &SpanishGrammar, sentence: &str) -> Vec<(ChunkKind, Vec<usize>, usize)> {
+        let tokens: Vec<String> = sentence.split_whitespace().map(String::from).collect();
+        let types: Vec<TokenType> = tokens.iter().map(|t| grammar.classify_token(t)).collect();
+        chunk(&tokens, &types)
+            .into_iter()
+            .map(|c| (c.kind, c.tokens, c.head))
+            .collect()
+    }
+
+    fn grammar() -> SpanishGrammar {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar.add_noun_forms("coliseo", NounCategory::Place);
+        grammar.add_adjective_forms("azul");
+        grammar.add_adjective_forms("romano");
+        grammar
+    }
+
+    #[test]
+    fn test_noun_phrase_with_de_complement() {
+        let grammar = grammar();
+        let chunks = chunks_of(&grammar, "la casa azul de Rosita me gusta");
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], (ChunkKind::NounPhrase, vec![0, 1, 2, 3, 4], 1));
+        assert_eq!(chunks[1], (ChunkKind::VerbPhrase, vec![5, 6], 6));
+    }
+
+    #[test]
+    fn test_prepositional_phrase() {
+        let grammar = grammar();
+        let chunks = chunks_of(&grammar, "visité el coliseo romano en smor");
+
+        assert_eq!(chunks[0], (ChunkKind::VerbPhrase, vec![0], 0));
+        assert_eq!(chunks[1], (ChunkKind::NounPhrase, vec![1, 2, 3], 2));
+        assert_eq!(chunks[2], (ChunkKind::PrepositionalPhrase, vec![4, 5], 5));
+    }
+
+    #[test]
+    fn test_coordination_and_negation() {
+        let grammar = grammar();
+        let chunks = chunks_of(&grammar, "la casa y el coliseo no son");
+
+        assert_eq!(chunks[0], (ChunkKind::NounPhrase, vec![0, 1, 2, 3, 4], 1));
+        assert_eq!(chunks[1], (ChunkKind::VerbPhrase, vec![5, 6], 6));
+
+        // Nombres propios coordinados al inicio de la oración
+        let chunks = chunks_of(&grammar, "Juan y María visité");
+        assert_eq!(chunks[0], (ChunkKind::NounPhrase, vec![0, 1, 2], 0));
+        let chunks = chunks_of(&grammar, "Smor visité");
+        assert_eq!(chunks[0], (ChunkKind::VerbPhrase, vec![1], 1));
+    }
+}
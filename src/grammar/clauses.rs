@@ -0,0 +1,352 @@
+//! # Clauses Module
+//!
+//! Segmentación en cláusulas: cada verbo conjugado encabeza la suya.
+//! "Requiero que me diseñes un producto que sustituya al propofol" →
+//! - Principal: "Requiero"
+//!   - Completiva: "que me diseñes un producto"
+//!     - Relativa (antecedente "producto"): "que sustituya al propofol"
+//!
+//! Nexos reconocidos:
+//! - "que" completivo (tras verbo) o relativo (tras sustantivo)
+//! - Adverbiales: porque, aunque, cuando, si, mientras, como, donde, pues, para que
+//! - Coordinación: y/e/o/u/pero/sino/ni entre verbos conjugados
+//!
+//! Un nexo solo abre cláusula si le sigue un verbo conjugado antes del
+//! siguiente nexo ("más barato que él" y "tiene que ser" no son cláusulas).
+
+use crate::tao::{GrammaticalComponent, GrammaticalRole};
+use super::chunker;
+use super::TokenType;
+
+const SUBORDINATORS: [&str; 9] = ["que", "porque", "aunque", "cuando", "si", "mientras", "como", "donde", "pues"];
+const COORDINATORS: [&str; 8] = ["y", "e", "o", "u", "pero", "sino", "ni", "mas"];
+
+/// Tipo de cláusula
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClauseKind {
+    /// Oración principal
+    Main,
+    /// Completiva con "que" ("requiero que me diseñes...")
+    Complement,
+    /// Relativa ("un producto que sustituya...", "la casa donde vivo")
+    Relative,
+    /// Adverbial (causa, concesión, tiempo, condición, finalidad)
+    Adverbial,
+    /// Coordinada con la cláusula padre ("corro y salto")
+    Coordinate,
+}
+
+/// Cláusula con sus tokens y componentes (índices en la oración)
+#[derive(Debug, Clone)]
+pub struct Clause {
+    pub kind: ClauseKind,
+    /// Tokens en orden (incluye el nexo; puede ser discontinua si hay incrustadas)
+    pub tokens: Vec<usize>,
+    /// Nexo que la introduce ("que", "porque", "y")
+    pub connector: Option<usize>,
+    /// Cláusula de la que depende (None = principal)
+    pub parent: Option<usize>,
+    /// Antecedente de la relativa
+    pub antecedent: Option<usize>,
+    /// Verbo conjugado núcleo
+    pub verb: Option<usize>,
+    /// Sintagmas con su rol dentro de la cláusula
+    pub components: Vec<GrammaticalComponent>,
+}
+
+impl Clause {
+    fn new(kind: ClauseKind, parent: Option<usize>, connector: Option<usize>) -> Self {
+        Self {
+            kind,
+            tokens: connector.into_iter().collect(),
+            connector,
+            parent,
+            antecedent: None,
+            verb: None,
+            components: Vec::new(),
+        }
+    }
+
+    /// Sujeto de la cláusula
+    pub fn subject(&self) -> Option<&GrammaticalComponent> {
+        self.components.iter().find(|c| c.role == GrammaticalRole::Subject)
+    }
+
+    /// Objetos directos e indirectos
+    pub fn objects(&self) -> Vec<&GrammaticalComponent> {
+        self.components.iter()
+            .filter(|c| matches!(c.role, GrammaticalRole::DirectObject | GrammaticalRole::IndirectObject))
+            .collect()
+    }
+
+    /// Texto de la cláusula
+    pub fn text(&self, tokens: &[String]) -> String {
+        self.tokens.iter().map(|&i| tokens[i].as_str()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Árbol de cláusulas de una oración (la principal es la primera)
+#[derive(Debug, Clone, Default)]
+pub struct ClauseTree {
+    pub clauses: Vec<Clause>,
+}
+
+impl ClauseTree {
+    /// Cláusula principal
+    pub fn main(&self) -> Option<&Clause> {
+        self.clauses.first()
+    }
+
+    /// Índices de las cláusulas que dependen de otra
+    pub fn children(&self, index: usize) -> Vec<usize> {
+        (0..self.clauses.len())
+            .filter(|&i| self.clauses[i].parent == Some(index))
+            .collect()
+    }
+
+    /// Cláusula que contiene un token
+    pub fn clause_of(&self, token: usize) -> Option<usize> {
+        self.clauses.iter().position(|c| c.tokens.contains(&token))
+    }
+
+    /// Profundidad de una cláusula (principal = 0)
+    pub fn depth(&self, index: usize) -> usize {
+        let mut depth = 0;
+        let mut current = self.clauses[index].parent;
+        while let Some(p) = current {
+            depth += 1;
+            current = self.clauses[p].parent;
+        }
+        depth
+    }
+}
+
+/// Segmenta una oración a partir de qué tokens son verbos conjugados y cuáles
+/// sustantivos (para distinguir relativas). No asigna componentes
+pub fn segment(tokens: &[String], finite: &[bool], nominal: &[bool]) -> ClauseTree {
+    let n = tokens.len();
+    let words: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+    let mut clauses = vec![Clause::new(ClauseKind::Main, None, None)];
+    let mut stack = vec![0];
+
+    // ¿Hay verbo conjugado antes del siguiente nexo o fin de oración?
+    let verb_ahead = |from: usize| {
+        (from..n)
+            .take_while(|&j| !is_connector(&words[j]) && !is_sentence_break(&words[j]))
+            .any(|j| finite[j])
+    };
+
+    let mut i = 0;
+    while i < n {
+        let top = *stack.last().unwrap_or(&0);
+        let word = words[i].as_str();
+
+        if SUBORDINATORS.contains(&word) && verb_ahead(i + 1) {
+            let after_noun = i > 0 && nominal[i - 1];
+            let kind = match word {
+                "que" if i > 0 && words[i - 1] == "para" => ClauseKind::Adverbial,
+                "que" | "donde" | "cuando" | "como" if after_noun => ClauseKind::Relative,
+                "que" => ClauseKind::Complement,
+                _ => ClauseKind::Adverbial,
+            };
+
+            let mut clause = Clause::new(kind, Some(top), Some(i));
+            if clause.kind == ClauseKind::Relative {
+                clause.antecedent = Some(i - 1);
+            }
+            // "para que": la preposición forma parte del nexo
+            if word == "que" && clause.kind == ClauseKind::Adverbial && clauses[top].tokens.last() == Some(&(i - 1)) {
+                clauses[top].tokens.pop();
+                clause.tokens.insert(0, i - 1);
+            }
+
+            clauses.push(clause);
+            stack.push(clauses.len() - 1);
+            i += 1;
+            continue;
+        }
+
+        if COORDINATORS.contains(&word) && clauses[top].verb.is_some() && verb_ahead_after_coordinator(&words, finite, i + 1) {
+            // El segundo coordinado depende del primero y lo sustituye en la pila
+            let clause = Clause::new(ClauseKind::Coordinate, Some(top), Some(i));
+            clauses.push(clause);
+            let new = clauses.len() - 1;
+            if let Some(last) = stack.last_mut() {
+                *last = new;
+            }
+
+            // "y que ...": el "que" pertenece al mismo nexo
+            if words.get(i + 1).is_some_and(|w| SUBORDINATORS.contains(&w.as_str())) {
+                clauses[new].tokens.push(i + 1);
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        let mut top = top;
+        if finite[i] && clauses[top].verb.is_some() {
+            // Cierra las incrustadas ya completas: "el producto que compré | es caro"
+            while stack.len() > 1 && clauses[top].verb.is_some() {
+                stack.pop();
+                top = *stack.last().unwrap_or(&0);
+            }
+            // Yuxtaposición: otro verbo sin nexo en una cláusula ya completa
+            if clauses[top].verb.is_some() {
+                clauses.push(Clause::new(ClauseKind::Coordinate, Some(top), None));
+                top = clauses.len() - 1;
+                if let Some(last) = stack.last_mut() {
+                    *last = top;
+                }
+            }
+        }
+
+        if finite[i] && clauses[top].verb.is_none() {
+            clauses[top].verb = Some(i);
+        }
+        clauses[top].tokens.push(i);
+        i += 1;
+    }
+
+    ClauseTree { clauses }
+}
+
+/// Tras un coordinante se permite un subordinante antes del verbo ("y que lo pruebes")
+fn verb_ahead_after_coordinator(words: &[String], finite: &[bool], from: usize) -> bool {
+    let start = match words.get(from) {
+        Some(w) if SUBORDINATORS.contains(&w.as_str()) => from + 1,
+        _ => from,
+    };
+    (start..words.len())
+        .take_while(|&j| !is_connector(&words[j]) && !is_sentence_break(&words[j]))
+        .any(|j| finite[j])
+}
+
+/// Segmenta una oración clasificada y asigna roles dentro de cada cláusula
+pub(super) fn analyze_clauses(tokens: &[String], types: &[TokenType]) -> ClauseTree {
+    let finite: Vec<bool> = (0..types.len()).map(|i| is_finite(tokens, types, i)).collect();
+    let nominal: Vec<bool> = types.iter().map(|t| matches!(t, TokenType::Noun(_) | TokenType::Adjective)).collect();
+
+    let mut tree = segment(tokens, &finite, &nominal);
+    for clause in &mut tree.clauses {
+        let sub_tokens: Vec<String> = clause.tokens.iter().map(|&i| tokens[i].clone()).collect();
+        let sub_types: Vec<TokenType> = clause.tokens.iter().map(|&i| types[i].clone()).collect();
+
+        let chunks = chunker::chunk(&sub_tokens, &sub_types);
+        let roles = chunker::assign_roles(&sub_types, &chunks);
+        clause.components = chunks.iter().zip(roles)
+            .filter_map(|(chunk, role)| Some(GrammaticalComponent {
+                role: role?,
+                tokens: chunk.tokens.iter().map(|&t| clause.tokens[t]).collect(),
+                head: Some(clause.tokens[chunk.head]),
+            }))
+            .collect();
+    }
+
+    tree
+}
+
+/// Verbo conjugado (infinitivos y gerundios con enclíticos no cuentan)
+fn is_finite(tokens: &[String], types: &[TokenType], i: usize) -> bool {
+    match &types[i] {
+        TokenType::Verb(info) => info.conjugations.contains_key(&tokens[i].to_lowercase()),
+        _ => false,
+    }
+}
+
+fn is_connector(word: &str) -> bool {
+    SUBORDINATORS.contains(&word) || COORDINATORS.contains(&word)
+}
+
+fn is_sentence_break(word: &str) -> bool {
+    matches!(word, "." | ";" | ":" | "?" | "!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{NounCategory, SpanishGrammar};
+
+    fn tokens(sentence: &str) -> Vec<String> {
+        sentence.split_whitespace().map(String::from).collect()
+    }
+
+    fn grammar() -> SpanishGrammar {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("coliseo", NounCategory::Place);
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar
+    }
+
+    #[test]
+    fn test_complement_and_relative() {
+        let tokens = tokens("requiero que me diseñes un producto que sustituya al propofol");
+        let finite: Vec<bool> = (0..tokens.len()).map(|i| [0, 3, 7].contains(&i)).collect();
+        let nominal: Vec<bool> = (0..tokens.len()).map(|i| [5, 9].contains(&i)).collect();
+
+        let tree = segment(&tokens, &finite, &nominal);
+        assert_eq!(tree.clauses.len(), 3);
+        assert_eq!(tree.clauses[0].kind, ClauseKind::Main);
+        assert_eq!(tree.clauses[0].text(&tokens), "requiero");
+
+        assert_eq!(tree.clauses[1].kind, ClauseKind::Complement);
+        assert_eq!(tree.clauses[1].parent, Some(0));
+        assert_eq!(tree.clauses[1].verb, Some(3));
+        assert_eq!(tree.clauses[1].text(&tokens), "que me diseñes un producto");
+
+        assert_eq!(tree.clauses[2].kind, ClauseKind::Relative);
+        assert_eq!(tree.clauses[2].parent, Some(1));
+        assert_eq!(tree.clauses[2].antecedent, Some(5));
+        assert_eq!(tree.depth(2), 2);
+        assert_eq!(tree.children(1), vec![2]);
+    }
+
+    #[test]
+    fn test_embedded_relative() {
+        let grammar = grammar();
+        let tokens = tokens("el coliseo que visité es bonito");
+        let tree = grammar.clauses(&tokens);
+
+        assert_eq!(tree.clauses.len(), 2);
+        let main = tree.main().unwrap();
+        assert_eq!(main.tokens, vec![0, 1, 4, 5]);
+        assert_eq!(main.verb, Some(4));
+        assert_eq!(main.subject().and_then(|s| s.head), Some(1));
+
+        assert_eq!(tree.clauses[1].kind, ClauseKind::Relative);
+        assert_eq!(tree.clauses[1].antecedent, Some(1));
+        assert_eq!(tree.clause_of(3), Some(1));
+    }
+
+    #[test]
+    fn test_adverbial_and_coordinate() {
+        let grammar = grammar();
+
+        let tree = grammar.clauses(&tokens("si corres visito la casa"));
+        assert_eq!(tree.clauses.len(), 2);
+        assert_eq!(tree.clauses[1].kind, ClauseKind::Adverbial);
+        assert_eq!(tree.main().unwrap().verb, Some(2));
+        assert_eq!(tree.main().unwrap().objects().len(), 1);
+
+        let tree = grammar.clauses(&tokens("visito la casa y corro"));
+        assert_eq!(tree.clauses.len(), 2);
+        assert_eq!(tree.clauses[1].kind, ClauseKind::Coordinate);
+        assert_eq!(tree.clauses[1].text(&tokens("visito la casa y corro")), "y corro");
+
+        // Coordinación de sujetos, no de cláusulas; "que" sin verbo tampoco abre cláusula
+        assert_eq!(grammar.clauses(&tokens("ella y él corren")).clauses.len(), 1);
+        assert_eq!(grammar.clauses(&tokens("la casa es más bonita que el coliseo")).clauses.len(), 1);
+    }
+
+    #[test]
+    fn test_agreement_per_clause() {
+        let grammar = grammar();
+        let analysis = grammar.analyze(&tokens("yo visito la casa que ella visita"));
+
+        assert_eq!(analysis.clauses.clauses.len(), 2);
+        assert!(analysis.issues.is_empty(), "{:?}", analysis.issues);
+
+        let analysis = grammar.analyze(&tokens("yo visito la casa que ella visito"));
+        assert!(analysis.issues.iter().any(|i| i.position == 6));
+    }
+}
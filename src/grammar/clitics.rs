@@ -0,0 +1,365 @@
+//! # Clitics Module
+//!
+//! Pronombres átonos y construcciones con "se":
+//! - Proclíticos en grupo: "se lo dijo", "me la dio"
+//! - Enclíticos unidos al verbo: "dámelo", "visitarlo", "dándoselo"
+//! - "la"/"los"/"las" ante verbo son pronombres, no artículos ("la veo")
+//! - Lecturas de "se": reflexiva ("Juan se lava"), dativo ("se lo dijo"),
+//!   pasiva refleja ("se venden casas") e impersonal ("se vive bien")
+//! - Leísmo ("le visité"), tolerado o no según la región
+
+use super::checker::RULE_LEISMO;
+use super::inflection::remove_accents;
+use super::{
+    GrammarIssue, IssueSeverity, Number, Person, PronounCase, PronounInfo, SpanishGrammar,
+    TokenType, VerbCategory, VerbInfo,
+};
+
+/// Pronombres átonos, en orden de búsqueda al separar enclíticos
+/// (las formas largas antes que sus sufijos: "nos" antes que "os")
+const CLITICS: [&str; 11] = ["nos", "les", "los", "las", "os", "me", "te", "se", "le", "lo", "la"];
+
+/// Variedad regional: determina qué usos de "le" como objeto directo se aceptan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Region {
+    /// Norma general: se admite el leísmo de persona en singular ("le vi", a él)
+    #[default]
+    General,
+    /// España: también se admite en plural ("les vi", a ellos)
+    Peninsular,
+    /// América: se recomienda siempre "lo"/"los"
+    American,
+}
+
+impl Region {
+    /// ¿Se acepta "le"/"les" como objeto directo?
+    pub fn tolerates_leismo(&self, number: &Number) -> bool {
+        match self {
+            Region::General => *number == Number::Singular,
+            Region::Peninsular => true,
+            Region::American => false,
+        }
+    }
+}
+
+/// Pronombre átono de objeto por su forma
+pub fn object_clitic(word: &str) -> Option<PronounInfo> {
+    let (person, number, case) = match word {
+        "lo" | "la" => (Person::Third, Number::Singular, PronounCase::DirectObj),
+        "los" | "las" => (Person::Third, Number::Plural, PronounCase::DirectObj),
+        "le" => (Person::Third, Number::Singular, PronounCase::IndirectObj),
+        "les" => (Person::Third, Number::Plural, PronounCase::IndirectObj),
+        "me" => (Person::First, Number::Singular, PronounCase::DirectObj),
+        "te" => (Person::Second, Number::Singular, PronounCase::DirectObj),
+        "nos" => (Person::First, Number::Plural, PronounCase::DirectObj),
+        "os" => (Person::Second, Number::Plural, PronounCase::DirectObj),
+        "se" => (Person::Third, Number::Singular, PronounCase::Reflexive),
+        _ => return None,
+    };
+    Some(PronounInfo { person, number, case })
+}
+
+/// Separa los enclíticos de una forma verbal: "dámelo" → ("da", ["me", "lo"]).
+/// No valida que la base sea un verbo (ver `SpanishGrammar::classify_token`)
+pub fn split_enclitics(word: &str) -> Option<(String, Vec<String>)> {
+    let lower = word.to_lowercase();
+    let mut base = lower.as_str();
+    let mut clitics = Vec::new();
+
+    // Como máximo tres: "dándoselo" → dando + se + lo
+    while clitics.len() < 3 {
+        let found = CLITICS.iter().find(|c| {
+            base.ends_with(*c) && base.chars().count() > c.chars().count() + 1
+        });
+        match found {
+            Some(c) => {
+                clitics.insert(0, c.to_string());
+                base = &base[..base.len() - c.len()];
+            }
+            None => break,
+        }
+    }
+
+    if clitics.is_empty() {
+        return None;
+    }
+
+    // La tilde la exige el enclítico, no la base: "dá" → "da", "cóme" → "come"
+    Some((remove_accents(base), clitics))
+}
+
+/// Verbo que aloja enclíticos: conjugación, infinitivo o gerundio de un
+/// verbo conocido por la gramática ("compañeros" no es "compañer" + "os").
+/// La forma completa se registra como conjugación para conservar sus rasgos
+pub(super) fn enclitic_verb(grammar: &SpanishGrammar, word: &str) -> Option<VerbInfo> {
+    let (base, _) = split_enclitics(word)?;
+    let lower = word.to_lowercase();
+
+    // Imperativo o forma conjugada ("da", "come", "dijo")
+    if let Some(info) = grammar.verbs.values().find(|v| v.conjugations.contains_key(&base)) {
+        let mut info = info.clone();
+        let conjugation = info.conjugations[&base].clone();
+        info.conjugations.insert(lower, conjugation);
+        return Some(info);
+    }
+
+    // Infinitivo ("visitarlo") o gerundio ("visitándolo")
+    let candidates: Vec<String> = if ["ar", "er", "ir"].iter().any(|e| base.ends_with(e)) {
+        vec![base.clone()]
+    } else if let Some(stem) = base.strip_suffix("ando") {
+        vec![format!("{}ar", stem)]
+    } else if let Some(stem) = base.strip_suffix("iendo").or_else(|| base.strip_suffix("yendo")) {
+        vec![format!("{}er", stem), format!("{}ir", stem)]
+    } else {
+        return None;
+    };
+
+    candidates.iter().find_map(|c| grammar.verbs.get(c)).cloned()
+}
+
+/// Corrige por contexto la clase de los pronombres átonos:
+/// "la"/"los"/"las" ante verbo y las lecturas de "se"
+pub(super) fn resolve_clitics(grammar: &SpanishGrammar, tokens: &[String], types: &mut [TokenType]) {
+    // Artículo seguido de clítico o verbo → pronombre ("la veo", "los compré")
+    for i in (0..types.len()).rev() {
+        let lower = tokens[i].to_lowercase();
+        if !matches!(lower.as_str(), "la" | "los" | "las") || !matches!(types[i], TokenType::Article(_)) {
+            continue;
+        }
+        let next_is_verb = types.get(i + 1).is_some_and(|t| match t {
+            // "la visita": si el verbo es también sustantivo, se mantiene el artículo
+            TokenType::Verb(_) => !grammar.nouns.contains_key(&tokens[i + 1].to_lowercase()),
+            TokenType::Pronoun(info) => info.case != PronounCase::Subject,
+            _ => false,
+        });
+        if next_is_verb {
+            if let Some(info) = object_clitic(&lower) {
+                types[i] = TokenType::Pronoun(info);
+            }
+        }
+    }
+
+    for i in 0..types.len() {
+        if tokens[i].to_lowercase() == "se" && matches!(types[i], TokenType::Pronoun(_)) {
+            let case = classify_se(tokens, types, i);
+            types[i] = TokenType::Pronoun(PronounInfo { person: Person::Third, number: Number::Singular, case });
+        }
+    }
+}
+
+/// Lectura de "se" según el verbo y los sintagmas que lo rodean
+fn classify_se(tokens: &[String], types: &[TokenType], at: usize) -> PronounCase {
+    // "se lo dijo": variante de "le" ante otro clítico
+    let next = tokens.get(at + 1).map(|t| t.to_lowercase());
+    if matches!(next.as_deref(), Some("lo" | "la" | "los" | "las")) {
+        return PronounCase::IndirectObj;
+    }
+
+    // Verbo que sigue a los clíticos y la negación
+    let verb_pos = match (at + 1..types.len()).find(|&j| !is_clitic_or_negation(tokens, types, j)) {
+        Some(j) if matches!(types[j], TokenType::Verb(_)) => j,
+        _ => return PronounCase::Reflexive,
+    };
+    let conjugation = match &types[verb_pos] {
+        TokenType::Verb(info) => info.conjugations.get(&tokens[verb_pos].to_lowercase()),
+        _ => None,
+    };
+    let verb_number = match conjugation {
+        Some(c) if c.person == Person::Third => c.number.clone(),
+        _ => return PronounCase::Reflexive,
+    };
+
+    // Sujeto explícito antes de "se": reflexivo ("Juan se lava")
+    let has_subject_before = (0..at).any(|j| match &types[j] {
+        TokenType::Noun(_) => j == 0 || !matches!(types[j - 1], TokenType::Preposition),
+        TokenType::Pronoun(info) => info.case == PronounCase::Subject,
+        TokenType::Unknown => j > 0
+            && (tokens[j].starts_with(char::is_uppercase) || matches!(types[j - 1], TokenType::Article(_))),
+        _ => false,
+    });
+    if has_subject_before {
+        return PronounCase::Reflexive;
+    }
+
+    // Tras el verbo: SN sin preposición → pasiva refleja ("se venden casas");
+    // "a" + persona o nada → impersonal ("se busca a los culpables", "se vive bien")
+    let mut j = verb_pos + 1;
+    while j < types.len() && matches!(types[j], TokenType::Adverb) {
+        j += 1;
+    }
+    let has_bare_object = j < types.len()
+        && matches!(types[j], TokenType::Article(_) | TokenType::Noun(_) | TokenType::Adjective);
+
+    if has_bare_object || verb_number == Number::Plural {
+        PronounCase::Passive
+    } else {
+        PronounCase::Impersonal
+    }
+}
+
+/// Detecta "le"/"les" como objeto directo de un verbo transitivo
+pub(super) fn check_leismo(tokens: &[String], types: &[TokenType], region: Region) -> Vec<GrammarIssue> {
+    let mut issues = Vec::new();
+
+    for (i, tt) in types.iter().enumerate() {
+        let info = match tt {
+            TokenType::Pronoun(info) if info.case == PronounCase::IndirectObj => info,
+            _ => continue,
+        };
+        let lower = tokens[i].to_lowercase();
+        if !matches!(lower.as_str(), "le" | "les") || region.tolerates_leismo(&info.number) {
+            continue;
+        }
+
+        // Verbo del grupo clítico, sin otro objeto directo átono
+        let mut j = i + 1;
+        let mut has_accusative = false;
+        while j < types.len() && is_clitic_or_negation(tokens, types, j) {
+            has_accusative |= matches!(&types[j], TokenType::Pronoun(p) if p.case == PronounCase::DirectObj);
+            j += 1;
+        }
+        let verb = match types.get(j) {
+            Some(TokenType::Verb(verb)) => verb,
+            _ => continue,
+        };
+        if has_accusative || !verb.transitive || verb.category == VerbCategory::Emotion {
+            continue;
+        }
+
+        // Con objeto directo explícito, "le" es dativo legítimo ("le visité la casa")
+        let has_object_after = types.get(j + 1).is_some_and(|t| {
+            matches!(t, TokenType::Article(_) | TokenType::Noun(_))
+        });
+        if has_object_after {
+            continue;
+        }
+
+        let replacement = if info.number == Number::Plural { "los" } else { "lo" };
+        issues.push(GrammarIssue {
+            position: i,
+            severity: IssueSeverity::Warning,
+            message: format!("Leísmo: '{}' como objeto directo de '{}'; se recomienda '{}'",
+                lower, tokens[j].to_lowercase(), replacement),
+            rule: RULE_LEISMO,
+            span: None,
+            suggestion: Some(replacement.to_string()),
+        });
+    }
+
+    issues
+}
+
+fn is_clitic_or_negation(tokens: &[String], types: &[TokenType], j: usize) -> bool {
+    match &types[j] {
+        TokenType::Pronoun(info) => info.case != PronounCase::Subject,
+        TokenType::Adverb => tokens[j].to_lowercase() == "no",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::NounCategory;
+    use crate::tao::{GrammaticalRole, SentenceType};
+
+    fn words(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn case_of(grammar: &SpanishGrammar, sentence: &str, at: usize) -> PronounCase {
+        let tokens = words(sentence);
+        match &grammar.classify_sentence(&tokens)[at] {
+            TokenType::Pronoun(info) => info.case.clone(),
+            other => panic!("'{}' no es pronombre: {:?}", tokens[at], other),
+        }
+    }
+
+    #[test]
+    fn test_split_enclitics() {
+        assert_eq!(split_enclitics("dámelo"), Some(("da".to_string(), vec!["me".to_string(), "lo".to_string()])));
+        assert_eq!(split_enclitics("dándoselo").unwrap().0, "dando");
+        assert_eq!(split_enclitics("comerlo").unwrap().0, "comer");
+        assert_eq!(split_enclitics("dánoslo").unwrap().1, vec!["nos", "lo"]);
+        assert_eq!(split_enclitics("casa"), None);
+
+        let grammar = SpanishGrammar::new();
+        assert!(matches!(grammar.classify_token("dámelo"), TokenType::Verb(v) if v.infinitive == "dar"));
+        assert!(matches!(grammar.classify_token("visitarlo"), TokenType::Verb(v) if v.infinitive == "visitar"));
+        assert!(matches!(grammar.classify_token("Carlos"), TokenType::Unknown));
+        assert!(matches!(grammar.classify_token("decírselo"), TokenType::Verb(v) if v.infinitive == "decir"));
+    }
+
+    #[test]
+    fn test_nouns_are_not_enclitic_verbs() {
+        let grammar = SpanishGrammar::new();
+
+        // Sustantivos en -eros/-aros/-iros y en -arte: la base no es un verbo conocido
+        for word in ["compañeros", "primeros", "pájaros", "suspiros", "dineros", "baluarte", "comerlo"] {
+            assert!(!matches!(grammar.classify_token(word), TokenType::Verb(_)), "{}", word);
+        }
+
+        let tagged = grammar.tag(&words("los compañeros corren"));
+        assert_eq!(tagged[0].pos, crate::grammar::conllu::Upos::Det);
+        assert_ne!(tagged[1].pos, crate::grammar::conllu::Upos::Verb);
+    }
+
+    #[test]
+    fn test_se_readings() {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+
+        assert_eq!(case_of(&grammar, "se venden casas", 0), PronounCase::Passive);
+        assert_eq!(case_of(&grammar, "se vende la casa", 0), PronounCase::Passive);
+        assert_eq!(case_of(&grammar, "se corre mucho", 0), PronounCase::Impersonal);
+        assert_eq!(case_of(&grammar, "se lo dijo", 0), PronounCase::IndirectObj);
+        assert_eq!(case_of(&grammar, "ella se va", 1), PronounCase::Reflexive);
+    }
+
+    #[test]
+    fn test_roles_and_sentence_type() {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+
+        // Pasiva refleja: el SN pospuesto es el sujeto y concuerda con el verbo
+        let passive = grammar.analyze(&words("Se venden casas"));
+        let subject = passive.structure.components.iter().find(|c| c.role == GrammaticalRole::Subject);
+        assert_eq!(subject.map(|c| c.tokens.clone()), Some(vec![2]));
+        assert!(passive.issues.is_empty());
+        assert!(!grammar.analyze(&words("Se vende casas")).issues.is_empty());
+
+        let impersonal = grammar.analyze(&words("se corre mucho"));
+        assert_eq!(impersonal.structure.sentence_type, SentenceType::Impersonal);
+
+        // Grupo proclítico dentro del sintagma verbal
+        let cluster = grammar.analyze(&words("Me lo dijo"));
+        assert_eq!(cluster.structure.components.len(), 1);
+        assert_eq!(cluster.structure.components[0].tokens, vec![0, 1, 2]);
+
+        // "la" ante verbo es pronombre
+        let tagged = grammar.tag(&words("la visité"));
+        assert_eq!(tagged[0].pos, crate::grammar::conllu::Upos::Pron);
+    }
+
+    #[test]
+    fn test_leismo_by_region() {
+        let mut grammar = SpanishGrammar::new();
+        let issues = |g: &SpanishGrammar, s: &str| -> Vec<&'static str> {
+            g.analyze(&words(s)).issues.iter().map(|i| i.rule).collect()
+        };
+
+        // Norma general: "le" singular se tolera, "les" plural no
+        assert!(issues(&grammar, "yo le visité").is_empty());
+        assert_eq!(issues(&grammar, "yo les visité"), vec![RULE_LEISMO]);
+        // Dativo legítimo con verbo de afección
+        assert!(issues(&grammar, "le gusta").is_empty());
+
+        grammar.set_region(Region::Peninsular);
+        assert!(issues(&grammar, "yo les visité").is_empty());
+
+        grammar.set_region(Region::American);
+        let analysis = grammar.analyze(&words("yo le visité"));
+        assert_eq!(analysis.issues[0].rule, RULE_LEISMO);
+        assert_eq!(analysis.issues[0].suggestion.as_deref(), Some("lo"));
+    }
+}
@@ -0,0 +1,285 @@
+//! # CoNLL-U Module
+//!
+//! Lectura y escritura del formato CoNLL-U de Universal Dependencies,
+//! usado por los treebanks del español (AnCora, GSD).
+//!
+//! Cada palabra ocupa una línea con 10 columnas separadas por tabulador:
+//! `ID FORM LEMMA UPOS XPOS FEATS HEAD DEPREL DEPS MISC`.
+//! Las líneas de tokens multipalabra ("1-2 al") y nodos vacíos ("1.1")
+//! se omiten: solo se conservan las palabras sintácticas.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Categoría gramatical universal (UPOS)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Upos {
+    Adj,
+    Adp,
+    Adv,
+    Aux,
+    Cconj,
+    Det,
+    Intj,
+    Noun,
+    Num,
+    Part,
+    Pron,
+    Propn,
+    Punct,
+    Sconj,
+    Sym,
+    Verb,
+    X,
+}
+
+impl Upos {
+    /// Todas las categorías, en orden
+    pub const ALL: [Upos; 17] = [
+        Upos::Adj, Upos::Adp, Upos::Adv, Upos::Aux, Upos::Cconj, Upos::Det,
+        Upos::Intj, Upos::Noun, Upos::Num, Upos::Part, Upos::Pron, Upos::Propn,
+        Upos::Punct, Upos::Sconj, Upos::Sym, Upos::Verb, Upos::X,
+    ];
+
+    /// Etiqueta UD ("NOUN", "VERB"...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Upos::Adj => "ADJ",
+            Upos::Adp => "ADP",
+            Upos::Adv => "ADV",
+            Upos::Aux => "AUX",
+            Upos::Cconj => "CCONJ",
+            Upos::Det => "DET",
+            Upos::Intj => "INTJ",
+            Upos::Noun => "NOUN",
+            Upos::Num => "NUM",
+            Upos::Part => "PART",
+            Upos::Pron => "PRON",
+            Upos::Propn => "PROPN",
+            Upos::Punct => "PUNCT",
+            Upos::Sconj => "SCONJ",
+            Upos::Sym => "SYM",
+            Upos::Verb => "VERB",
+            Upos::X => "X",
+        }
+    }
+
+    /// Parsea una etiqueta UD (desconocidas → X)
+    pub fn from_ud_str(s: &str) -> Self {
+        Upos::ALL.iter()
+            .find(|u| u.as_str() == s)
+            .copied()
+            .unwrap_or(Upos::X)
+    }
+}
+
+impl fmt::Display for Upos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Palabra de una oración CoNLL-U
+#[derive(Debug, Clone)]
+pub struct ConlluToken {
+    /// Índice desde 1
+    pub id: usize,
+    pub form: String,
+    pub lemma: String,
+    pub upos: Upos,
+    pub xpos: String,
+    /// Rasgos morfológicos ("Gender=Fem|Number=Sing" o "_")
+    pub feats: String,
+    /// Índice del núcleo (0 = raíz)
+    pub head: usize,
+    pub deprel: String,
+}
+
+/// Oración CoNLL-U
+#[derive(Debug, Clone, Default)]
+pub struct ConlluSentence {
+    /// Identificador (`# sent_id = ...`)
+    pub sent_id: Option<String>,
+    /// Texto original (`# text = ...`)
+    pub text: Option<String>,
+    pub tokens: Vec<ConlluToken>,
+}
+
+impl ConlluSentence {
+    /// Formas de las palabras, en orden
+    pub fn forms(&self) -> Vec<String> {
+        self.tokens.iter().map(|t| t.form.clone()).collect()
+    }
+}
+
+/// Error de lectura CoNLL-U
+#[derive(Debug)]
+pub enum ConlluError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for ConlluError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConlluError::IoError(e) => write!(f, "IO error: {}", e),
+            ConlluError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for ConlluError {}
+
+/// Lee un archivo CoNLL-U
+pub fn read_conllu_file(path: &Path) -> Result<Vec<ConlluSentence>, ConlluError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ConlluError::IoError(e.to_string()))?;
+    parse_conllu(&content)
+}
+
+/// Parsea texto CoNLL-U
+pub fn parse_conllu(content: &str) -> Result<Vec<ConlluSentence>, ConlluError> {
+    let mut sentences = Vec::new();
+    let mut current = ConlluSentence::default();
+
+    for (line_no, line) in content.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+
+        if line.trim().is_empty() {
+            if !current.tokens.is_empty() {
+                sentences.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim();
+            if let Some(id) = comment.strip_prefix("sent_id =") {
+                current.sent_id = Some(id.trim().to_string());
+            } else if let Some(text) = comment.strip_prefix("text =") {
+                current.text = Some(text.trim().to_string());
+            }
+            continue;
+        }
+
+        let cols: Vec<&str> = line.split('\t').collect();
+        if cols.len() != 10 {
+            return Err(ConlluError::ParseError(
+                line_no + 1,
+                format!("se esperaban 10 columnas, hay {}", cols.len()),
+            ));
+        }
+
+        // Tokens multipalabra y nodos vacíos
+        if cols[0].contains('-') || cols[0].contains('.') {
+            continue;
+        }
+
+        let id = cols[0].parse::<usize>()
+            .map_err(|_| ConlluError::ParseError(line_no + 1, format!("ID inválido: {}", cols[0])))?;
+        let head = if cols[6] == "_" {
+            0
+        } else {
+            cols[6].parse::<usize>()
+                .map_err(|_| ConlluError::ParseError(line_no + 1, format!("HEAD inválido: {}", cols[6])))?
+        };
+
+        current.tokens.push(ConlluToken {
+            id,
+            form: cols[1].to_string(),
+            lemma: cols[2].to_string(),
+            upos: Upos::from_ud_str(cols[3]),
+            xpos: cols[4].to_string(),
+            feats: cols[5].to_string(),
+            head,
+            deprel: cols[7].to_string(),
+        });
+    }
+
+    if !current.tokens.is_empty() {
+        sentences.push(current);
+    }
+
+    Ok(sentences)
+}
+
+/// Escribe oraciones en formato CoNLL-U
+pub fn write_conllu(sentences: &[ConlluSentence]) -> String {
+    let mut out = String::new();
+
+    for sentence in sentences {
+        if let Some(id) = &sentence.sent_id {
+            out.push_str(&format!("# sent_id = {}\n", id));
+        }
+        if let Some(text) = &sentence.text {
+            out.push_str(&format!("# text = {}\n", text));
+        }
+        for t in &sentence.tokens {
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t_\t_\n",
+                t.id,
+                t.form,
+                if t.lemma.is_empty() { "_" } else { &t.lemma },
+                t.upos,
+                if t.xpos.is_empty() { "_" } else { &t.xpos },
+                if t.feats.is_empty() { "_" } else { &t.feats },
+                t.head,
+                t.deprel,
+            ));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# sent_id = test-1
+# text = Voy al parque.
+1\tVoy\tir\tVERB\t_\tMood=Ind|Number=Sing|Person=1\t0\troot\t_\t_
+2-3\tal\t_\t_\t_\t_\t_\t_\t_\t_
+2\ta\ta\tADP\t_\t_\t4\tcase\t_\t_
+3\tel\tel\tDET\t_\tDefinite=Def\t4\tdet\t_\t_
+4\tparque\tparque\tNOUN\t_\tGender=Masc|Number=Sing\t1\tobl\t_\t_
+5\t.\t.\tPUNCT\t_\t_\t1\tpunct\t_\t_
+";
+
+    #[test]
+    fn test_parse_conllu() {
+        let sentences = parse_conllu(SAMPLE).unwrap();
+        assert_eq!(sentences.len(), 1);
+
+        let s = &sentences[0];
+        assert_eq!(s.sent_id.as_deref(), Some("test-1"));
+        assert_eq!(s.text.as_deref(), Some("Voy al parque."));
+        // El token multipalabra "al" se omite
+        assert_eq!(s.forms(), vec!["Voy", "a", "el", "parque", "."]);
+        assert_eq!(s.tokens[3].upos, Upos::Noun);
+        assert_eq!(s.tokens[3].head, 1);
+        assert_eq!(s.tokens[3].deprel, "obl");
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let sentences = parse_conllu(SAMPLE).unwrap();
+        let written = write_conllu(&sentences);
+        let reparsed = parse_conllu(&written).unwrap();
+
+        assert_eq!(reparsed[0].forms(), sentences[0].forms());
+        assert_eq!(reparsed[0].tokens[1].deprel, "case");
+    }
+
+    #[test]
+    fn test_parse_error_reports_line() {
+        let bad = "1\tcasa\tcasa\tNOUN\n";
+        match parse_conllu(bad) {
+            Err(ConlluError::ParseError(line, _)) => assert_eq!(line, 1),
+            other => panic!("se esperaba error de parseo: {:?}", other),
+        }
+    }
+}
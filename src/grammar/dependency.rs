@@ -0,0 +1,598 @@
+//! # Dependency Module
+//!
+//! Análisis de dependencias determinista basado en reglas, construido
+//! sobre las clases léxicas de `SpanishGrammar` y los sintagmas del chunker.
+//! Produce un árbol por oración con relaciones de Universal Dependencies
+//! (nsubj, obj, iobj, obl, amod, det, case...) exportable a CoNLL-U.
+//!
+//! ## Ejemplo
+//! "Me gusta la casa azul" →
+//! ```text
+//! 1  Me     PRON  2  iobj
+//! 2  gusta  VERB  0  root
+//! 3  la     DET   4  det
+//! 4  casa   NOUN  2  nsubj
+//! 5  azul   ADJ   4  amod
+//! ```
+
+use std::fmt;
+use crate::tao::GrammaticalRole;
+use super::checker::{sentence_ranges, tokenize};
+use super::chunker::{self, ChunkKind};
+use super::conllu::{write_conllu, ConlluSentence, ConlluToken, Upos};
+use super::{is_coordinating, token_upos, MorphFeatures, PronounCase, SpanishGrammar, TokenType, VerbCategory};
+
+/// Relación de dependencia (subconjunto de Universal Dependencies)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepRelation {
+    Root,
+    Nsubj,
+    Obj,
+    Iobj,
+    Obl,
+    Nmod,
+    Amod,
+    Det,
+    Case,
+    Advmod,
+    Cc,
+    Conj,
+    Mark,
+    Cop,
+    Expl,
+    Xcomp,
+    Punct,
+    Dep,
+}
+
+impl DepRelation {
+    /// Etiqueta UD ("nsubj", "obj"...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DepRelation::Root => "root",
+            DepRelation::Nsubj => "nsubj",
+            DepRelation::Obj => "obj",
+            DepRelation::Iobj => "iobj",
+            DepRelation::Obl => "obl",
+            DepRelation::Nmod => "nmod",
+            DepRelation::Amod => "amod",
+            DepRelation::Det => "det",
+            DepRelation::Case => "case",
+            DepRelation::Advmod => "advmod",
+            DepRelation::Cc => "cc",
+            DepRelation::Conj => "conj",
+            DepRelation::Mark => "mark",
+            DepRelation::Cop => "cop",
+            DepRelation::Expl => "expl",
+            DepRelation::Xcomp => "xcomp",
+            DepRelation::Punct => "punct",
+            DepRelation::Dep => "dep",
+        }
+    }
+}
+
+impl fmt::Display for DepRelation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Palabra del árbol de dependencias
+#[derive(Debug, Clone)]
+pub struct DependencyToken {
+    /// Índice desde 1 (como en CoNLL-U)
+    pub id: usize,
+    pub form: String,
+    pub lemma: String,
+    pub upos: Upos,
+    pub features: MorphFeatures,
+    /// Núcleo (0 = raíz)
+    pub head: usize,
+    pub deprel: DepRelation,
+}
+
+/// Árbol de dependencias de una oración
+#[derive(Debug, Clone, Default)]
+pub struct DependencyTree {
+    pub tokens: Vec<DependencyToken>,
+}
+
+impl DependencyTree {
+    /// Raíz del árbol
+    pub fn root(&self) -> Option<&DependencyToken> {
+        self.tokens.iter().find(|t| t.head == 0)
+    }
+
+    /// Dependientes directos de una palabra (por id)
+    pub fn dependents(&self, id: usize) -> Vec<&DependencyToken> {
+        self.tokens.iter().filter(|t| t.head == id).collect()
+    }
+
+    /// Primer dependiente con una relación dada
+    pub fn dependent_with(&self, id: usize, relation: DepRelation) -> Option<&DependencyToken> {
+        self.tokens.iter().find(|t| t.head == id && t.deprel == relation)
+    }
+
+    /// Convierte a oración CoNLL-U
+    pub fn to_conllu_sentence(&self) -> ConlluSentence {
+        ConlluSentence {
+            sent_id: None,
+            text: Some(self.tokens.iter().map(|t| t.form.as_str()).collect::<Vec<_>>().join(" ")),
+            tokens: self.tokens.iter().map(|t| ConlluToken {
+                id: t.id,
+                form: t.form.clone(),
+                lemma: t.lemma.clone(),
+                upos: t.upos,
+                xpos: String::new(),
+                feats: t.features.to_ud_string(),
+                head: t.head,
+                deprel: t.deprel.as_str().to_string(),
+            }).collect(),
+        }
+    }
+
+    /// Exporta en formato CoNLL-U
+    pub fn to_conllu(&self) -> String {
+        write_conllu(&[self.to_conllu_sentence()])
+    }
+}
+
+/// Métricas de evaluación contra un treebank
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentScores {
+    /// Palabras evaluadas
+    pub tokens: usize,
+    /// Unlabeled Attachment Score: núcleo correcto
+    pub uas: f64,
+    /// Labeled Attachment Score: núcleo y relación correctos
+    pub las: f64,
+}
+
+/// Parser de dependencias basado en reglas
+#[derive(Debug, Clone)]
+pub struct DependencyParser {
+    grammar: SpanishGrammar,
+}
+
+impl DependencyParser {
+    /// Crea parser con la gramática base
+    pub fn new() -> Self {
+        Self::with_grammar(SpanishGrammar::new())
+    }
+
+    /// Crea parser con una gramática ya cargada
+    pub fn with_grammar(grammar: SpanishGrammar) -> Self {
+        Self { grammar }
+    }
+
+    /// Gramática usada por el parser
+    pub fn grammar(&self) -> &SpanishGrammar {
+        &self.grammar
+    }
+
+    /// Analiza un texto: un árbol por oración
+    pub fn parse_text(&self, text: &str) -> Vec<DependencyTree> {
+        let tokens = tokenize(text);
+        sentence_ranges(&tokens)
+            .into_iter()
+            .map(|(start, end)| {
+                let words: Vec<String> = tokens[start..end].iter().map(|t| t.text.clone()).collect();
+                self.parse(&words)
+            })
+            .collect()
+    }
+
+    /// Analiza una oración ya tokenizada (la puntuación va como token aparte)
+    pub fn parse(&self, tokens: &[String]) -> DependencyTree {
+        let n = tokens.len();
+        if n == 0 {
+            return DependencyTree::default();
+        }
+
+        let types = self.grammar.classify_sentence(tokens);
+        let chunks = chunker::chunk(tokens, &types);
+        let roles = chunker::assign_roles(&types, &chunks);
+
+        // heads[i] = Some(j) → j es núcleo de i; None → raíz
+        let mut heads: Vec<Option<usize>> = vec![None; n];
+        let mut rels = vec![DepRelation::Dep; n];
+        let mut upos: Vec<Upos> = (0..n).map(|i| token_upos(tokens, &types, i)).collect();
+
+        // 1. Estructura interna de cada sintagma
+        for chunk in &chunks {
+            match chunk.kind {
+                ChunkKind::VerbPhrase => attach_verb_phrase(&chunk.tokens, chunk.head, tokens, &types, &mut heads, &mut rels),
+                ChunkKind::NounPhrase | ChunkKind::PrepositionalPhrase => {
+                    attach_nominal(&chunk.tokens, &types, &mut heads, &mut rels, &mut upos)
+                }
+                ChunkKind::Single => {}
+            }
+        }
+
+        // 2. Raíz: verbo principal, o predicado si es copulativo
+        let first_vp = chunks.iter().position(|c| c.kind == ChunkKind::VerbPhrase);
+        let mut predicate_chunk = None;
+        let root = match first_vp {
+            Some(v) => {
+                let verb = chunks[v].head;
+                let copulative = matches!(&types[verb],
+                    TokenType::Verb(info) if info.category == VerbCategory::State);
+                let predicate = chunks.iter().enumerate().skip(v + 1).find(|(_, c)| {
+                    c.kind == ChunkKind::NounPhrase
+                        || (c.kind == ChunkKind::Single && matches!(types[c.head], TokenType::Adjective))
+                });
+
+                match (copulative, predicate) {
+                    (true, Some((p, pred))) => {
+                        heads[verb] = Some(pred.head);
+                        rels[verb] = DepRelation::Cop;
+                        upos[verb] = Upos::Aux;
+                        predicate_chunk = Some(p);
+                        pred.head
+                    }
+                    _ => verb,
+                }
+            }
+            None => chunks.iter()
+                .find(|c| c.kind != ChunkKind::Single)
+                .or(chunks.first())
+                .map(|c| c.head)
+                .unwrap_or_else(|| (0..n).find(|&i| upos[i] != Upos::Punct).unwrap_or(0)),
+        };
+        heads[root] = None;
+        rels[root] = DepRelation::Root;
+
+        // 3. Relaciones entre sintagmas
+        for (ci, chunk) in chunks.iter().enumerate() {
+            let h = chunk.head;
+            if h == root || Some(ci) == predicate_chunk || rels[h] == DepRelation::Cop {
+                continue;
+            }
+
+            let relation = match (&chunk.kind, &roles[ci]) {
+                (ChunkKind::VerbPhrase, _) => {
+                    let coordinated = ci > 0 && is_coordinating(&tokens[chunks[ci - 1].head]);
+                    if coordinated { DepRelation::Conj } else { DepRelation::Dep }
+                }
+                (ChunkKind::NounPhrase, Some(GrammaticalRole::Subject)) => DepRelation::Nsubj,
+                (ChunkKind::NounPhrase, _) if predicate_chunk.is_some() => DepRelation::Nsubj,
+                (ChunkKind::NounPhrase, _) => DepRelation::Obj,
+                (ChunkKind::PrepositionalPhrase, _) if first_vp.is_some() => DepRelation::Obl,
+                (ChunkKind::PrepositionalPhrase, _) => DepRelation::Nmod,
+                (ChunkKind::Single, _) => {
+                    let (head, relation) = attach_single(ci, &chunks, tokens, &types, root);
+                    heads[h] = Some(head);
+                    rels[h] = relation;
+                    continue;
+                }
+            };
+
+            heads[h] = Some(root);
+            rels[h] = relation;
+        }
+
+        // 4. Tokens sueltos (puntuación y palabras desconocidas)
+        for i in 0..n {
+            if i != root && heads[i].is_none() {
+                heads[i] = Some(root);
+                rels[i] = if upos[i] == Upos::Punct { DepRelation::Punct } else { DepRelation::Dep };
+            }
+        }
+
+        DependencyTree {
+            tokens: (0..n).map(|i| DependencyToken {
+                id: i + 1,
+                form: tokens[i].clone(),
+                lemma: self.grammar.lemma(&tokens[i], &types[i], upos[i]),
+                features: self.grammar.features(&tokens[i], &types[i]),
+                upos: upos[i],
+                head: heads[i].map(|h| h + 1).unwrap_or(0),
+                deprel: rels[i],
+            }).collect(),
+        }
+    }
+
+    /// Evalúa contra oraciones anotadas (UAS/LAS sobre la tokenización gold)
+    pub fn evaluate(&self, gold: &[ConlluSentence]) -> AttachmentScores {
+        let mut total = 0;
+        let mut head_ok = 0;
+        let mut label_ok = 0;
+
+        for sentence in gold {
+            let tree = self.parse(&sentence.forms());
+            for (predicted, expected) in tree.tokens.iter().zip(&sentence.tokens) {
+                total += 1;
+                if predicted.head == expected.head {
+                    head_ok += 1;
+                    // Se compara la relación universal ("obl:arg" → "obl")
+                    let universal = expected.deprel.split(':').next().unwrap_or("");
+                    if predicted.deprel.as_str() == universal {
+                        label_ok += 1;
+                    }
+                }
+            }
+        }
+
+        if total == 0 {
+            return AttachmentScores::default();
+        }
+
+        AttachmentScores {
+            tokens: total,
+            uas: head_ok as f64 / total as f64,
+            las: label_ok as f64 / total as f64,
+        }
+    }
+}
+
+impl Default for DependencyParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clíticos y negación dependen del verbo; verbos encadenados son xcomp
+fn attach_verb_phrase(
+    span: &[usize],
+    head: usize,
+    tokens: &[String],
+    types: &[TokenType],
+    heads: &mut [Option<usize>],
+    rels: &mut [DepRelation],
+) {
+    // Verbos de afección ("me gusta") llevan dativo
+    let dative_verb = matches!(&types[head], TokenType::Verb(info) if info.category == VerbCategory::Emotion);
+
+    for &t in span {
+        if t == head {
+            continue;
+        }
+        heads[t] = Some(head);
+        rels[t] = match &types[t] {
+            TokenType::Pronoun(info) => match info.case {
+                PronounCase::IndirectObj => DepRelation::Iobj,
+                PronounCase::DirectObj if dative_verb => DepRelation::Iobj,
+                PronounCase::DirectObj => DepRelation::Obj,
+                PronounCase::Reflexive | PronounCase::Passive | PronounCase::Impersonal => DepRelation::Expl,
+                PronounCase::Subject => DepRelation::Nsubj,
+            },
+            TokenType::Adverb if tokens[t].to_lowercase() == "no" => DepRelation::Advmod,
+            TokenType::Verb(_) => DepRelation::Xcomp,
+            _ => DepRelation::Dep,
+        };
+    }
+}
+
+/// Estructura interna de SN/SP: segmentos separados por "de" o coordinación
+fn attach_nominal(
+    span: &[usize],
+    types: &[TokenType],
+    heads: &mut [Option<usize>],
+    rels: &mut [DepRelation],
+    upos: &mut [Upos],
+) {
+    // Segmentos: (marcador previo, tokens)
+    let mut segments: Vec<(Option<usize>, Vec<usize>)> = vec![(None, Vec::new())];
+    for &t in span {
+        let is_marker = matches!(types[t], TokenType::Preposition | TokenType::Conjunction);
+        if is_marker {
+            segments.push((Some(t), Vec::new()));
+        } else if let Some(last) = segments.last_mut() {
+            last.1.push(t);
+        }
+    }
+
+    let mut first_head: Option<usize> = None;
+    let mut previous_head: Option<usize> = None;
+
+    for (marker, words) in &segments {
+        // El segmento inicial de un SP está vacío: la preposición marca el siguiente
+        let seg_head = match segment_head(words, types) {
+            Some(h) => h,
+            None => continue,
+        };
+        if matches!(types[seg_head], TokenType::Unknown) && upos[seg_head] == Upos::X {
+            upos[seg_head] = Upos::Noun;
+        }
+
+        for &w in words {
+            if w == seg_head {
+                continue;
+            }
+            heads[w] = Some(seg_head);
+            rels[w] = match types[w] {
+                TokenType::Article(_) => DepRelation::Det,
+                TokenType::Adjective => DepRelation::Amod,
+                _ => DepRelation::Dep,
+            };
+        }
+
+        match marker {
+            Some(m) if matches!(types[*m], TokenType::Conjunction) => {
+                heads[*m] = Some(seg_head);
+                rels[*m] = DepRelation::Cc;
+                if let Some(first) = first_head {
+                    heads[seg_head] = Some(first);
+                    rels[seg_head] = DepRelation::Conj;
+                }
+            }
+            Some(m) => {
+                heads[*m] = Some(seg_head);
+                rels[*m] = DepRelation::Case;
+                if let Some(prev) = previous_head {
+                    heads[seg_head] = Some(prev);
+                    rels[seg_head] = DepRelation::Nmod;
+                }
+            }
+            None => {}
+        }
+
+        if first_head.is_none() {
+            first_head = Some(seg_head);
+        }
+        previous_head = Some(seg_head);
+    }
+}
+
+/// Núcleo de un segmento nominal: sustantivo, desconocido, pronombre o último adjetivo
+fn segment_head(words: &[usize], types: &[TokenType]) -> Option<usize> {
+    words.iter()
+        .find(|&&w| matches!(types[w], TokenType::Noun(_) | TokenType::Unknown | TokenType::Pronoun(_)))
+        .or_else(|| words.iter().rev().find(|&&w| matches!(types[w], TokenType::Adjective)))
+        .or(words.last())
+        .copied()
+}
+
+/// Núcleo y relación de un token que no forma sintagma
+fn attach_single(
+    ci: usize,
+    chunks: &[chunker::Chunk],
+    tokens: &[String],
+    types: &[TokenType],
+    root: usize,
+) -> (usize, DepRelation) {
+    let t = chunks[ci].head;
+    let next_head = chunks.get(ci + 1).map(|c| c.head).filter(|&h| h != t);
+
+    match &types[t] {
+        TokenType::Adverb => (root, DepRelation::Advmod),
+        TokenType::Conjunction if is_coordinating(&tokens[t]) => (next_head.unwrap_or(root), DepRelation::Cc),
+        TokenType::Conjunction => (next_head.unwrap_or(root), DepRelation::Mark),
+        TokenType::Adjective => {
+            // Adjetivo suelto: modifica al sintagma nominal más cercano
+            let nominal = chunks.iter()
+                .enumerate()
+                .filter(|(_, c)| c.kind == ChunkKind::NounPhrase)
+                .min_by_key(|(i, _)| (*i as isize - ci as isize).abs())
+                .map(|(_, c)| c.head);
+            match nominal {
+                Some(h) => (h, DepRelation::Amod),
+                None => (root, DepRelation::Dep),
+            }
+        }
+        TokenType::Pronoun(info) if info.case == PronounCase::IndirectObj => (root, DepRelation::Iobj),
+        TokenType::Pronoun(_) => (root, DepRelation::Obj),
+        TokenType::Article(_) => (next_head.unwrap_or(root), DepRelation::Det),
+        TokenType::Preposition => (next_head.unwrap_or(root), DepRelation::Case),
+        _ => (root, DepRelation::Dep),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::conllu::{parse_conllu, read_conllu_file};
+    use crate::grammar::NounCategory;
+    use std::path::Path;
+
+    /// Muestra anotada según las convenciones de UD Spanish-AnCora
+    const GOLD: &str = "# text = Me gusta la casa azul .
+1\tMe\tyo\tPRON\t_\t_\t2\tiobj\t_\t_
+2\tgusta\tgustar\tVERB\t_\t_\t0\troot\t_\t_
+3\tla\tel\tDET\t_\t_\t4\tdet\t_\t_
+4\tcasa\tcasa\tNOUN\t_\t_\t2\tnsubj\t_\t_
+5\tazul\tazul\tADJ\t_\t_\t4\tamod\t_\t_
+6\t.\t.\tPUNCT\t_\t_\t2\tpunct\t_\t_
+
+# text = Yo visité el coliseo en Roma .
+1\tYo\tyo\tPRON\t_\t_\t2\tnsubj\t_\t_
+2\tvisité\tvisitar\tVERB\t_\t_\t0\troot\t_\t_
+3\tel\tel\tDET\t_\t_\t4\tdet\t_\t_
+4\tcoliseo\tcoliseo\tNOUN\t_\t_\t2\tobj\t_\t_
+5\ten\ten\tADP\t_\t_\t6\tcase\t_\t_
+6\tRoma\tRoma\tPROPN\t_\t_\t2\tobl\t_\t_
+7\t.\t.\tPUNCT\t_\t_\t2\tpunct\t_\t_
+
+# text = La casa de Rosita es grande .
+1\tLa\tel\tDET\t_\t_\t2\tdet\t_\t_
+2\tcasa\tcasa\tNOUN\t_\t_\t6\tnsubj\t_\t_
+3\tde\tde\tADP\t_\t_\t4\tcase\t_\t_
+4\tRosita\tRosita\tPROPN\t_\t_\t2\tnmod\t_\t_
+5\tes\tser\tAUX\t_\t_\t6\tcop\t_\t_
+6\tgrande\tgrande\tADJ\t_\t_\t0\troot\t_\t_
+7\t.\t.\tPUNCT\t_\t_\t6\tpunct\t_\t_
+
+# text = Nosotros no corremos .
+1\tNosotros\tyo\tPRON\t_\t_\t3\tnsubj\t_\t_
+2\tno\tno\tADV\t_\t_\t3\tadvmod\t_\t_
+3\tcorremos\tcorrer\tVERB\t_\t_\t0\troot\t_\t_
+4\t.\t.\tPUNCT\t_\t_\t3\tpunct\t_\t_
+
+# text = El coliseo y la casa son grandes .
+1\tEl\tel\tDET\t_\t_\t2\tdet\t_\t_
+2\tcoliseo\tcoliseo\tNOUN\t_\t_\t7\tnsubj\t_\t_
+3\ty\ty\tCCONJ\t_\t_\t5\tcc\t_\t_
+4\tla\tel\tDET\t_\t_\t5\tdet\t_\t_
+5\tcasa\tcasa\tNOUN\t_\t_\t2\tconj\t_\t_
+6\tson\tser\tAUX\t_\t_\t7\tcop\t_\t_
+7\tgrandes\tgrande\tADJ\t_\t_\t0\troot\t_\t_
+8\t.\t.\tPUNCT\t_\t_\t7\tpunct\t_\t_
+";
+
+    fn parser() -> DependencyParser {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar.add_noun_forms("coliseo", NounCategory::Place);
+        grammar.add_adjective_forms("azul");
+        grammar.add_adjective_forms("grande");
+        DependencyParser::with_grammar(grammar)
+    }
+
+    #[test]
+    fn test_parse_simple_sentence() {
+        let parser = parser();
+        let trees = parser.parse_text("Me gusta la casa azul.");
+        assert_eq!(trees.len(), 1);
+
+        let tree = &trees[0];
+        let root = tree.root().unwrap();
+        assert_eq!(root.form, "gusta");
+        assert_eq!(root.lemma, "gustar");
+
+        let subject = tree.dependent_with(root.id, DepRelation::Nsubj).unwrap();
+        assert_eq!(subject.form, "casa");
+        assert!(tree.dependent_with(subject.id, DepRelation::Det).is_some());
+        assert!(tree.dependent_with(subject.id, DepRelation::Amod).is_some());
+    }
+
+    #[test]
+    fn test_copula_and_conllu_export() {
+        let parser = parser();
+        let tree = &parser.parse_text("La casa de Rosita es grande.")[0];
+
+        assert_eq!(tree.root().unwrap().form, "grande");
+        let conllu = tree.to_conllu();
+        assert!(conllu.contains("es\tser\tAUX\t_\tMood=Ind|Number=Sing|Person=3|Tense=Pres\t6\tcop"));
+
+        // La exportación es CoNLL-U válido
+        let reparsed = parse_conllu(&conllu).unwrap();
+        assert_eq!(reparsed[0].tokens.len(), 7);
+    }
+
+    #[test]
+    fn test_evaluation_against_gold() {
+        let parser = parser();
+        let gold = parse_conllu(GOLD).unwrap();
+
+        let scores = parser.evaluate(&gold);
+        assert_eq!(scores.tokens, 32);
+        assert!(scores.uas >= 0.9, "UAS = {:.3}", scores.uas);
+        assert!(scores.las >= 0.9, "LAS = {:.3}", scores.las);
+    }
+
+    #[test]
+    #[ignore = "requiere el treebank UD Spanish-AnCora (NLSRE_UD_DIR)"]
+    fn test_evaluation_against_treebank_file() {
+        // Treebank opcional (no se distribuye con el repo):
+        // NLSRE_UD_DIR=ruta cargo test -- --ignored
+        let dir = std::env::var("NLSRE_UD_DIR").unwrap_or_else(|_| "data/ud".to_string());
+        let path = Path::new(&dir).join("es_ancora-ud-test.conllu");
+
+        let gold = read_conllu_file(&path).expect("treebank de prueba en NLSRE_UD_DIR");
+        let scores = DependencyParser::new().evaluate(&gold);
+        assert!(scores.tokens > 0);
+        assert!(scores.las <= scores.uas);
+        assert!(scores.uas >= 0.40, "UAS {:.3} por debajo del mínimo", scores.uas);
+        assert!(scores.las >= 0.30, "LAS {:.3} por debajo del mínimo", scores.las);
+    }
+}
@@ -0,0 +1,759 @@
+//! # Inflection Module
+//!
+//! Flexión nominal y adjetival del español.
+//! Genera y analiza formas de género y número:
+//! - Plurales: "casa" → "casas", "canción" → "canciones", "luz" → "luces"
+//! - Invariables: "crisis", "lunes", "tórax"
+//! - Pares de género: "niño" → "niña", "actor" → "actriz"
+//! - Excepciones: "el mapa", "la mano", "el agua"
+
+use std::collections::{HashMap, HashSet};
+use super::{Gender, Number, NounCategory, NounInfo};
+
+/// Raíces de femeninos en -a cuyo masculino termina en consonante
+/// (trabajador/a, español/a, mandón/ona, francés/esa, alemán/ana)
+const CONSONANT_MASCULINE_STEMS: &[&str] = &[
+    "dor", "tor", "sor", "ñol", "gol", "don", "ron", "lon",
+    "les", "ces", "gues", "des", "eman", "alan",
+];
+
+/// Motor de flexión nominal y adjetival
+#[derive(Debug, Clone)]
+pub struct Inflector {
+    /// Sustantivos masculinos con terminación femenina (el mapa, el día)
+    masculine_exceptions: HashSet<String>,
+
+    /// Sustantivos femeninos con terminación masculina (la mano, la flor)
+    feminine_exceptions: HashSet<String>,
+
+    /// Femeninos con "a" tónica inicial que llevan "el" en singular (el agua)
+    stressed_a_feminines: HashSet<String>,
+
+    /// Pares de género irregulares: masculino → femenino
+    gender_pairs: HashMap<String, String>,
+
+    /// Plurales irregulares: singular → plural
+    irregular_plurals: HashMap<String, String>,
+
+    /// Palabras invariables en número (la crisis / las crisis)
+    invariable_number: HashSet<String>,
+
+    /// Adjetivos invariables en género aunque terminen en -or (mejor, mayor)
+    invariable_gender: HashSet<String>,
+}
+
+/// Resultado del análisis de una forma nominal o adjetival
+#[derive(Debug, Clone, PartialEq)]
+pub struct InflectionAnalysis {
+    /// Forma base (singular; masculino singular para adjetivos)
+    pub lemma: String,
+    /// Género de la forma (Neutral = común en cuanto al género: "azul", "grande")
+    pub gender: Gender,
+    /// Número de la forma
+    pub number: Number,
+}
+
+/// Una forma flexionada
+#[derive(Debug, Clone, PartialEq)]
+pub struct InflectedForm {
+    pub form: String,
+    pub gender: Gender,
+    pub number: Number,
+}
+
+impl Inflector {
+    /// Crea el motor con las excepciones conocidas
+    pub fn new() -> Self {
+        let mut inflector = Self {
+            masculine_exceptions: HashSet::new(),
+            feminine_exceptions: HashSet::new(),
+            stressed_a_feminines: HashSet::new(),
+            gender_pairs: HashMap::new(),
+            irregular_plurals: HashMap::new(),
+            invariable_number: HashSet::new(),
+            invariable_gender: HashSet::new(),
+        };
+
+        inflector.load_exceptions();
+        inflector
+    }
+
+    /// Carga tablas de excepciones
+    fn load_exceptions(&mut self) {
+        // === MASCULINOS EN -A (mayoría de origen griego en -ma) ===
+        for word in &["mapa", "día", "problema", "tema", "sistema", "programa",
+                      "clima", "idioma", "drama", "esquema", "poema", "planeta",
+                      "cometa", "dilema", "enigma", "síntoma", "diploma", "aroma",
+                      "fantasma", "pijama", "tranvía", "sofá", "lema", "dogma",
+                      "panorama", "teorema", "prisma", "carisma", "trauma",
+                      // Terminación de femenino (-ie, -ez) pero masculinos
+                      "pie", "pez", "ajedrez", "jerez", "juez"] {
+            self.masculine_exceptions.insert(word.to_string());
+        }
+
+        // === FEMENINOS CON TERMINACIÓN NO FEMENINA ===
+        for word in &["mano", "foto", "moto", "radio", "libido", "seo",
+                      "flor", "labor", "sor", "miel", "piel", "sal", "cal",
+                      "col", "red", "sed", "pared", "merced", "ley", "grey",
+                      "calle", "clase", "noche", "nube", "gente", "leche",
+                      "carne", "fuente", "llave", "frase", "mente", "muerte",
+                      "parte", "suerte", "tarde", "torre", "base", "fe",
+                      "fiebre", "nieve", "sede", "serie", "especie", "superficie",
+                      "madre", "corriente", "sangre", "fase", "índole",
+                      "catástrofe", "razón", "imagen", "virgen", "sartén",
+                      "cárcel", "vocal", "señal", "cicatriz", "nariz", "raíz",
+                      "voz", "luz", "cruz", "paz", "vez", "nuez", "coz"] {
+            self.feminine_exceptions.insert(word.to_string());
+        }
+
+        // === FEMENINOS CON "A" TÓNICA INICIAL (el agua, las aguas) ===
+        for word in &["agua", "alma", "arma", "hambre", "águila", "área",
+                      "aula", "hacha", "hada", "ala", "ancla", "asa", "ave",
+                      "habla", "haba", "arca", "aria", "hampa", "álgebra"] {
+            self.stressed_a_feminines.insert(word.to_string());
+        }
+
+        // === PARES DE GÉNERO IRREGULARES ===
+        for (masc, fem) in &[("actor", "actriz"), ("emperador", "emperatriz"),
+                             ("rey", "reina"), ("príncipe", "princesa"),
+                             ("héroe", "heroína"), ("alcalde", "alcaldesa"),
+                             ("conde", "condesa"), ("duque", "duquesa"),
+                             ("barón", "baronesa"), ("poeta", "poetisa"),
+                             ("sacerdote", "sacerdotisa"), ("tigre", "tigresa"),
+                             ("gallo", "gallina"), ("padre", "madre"),
+                             ("hombre", "mujer"), ("yerno", "nuera"),
+                             ("caballo", "yegua"), ("toro", "vaca"),
+                             ("macho", "hembra"), ("padrino", "madrina")] {
+            self.gender_pairs.insert(masc.to_string(), fem.to_string());
+        }
+
+        // === PLURALES IRREGULARES (desplazamiento del acento) ===
+        for (singular, plural) in &[("carácter", "caracteres"),
+                                    ("régimen", "regímenes"),
+                                    ("espécimen", "especímenes")] {
+            self.irregular_plurals.insert(singular.to_string(), plural.to_string());
+        }
+
+        // === INVARIABLES EN NÚMERO ===
+        for word in &["lunes", "martes", "miércoles", "jueves", "viernes",
+                      "crisis", "tesis", "análisis", "síntesis", "dosis",
+                      "virus", "tórax", "clímax", "paraguas", "cumpleaños",
+                      "sacacorchos", "abrelatas", "tijeras", "gafas"] {
+            self.invariable_number.insert(word.to_string());
+        }
+
+        // === ADJETIVOS INVARIABLES EN GÉNERO ===
+        for word in &["mejor", "peor", "mayor", "menor", "superior", "inferior",
+                      "exterior", "interior", "anterior", "posterior", "ulterior",
+                      "cortés", "montés"] {
+            self.invariable_gender.insert(word.to_string());
+        }
+    }
+
+    // === NÚMERO ===
+
+    /// Forma el plural de un sustantivo o adjetivo en singular
+    pub fn pluralize(&self, word: &str) -> String {
+        let lower = word.to_lowercase();
+
+        if let Some(plural) = self.irregular_plurals.get(&lower) {
+            return plural.clone();
+        }
+        if self.invariable_number.contains(&lower) {
+            return lower;
+        }
+
+        let chars: Vec<char> = lower.chars().collect();
+        let last = match chars.last() {
+            Some(&c) => c,
+            None => return lower,
+        };
+        let nuclei = vowel_nuclei(&chars);
+
+        match last {
+            // Terminación en -s/-x: llanas y esdrújulas son invariables
+            's' | 'x' => {
+                if nuclei.len() <= 1 {
+                    format!("{}es", lower)
+                } else if has_written_accent_on_last(&chars, &nuclei) {
+                    format!("{}es", remove_accents(&lower))
+                } else {
+                    lower
+                }
+            }
+            // -z → -ces (luz → luces, lápiz → lápices)
+            'z' => {
+                let stem: String = chars[..chars.len() - 1].iter().collect();
+                format!("{}ces", stem)
+            }
+            // Vocal átona o á/é/ó tónicas → -s
+            'a' | 'e' | 'o' | 'i' | 'u' | 'á' | 'é' | 'ó' => format!("{}s", lower),
+            // í/ú tónicas → -es (rubí → rubíes)
+            'í' | 'ú' => format!("{}es", lower),
+            // Consonantes propias del español → -es con ajuste de acento
+            'l' | 'r' | 'n' | 'd' | 'j' | 'y' => {
+                if has_written_accent_on_last(&chars, &nuclei) {
+                    // Aguda con tilde: canción → canciones
+                    format!("{}es", remove_accents(&lower))
+                } else if last == 'n' && nuclei.len() > 1 && !has_written_accent(&chars) {
+                    // Llana en -n pasa a esdrújula: joven → jóvenes
+                    let mut accented = chars.clone();
+                    let (start, end) = nuclei[nuclei.len() - 2];
+                    accent_nucleus(&mut accented, start, end);
+                    format!("{}es", accented.iter().collect::<String>())
+                } else {
+                    format!("{}es", lower)
+                }
+            }
+            // Préstamos terminados en otras consonantes: club → clubs
+            _ => format!("{}s", lower),
+        }
+    }
+
+    /// Candidatos a singular de una forma, del más al menos probable
+    pub fn singular_candidates(&self, word: &str) -> Vec<String> {
+        let lower = word.to_lowercase();
+
+        if self.invariable_number.contains(&lower) {
+            return vec![lower];
+        }
+        for (singular, plural) in &self.irregular_plurals {
+            if *plural == lower {
+                return vec![singular.clone()];
+            }
+        }
+
+        let chars: Vec<char> = lower.chars().collect();
+        let n = chars.len();
+        if n < 3 || chars[n - 1] != 's' {
+            return vec![lower];
+        }
+
+        // Llanas en -is/-us son invariables (crisis, virus)
+        if (lower.ends_with("is") || lower.ends_with("us")) && !lower.ends_with("ís")
+            && !has_written_accent(&chars)
+        {
+            return vec![lower];
+        }
+
+        if n >= 4 && lower.ends_with("es") {
+            let stem: Vec<char> = chars[..n - 2].to_vec();
+            let stem_str: String = stem.iter().collect();
+            let coda = stem[stem.len() - 1];
+            let before_coda = stem.get(stem.len().wrapping_sub(2)).copied();
+
+            // -ces: luces → luz, dulces → dulce
+            if coda == 'c' {
+                let base: String = stem[..stem.len() - 1].iter().collect();
+                return if before_coda.is_some_and(is_vowel) {
+                    vec![format!("{}z", base), format!("{}e", stem_str)]
+                } else {
+                    vec![format!("{}e", stem_str)]
+                };
+            }
+
+            // rubíes → rubí
+            if coda == 'í' || coda == 'ú' {
+                return vec![stem_str];
+            }
+
+            // Consonante simple en coda: canciones → canción, árboles → árbol
+            if matches!(coda, 'l' | 'r' | 'n' | 'd' | 'j' | 'y' | 's' | 'x')
+                && before_coda.is_some_and(is_vowel)
+            {
+                let restored = restore_singular_accent(&chars, &stem);
+                let plus_e = format!("{}e", stem_str);
+                let stem_nuclei = vowel_nuclei(&stem);
+                return if coda == 's' && stem_nuclei.len() <= 1 {
+                    vec![plus_e, restored]
+                } else {
+                    vec![restored, plus_e]
+                };
+            }
+        }
+
+        // Vocal + s: casas → casa, cafés → café
+        vec![chars[..n - 1].iter().collect()]
+    }
+
+    /// Indica si la palabra es invariable en número
+    pub fn is_number_invariable(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        if self.invariable_number.contains(&lower) {
+            return true;
+        }
+        let chars: Vec<char> = lower.chars().collect();
+        let nuclei = vowel_nuclei(&chars);
+        matches!(chars.last(), Some('s') | Some('x'))
+            && nuclei.len() > 1
+            && !has_written_accent_on_last(&chars, &nuclei)
+            && self.singular_candidates(&lower).first() == Some(&lower)
+    }
+
+    // === GÉNERO ===
+
+    /// Género de un sustantivo en singular
+    pub fn noun_gender(&self, singular: &str) -> Gender {
+        let lower = singular.to_lowercase();
+
+        if self.masculine_exceptions.contains(&lower) {
+            return Gender::Masculine;
+        }
+        if self.feminine_exceptions.contains(&lower) || self.stressed_a_feminines.contains(&lower) {
+            return Gender::Feminine;
+        }
+        if self.gender_pairs.values().any(|f| *f == lower) {
+            return Gender::Feminine;
+        }
+
+        let feminine_suffixes = ["ción", "sión", "dad", "tad", "tud", "umbre", "ie",
+                                 "sis", "itis", "triz", "ez", "a"];
+        if feminine_suffixes.iter().any(|s| lower.ends_with(s)) {
+            return Gender::Feminine;
+        }
+
+        Gender::Masculine
+    }
+
+    /// Indica si un sustantivo femenino lleva "el"/"un" en singular (el agua)
+    pub fn takes_masculine_article(&self, word: &str) -> bool {
+        self.stressed_a_feminines.contains(&word.to_lowercase())
+    }
+
+    /// Forma femenina de un sustantivo o adjetivo (None si es invariable)
+    pub fn feminine(&self, masculine: &str) -> Option<String> {
+        let lower = masculine.to_lowercase();
+
+        if let Some(fem) = self.gender_pairs.get(&lower) {
+            return Some(fem.clone());
+        }
+        if self.invariable_gender.contains(&lower) || lower.ends_with("ista") {
+            return None;
+        }
+
+        let chars: Vec<char> = lower.chars().collect();
+        let n = chars.len();
+        if n < 2 {
+            return None;
+        }
+        let stem: String = chars[..n - 1].iter().collect();
+
+        if chars[n - 1] == 'o' {
+            return Some(format!("{}a", stem));
+        }
+        // -or, -ñol/-gol: trabajadora, española, mongola
+        if lower.ends_with("or") || lower.ends_with("ñol") || lower.ends_with("gol") {
+            return Some(format!("{}a", lower));
+        }
+        // -ón, -án, -ín, -és tónicas: pierden la tilde (mandón → mandona)
+        if matches!(chars[n - 1], 'n' | 's') && matches!(chars[n - 2], 'ó' | 'á' | 'í' | 'é') {
+            return Some(format!("{}a", remove_accents(&lower)));
+        }
+
+        None
+    }
+
+    /// Candidatos a masculino de una forma femenina
+    fn masculine_candidates(&self, feminine: &str) -> Vec<String> {
+        let lower = feminine.to_lowercase();
+        let mut candidates = Vec::new();
+
+        for (masc, fem) in &self.gender_pairs {
+            if *fem == lower {
+                candidates.push(masc.clone());
+            }
+        }
+
+        if let Some(stem) = lower.strip_suffix('a') {
+            if stem.ends_with("or") || stem.ends_with("ñol") || stem.ends_with("gol") {
+                candidates.push(stem.to_string());
+            }
+            // mandona → mandón, francesa → francés
+            for (plain, accented) in &[("on", "ón"), ("an", "án"), ("in", "ín"), ("es", "és")] {
+                if let Some(base) = stem.strip_suffix(plain) {
+                    candidates.push(format!("{}{}", base, accented));
+                }
+            }
+            candidates.push(format!("{}o", stem));
+        }
+
+        candidates
+    }
+
+    // === ANÁLISIS ===
+
+    /// Analiza un sustantivo (lema, género y número) usando solo reglas
+    pub fn analyze_noun(&self, word: &str) -> InflectionAnalysis {
+        self.analyze_noun_with(word, |_| false)
+    }
+
+    /// Analiza un sustantivo prefiriendo lemas que `is_known` reconoce
+    pub fn analyze_noun_with<F: Fn(&str) -> bool>(&self, word: &str, is_known: F) -> InflectionAnalysis {
+        let lower = word.to_lowercase();
+        let (lemma, number) = self.split_number(&lower, &is_known);
+        let gender = self.noun_gender(&lemma);
+
+        InflectionAnalysis { lemma, gender, number }
+    }
+
+    /// Analiza un adjetivo (lema en masculino singular, género y número)
+    pub fn analyze_adjective(&self, word: &str) -> InflectionAnalysis {
+        self.analyze_adjective_with(word, |_| false)
+    }
+
+    /// Analiza un adjetivo prefiriendo lemas que `is_known` reconoce
+    pub fn analyze_adjective_with<F: Fn(&str) -> bool>(&self, word: &str, is_known: F) -> InflectionAnalysis {
+        let lower = word.to_lowercase();
+        let (singular, number) = self.split_number(&lower, &is_known);
+
+        if singular.ends_with('o') {
+            return InflectionAnalysis { lemma: singular, gender: Gender::Masculine, number };
+        }
+
+        if singular.ends_with('a') && !singular.ends_with("ista") {
+            let candidates = self.masculine_candidates(&singular);
+            // Sin léxico: masculino en consonante solo para las terminaciones
+            // que lo piden (española, francesa, trabajadora); si no, en -o
+            let stem = &singular[..singular.len() - 1];
+            let fallback = if CONSONANT_MASCULINE_STEMS.iter().any(|e| stem.ends_with(e)) {
+                candidates.first()
+            } else {
+                candidates.last()
+            };
+            let lemma = candidates.iter()
+                .find(|c| is_known(c))
+                .or(fallback)
+                .cloned()
+                .unwrap_or_else(|| singular.clone());
+            return InflectionAnalysis { lemma, gender: Gender::Feminine, number };
+        }
+
+        // Masculino con femenino en -a (español, trabajador)
+        if self.feminine(&singular).is_some() {
+            return InflectionAnalysis { lemma: singular, gender: Gender::Masculine, number };
+        }
+
+        // Invariable en género: grande, azul, feliz
+        InflectionAnalysis { lemma: singular, gender: Gender::Neutral, number }
+    }
+
+    /// Separa número de una forma: (singular, número)
+    fn split_number<F: Fn(&str) -> bool>(&self, lower: &str, is_known: &F) -> (String, Number) {
+        if is_known(lower) && !lower.ends_with('s') {
+            return (lower.to_string(), Number::Singular);
+        }
+
+        let candidates = self.singular_candidates(lower);
+        let singular = candidates.iter()
+            .find(|c| is_known(c))
+            .unwrap_or(&candidates[0])
+            .clone();
+
+        if singular == lower {
+            (singular, Number::Singular)
+        } else {
+            (singular, Number::Plural)
+        }
+    }
+
+    /// Construye `NounInfo` para una forma nominal
+    pub fn noun_info(&self, word: &str, category: NounCategory) -> NounInfo {
+        let analysis = self.analyze_noun(word);
+        NounInfo {
+            gender: analysis.gender,
+            number: analysis.number,
+            category,
+            can_be_subject: true,
+            can_be_object: true,
+        }
+    }
+
+    // === GENERACIÓN ===
+
+    /// Todas las formas de un sustantivo (singular/plural, y femenino si es par de género)
+    pub fn noun_forms(&self, lemma: &str, category: &NounCategory) -> Vec<InflectedForm> {
+        let lower = lemma.to_lowercase();
+        let gender = self.noun_gender(&lower);
+        let mut forms = self.number_forms(&lower, gender.clone());
+
+        let has_gender_pair = self.gender_pairs.contains_key(&lower)
+            || matches!(category, NounCategory::Person | NounCategory::Animal);
+
+        if has_gender_pair && gender == Gender::Masculine {
+            if let Some(fem) = self.feminine(&lower) {
+                forms.extend(self.number_forms(&fem, Gender::Feminine));
+            }
+        }
+
+        forms
+    }
+
+    /// Todas las formas de un adjetivo (hasta 4: ms, fs, mp, fp)
+    pub fn adjective_forms(&self, lemma: &str) -> Vec<InflectedForm> {
+        let lower = lemma.to_lowercase();
+
+        match self.feminine(&lower) {
+            Some(fem) => {
+                let mut forms = self.number_forms(&lower, Gender::Masculine);
+                forms.extend(self.number_forms(&fem, Gender::Feminine));
+                forms
+            }
+            None => self.number_forms(&lower, Gender::Neutral),
+        }
+    }
+
+    /// Formas singular y plural con un género fijo
+    fn number_forms(&self, singular: &str, gender: Gender) -> Vec<InflectedForm> {
+        let plural = self.pluralize(singular);
+        let mut forms = vec![InflectedForm {
+            form: singular.to_string(),
+            gender: gender.clone(),
+            number: Number::Singular,
+        }];
+        if plural != singular {
+            forms.push(InflectedForm { form: plural, gender, number: Number::Plural });
+        }
+        forms
+    }
+}
+
+impl Default for Inflector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// === Funciones fonológicas auxiliares ===
+
+/// Verifica si un carácter es vocal (con o sin tilde)
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'ü')
+}
+
+/// Vocal fuerte (a, e, o) o débil tónica (í, ú), que forman hiato
+fn is_strong_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'o' | 'á' | 'é' | 'ó' | 'í' | 'ú')
+}
+
+fn has_accent(c: char) -> bool {
+    matches!(c, 'á' | 'é' | 'í' | 'ó' | 'ú')
+}
+
+fn has_written_accent(chars: &[char]) -> bool {
+    chars.iter().any(|&c| has_accent(c))
+}
+
+/// Núcleos silábicos como rangos (inicio, fin) inclusivos de índices de vocales
+fn vowel_nuclei(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut nuclei: Vec<(usize, usize)> = Vec::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        // "u" muda en que/qui/gue/gui
+        let silent_u = c == 'u'
+            && i > 0
+            && matches!(chars[i - 1], 'q' | 'g')
+            && chars.get(i + 1).is_some_and(|&n| matches!(n, 'e' | 'i' | 'é' | 'í'));
+        // "y" final tras vocal forma diptongo (rey, ley)
+        let final_y = c == 'y' && i + 1 == chars.len() && i > 0 && is_vowel(chars[i - 1]);
+
+        let is_nucleus = (is_vowel(c) && !silent_u) || final_y;
+        if !is_nucleus {
+            continue;
+        }
+
+        if let Some(last) = nuclei.last_mut() {
+            if last.1 + 1 == i {
+                let prev = chars[last.1];
+                // Hiato: dos vocales fuertes no forman diptongo
+                if !(is_strong_vowel(prev) && is_strong_vowel(c)) {
+                    last.1 = i;
+                    continue;
+                }
+            }
+        }
+        nuclei.push((i, i));
+    }
+
+    nuclei
+}
+
+/// Verifica si la tilde está en el último núcleo silábico
+fn has_written_accent_on_last(chars: &[char], nuclei: &[(usize, usize)]) -> bool {
+    nuclei.last().is_some_and(|&(start, end)| chars[start..=end].iter().any(|&c| has_accent(c)))
+}
+
+/// Acentúa la vocal fuerte de un núcleo (o la última débil si no hay fuerte)
+fn accent_nucleus(chars: &mut [char], start: usize, end: usize) {
+    let target = (start..=end)
+        .find(|&i| matches!(chars[i], 'a' | 'e' | 'o'))
+        .unwrap_or(end);
+    chars[target] = add_accent(chars[target]);
+}
+
+fn add_accent(c: char) -> char {
+    match c {
+        'a' => 'á',
+        'e' => 'é',
+        'i' => 'í',
+        'o' => 'ó',
+        'u' => 'ú',
+        _ => c,
+    }
+}
+
+pub(super) fn remove_accents(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'á' => 'a',
+            'é' => 'e',
+            'í' => 'i',
+            'ó' => 'o',
+            'ú' => 'u',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Reconstruye la tilde del singular a partir del plural en -es
+fn restore_singular_accent(plural: &[char], stem: &[char]) -> String {
+    let stem_str: String = stem.iter().collect();
+    let stem_nuclei = vowel_nuclei(stem);
+
+    if has_written_accent(plural) {
+        // jóvenes → joven: llana terminada en -n/-s no lleva tilde
+        let accent_pos = plural.iter().position(|&c| has_accent(c)).unwrap_or(0);
+        let on_last = stem_nuclei.last().is_some_and(|&(s, e)| accent_pos >= s && accent_pos <= e);
+        let ends_n_s = matches!(stem.last(), Some('n') | Some('s'));
+        if ends_n_s && !on_last && stem_nuclei.len() > 1 {
+            return remove_accents(&stem_str);
+        }
+        return stem_str;
+    }
+
+    // canciones → canción: aguda terminada en -n/-s lleva tilde
+    if matches!(stem.last(), Some('n') | Some('s')) && stem_nuclei.len() > 1 {
+        let mut accented = stem.to_vec();
+        let (start, end) = stem_nuclei[stem_nuclei.len() - 1];
+        accent_nucleus(&mut accented, start, end);
+        return accented.iter().collect();
+    }
+
+    stem_str
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pluralize() {
+        let inf = Inflector::new();
+
+        assert_eq!(inf.pluralize("casa"), "casas");
+        assert_eq!(inf.pluralize("árbol"), "árboles");
+        assert_eq!(inf.pluralize("canción"), "canciones");
+        assert_eq!(inf.pluralize("joven"), "jóvenes");
+        assert_eq!(inf.pluralize("examen"), "exámenes");
+        assert_eq!(inf.pluralize("luz"), "luces");
+        assert_eq!(inf.pluralize("lápiz"), "lápices");
+        assert_eq!(inf.pluralize("rubí"), "rubíes");
+        assert_eq!(inf.pluralize("sofá"), "sofás");
+        assert_eq!(inf.pluralize("rey"), "reyes");
+        assert_eq!(inf.pluralize("mes"), "meses");
+        assert_eq!(inf.pluralize("autobús"), "autobuses");
+        assert_eq!(inf.pluralize("carácter"), "caracteres");
+    }
+
+    #[test]
+    fn test_invariable_plurals() {
+        let inf = Inflector::new();
+
+        assert_eq!(inf.pluralize("crisis"), "crisis");
+        assert_eq!(inf.pluralize("lunes"), "lunes");
+        assert_eq!(inf.pluralize("tórax"), "tórax");
+        assert!(inf.is_number_invariable("crisis"));
+        assert!(!inf.is_number_invariable("casa"));
+        assert!(!inf.is_number_invariable("casas"));
+    }
+
+    #[test]
+    fn test_singular_analysis() {
+        let inf = Inflector::new();
+
+        let a = inf.analyze_noun("canciones");
+        assert_eq!(a.lemma, "canción");
+        assert_eq!(a.number, Number::Plural);
+        assert_eq!(a.gender, Gender::Feminine);
+
+        assert_eq!(inf.analyze_noun("jóvenes").lemma, "joven");
+        assert_eq!(inf.analyze_noun("luces").lemma, "luz");
+        assert_eq!(inf.analyze_noun("dulces").lemma, "dulce");
+        assert_eq!(inf.analyze_noun("crisis").number, Number::Singular);
+
+        // Con léxico conocido se elige el lema correcto
+        let a = inf.analyze_noun_with("clases", |w| w == "clase");
+        assert_eq!(a.lemma, "clase");
+    }
+
+    #[test]
+    fn test_gender_exceptions() {
+        let inf = Inflector::new();
+
+        assert_eq!(inf.noun_gender("mapa"), Gender::Masculine);
+        assert_eq!(inf.noun_gender("problema"), Gender::Masculine);
+        assert_eq!(inf.noun_gender("mano"), Gender::Feminine);
+        assert_eq!(inf.noun_gender("canción"), Gender::Feminine);
+        assert_eq!(inf.noun_gender("coliseo"), Gender::Masculine);
+
+        // "el agua" es femenino aunque lleve "el"
+        assert_eq!(inf.noun_gender("agua"), Gender::Feminine);
+        assert!(inf.takes_masculine_article("agua"));
+        assert!(!inf.takes_masculine_article("casa"));
+
+        // -ie y -ez no siempre son femeninos
+        assert_eq!(inf.noun_gender("pie"), Gender::Masculine);
+        assert_eq!(inf.noun_gender("pez"), Gender::Masculine);
+        assert_eq!(inf.noun_gender("serie"), Gender::Feminine);
+        assert_eq!(inf.noun_gender("vejez"), Gender::Feminine);
+    }
+
+    #[test]
+    fn test_gender_pairs() {
+        let inf = Inflector::new();
+
+        assert_eq!(inf.feminine("niño").as_deref(), Some("niña"));
+        assert_eq!(inf.feminine("actor").as_deref(), Some("actriz"));
+        assert_eq!(inf.feminine("profesor").as_deref(), Some("profesora"));
+        assert_eq!(inf.feminine("francés").as_deref(), Some("francesa"));
+        assert_eq!(inf.feminine("español").as_deref(), Some("española"));
+        assert_eq!(inf.feminine("azul"), None);
+        assert_eq!(inf.feminine("mejor"), None);
+    }
+
+    #[test]
+    fn test_adjective_forms() {
+        let inf = Inflector::new();
+
+        let forms: Vec<String> = inf.adjective_forms("romano").into_iter().map(|f| f.form).collect();
+        assert_eq!(forms, vec!["romano", "romanos", "romana", "romanas"]);
+
+        let forms: Vec<String> = inf.adjective_forms("azul").into_iter().map(|f| f.form).collect();
+        assert_eq!(forms, vec!["azul", "azules"]);
+
+        let a = inf.analyze_adjective("azules");
+        assert_eq!(a.lemma, "azul");
+        assert_eq!(a.gender, Gender::Neutral);
+        assert_eq!(a.number, Number::Plural);
+
+        let a = inf.analyze_adjective("romanas");
+        assert_eq!(a.lemma, "romano");
+        assert_eq!(a.gender, Gender::Feminine);
+
+        // Masculino en consonante (sin léxico)
+        for (feminine, lemma) in [("española", "español"), ("francesas", "francés"),
+                                  ("trabajadora", "trabajador"), ("alemana", "alemán"),
+                                  ("mandona", "mandón"), ("sola", "solo"), ("humana", "humano")] {
+            assert_eq!(inf.analyze_adjective(feminine).lemma, lemma, "{}", feminine);
+        }
+    }
+}
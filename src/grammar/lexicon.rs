@@ -0,0 +1,250 @@
+//! # Lexicon Module
+//!
+//! Léxico de clases cerradas (artículos, preposiciones, pronombres,
+//! conjunciones y adverbios) en un archivo de texto editable:
+//!
+//! ```text
+//! @version 1
+//! article      el     Definite=Def|Gender=Masc|Number=Sing
+//! preposition  hacia
+//! pronoun      les    Person=3|Number=Plur|Case=Dat
+//! ```
+//!
+//! El léxico por defecto (`data/grammar/lexicon.txt`) va embebido en el
+//! binario; `Lexicon::from_file` permite usar o añadir uno propio.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use super::{ArticleInfo, Gender, Number, Person, PronounCase, PronounInfo};
+
+/// Versión del formato que entiende este parser
+pub const LEXICON_VERSION: u32 = 1;
+
+/// Léxico por defecto
+const EMBEDDED_LEXICON: &str = include_str!("../../data/grammar/lexicon.txt");
+
+/// Clase de una palabra del léxico cerrado
+#[derive(Debug, Clone)]
+pub enum WordClass {
+    Article(ArticleInfo),
+    Preposition,
+    Pronoun(PronounInfo),
+    Conjunction,
+    Adverb,
+}
+
+/// Léxico de clases cerradas, en el orden del archivo
+#[derive(Debug, Clone)]
+pub struct Lexicon {
+    /// Versión declarada con `@version`
+    pub version: u32,
+    entries: Vec<(String, WordClass)>,
+}
+
+/// Error de lectura del léxico
+#[derive(Debug)]
+pub enum LexiconError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for LexiconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexiconError::IoError(e) => write!(f, "IO error: {}", e),
+            LexiconError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for LexiconError {}
+
+impl Lexicon {
+    /// Léxico vacío de la versión actual
+    pub fn new() -> Self {
+        Self { version: LEXICON_VERSION, entries: Vec::new() }
+    }
+
+    /// Léxico embebido en el binario
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED_LEXICON).expect("léxico embebido mal formado")
+    }
+
+    /// Lee un léxico desde archivo
+    pub fn from_file(path: &Path) -> Result<Self, LexiconError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| LexiconError::IoError(e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Parsea el formato de texto (requiere `@version` antes de las entradas)
+    pub fn parse(content: &str) -> Result<Self, LexiconError> {
+        let mut version = None;
+        let mut entries = Vec::new();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line_no = line_no + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields[0] == "@version" {
+                let v = fields.get(1)
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .ok_or_else(|| LexiconError::ParseError(line_no, "versión inválida".to_string()))?;
+                if v > LEXICON_VERSION {
+                    return Err(LexiconError::ParseError(line_no, format!("versión {} no soportada", v)));
+                }
+                version = Some(v);
+                continue;
+            }
+
+            if version.is_none() {
+                return Err(LexiconError::ParseError(line_no, "falta la línea @version".to_string()));
+            }
+            if fields.len() < 2 || fields.len() > 3 {
+                return Err(LexiconError::ParseError(line_no, "se esperaba: categoría forma [rasgos]".to_string()));
+            }
+
+            let feats = fields.get(2).copied().unwrap_or("_");
+            let class = parse_class(fields[0], feats)
+                .map_err(|e| LexiconError::ParseError(line_no, e))?;
+            entries.push((fields[1].to_lowercase(), class));
+        }
+
+        let version = version.ok_or_else(|| LexiconError::ParseError(0, "falta la línea @version".to_string()))?;
+        Ok(Self { version, entries })
+    }
+
+    /// Entradas (forma, clase) en orden
+    pub fn entries(&self) -> &[(String, WordClass)] {
+        &self.entries
+    }
+
+    /// Formas del léxico en orden
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(word, _)| word.as_str())
+    }
+
+    /// Añade las entradas de otro léxico (las posteriores prevalecen al cargar)
+    pub fn extend(&mut self, other: Lexicon) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for Lexicon {
+    fn default() -> Self {
+        Self::embedded()
+    }
+}
+
+fn parse_class(category: &str, feats: &str) -> Result<WordClass, String> {
+    let get = |key: &str| -> Option<&str> {
+        feats.split('|')
+            .filter_map(|kv| kv.split_once('='))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    };
+    let required = |key: &str| get(key).ok_or_else(|| format!("falta el rasgo {}", key));
+
+    match category {
+        "article" => Ok(WordClass::Article(ArticleInfo {
+            definite: match required("Definite")? {
+                "Def" => true,
+                "Ind" => false,
+                other => return Err(format!("Definite={} inválido", other)),
+            },
+            gender: parse_gender(required("Gender")?)?,
+            number: parse_number(required("Number")?)?,
+        })),
+        "pronoun" => Ok(WordClass::Pronoun(PronounInfo {
+            person: match required("Person")? {
+                "1" => Person::First,
+                "2" => Person::Second,
+                "3" => Person::Third,
+                other => return Err(format!("Person={} inválido", other)),
+            },
+            number: parse_number(required("Number")?)?,
+            case: match required("Case")? {
+                "Nom" => PronounCase::Subject,
+                "Acc" => PronounCase::DirectObj,
+                "Dat" => PronounCase::IndirectObj,
+                "Refl" => PronounCase::Reflexive,
+                other => return Err(format!("Case={} inválido", other)),
+            },
+        })),
+        "preposition" => Ok(WordClass::Preposition),
+        "conjunction" => Ok(WordClass::Conjunction),
+        "adverb" => Ok(WordClass::Adverb),
+        other => Err(format!("categoría desconocida: {}", other)),
+    }
+}
+
+fn parse_gender(value: &str) -> Result<Gender, String> {
+    match value {
+        "Masc" => Ok(Gender::Masculine),
+        "Fem" => Ok(Gender::Feminine),
+        other => Err(format!("Gender={} inválido", other)),
+    }
+}
+
+fn parse_number(value: &str) -> Result<Number, String> {
+    match value {
+        "Sing" => Ok(Number::Singular),
+        "Plur" => Ok(Number::Plural),
+        other => Err(format!("Number={} inválido", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{SpanishGrammar, TokenType};
+
+    #[test]
+    fn test_embedded_lexicon() {
+        let lexicon = Lexicon::embedded();
+        assert_eq!(lexicon.version, LEXICON_VERSION);
+        assert!(lexicon.words().any(|w| w == "según"));
+
+        let les = lexicon.entries().iter().find(|(w, _)| w == "les").unwrap();
+        assert!(matches!(&les.1, WordClass::Pronoun(info)
+            if info.case == PronounCase::IndirectObj && info.number == Number::Plural));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(Lexicon::parse("adverb muy"), Err(LexiconError::ParseError(1, _))));
+        assert!(matches!(Lexicon::parse("@version 99"), Err(LexiconError::ParseError(1, _))));
+        assert!(matches!(
+            Lexicon::parse("@version 1\n\narticle el Gender=Masc|Number=Sing"),
+            Err(LexiconError::ParseError(3, _))
+        ));
+        assert!(matches!(Lexicon::parse("@version 1\nverb correr"), Err(LexiconError::ParseError(2, _))));
+    }
+
+    #[test]
+    fn test_extend_grammar() {
+        let extra = Lexicon::parse("@version 1\n# locativos\nadverb acá\npreposition mediante\n").unwrap();
+        assert_eq!(extra.len(), 2);
+
+        let mut grammar = SpanishGrammar::new();
+        assert!(matches!(grammar.classify_token("acá"), TokenType::Unknown));
+
+        grammar.load_lexicon(&extra);
+        assert!(matches!(grammar.classify_token("acá"), TokenType::Adverb));
+        assert!(matches!(grammar.classify_token("mediante"), TokenType::Preposition));
+    }
+}
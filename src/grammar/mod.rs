@@ -10,8 +10,26 @@
 //! 1. NÚCLEO: Sujeto + Predicado (acción)
 //! 2. REFINAMIENTO: Artículos, adjetivos, complementos
 
+pub mod inflection;
+pub mod checker;
+pub mod conllu;
+pub mod dependency;
+pub mod tagger;
+pub mod clitics;
+pub mod mood;
+pub mod clauses;
+pub mod lexicon;
+mod agreement;
+mod chunker;
+
 use std::collections::{HashMap, HashSet};
 use crate::tao::{GrammaticalRole, GrammaticalStructure, GrammaticalComponent, SentenceType};
+use inflection::Inflector;
+use conllu::Upos;
+use tagger::PosTagger;
+use clitics::Region;
+use clauses::ClauseTree;
+use lexicon::{Lexicon, WordClass};
 
 /// Motor de gramática española
 #[derive(Debug, Clone)]
@@ -39,6 +57,15 @@ pub struct SpanishGrammar {
 
     /// Adverbios
     adverbs: HashSet<String>,
+
+    /// Flexión nominal y adjetival
+    inflector: Inflector,
+
+    /// Etiquetador entrenado (desambigua por contexto)
+    tagger: Option<PosTagger>,
+
+    /// Variedad regional (tolerancia al leísmo)
+    region: Region,
 }
 
 /// Información de un verbo
@@ -146,13 +173,77 @@ pub enum PronounCase {
     DirectObj,   // me, te, lo/la
     IndirectObj, // me, te, le
     Reflexive,   // me, te, se
+    Passive,     // se (pasiva refleja: "se venden casas")
+    Impersonal,  // se (impersonal: "se vive bien")
+}
+
+/// Rasgos morfológicos de una palabra (los que no aplican quedan en None)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MorphFeatures {
+    pub gender: Option<Gender>,
+    pub number: Option<Number>,
+    pub person: Option<Person>,
+    pub tense: Option<Tense>,
+}
+
+impl MorphFeatures {
+    /// Columna FEATS de CoNLL-U ("Gender=Fem|Number=Sing", "_" si vacío)
+    pub fn to_ud_string(&self) -> String {
+        let mut feats = Vec::new();
+
+        match self.gender {
+            Some(Gender::Masculine) => feats.push("Gender=Masc"),
+            Some(Gender::Feminine) => feats.push("Gender=Fem"),
+            _ => {}
+        }
+        match self.tense {
+            Some(Tense::Conditional) => feats.push("Mood=Cnd"),
+            Some(Tense::Subjunctive) => feats.push("Mood=Sub"),
+            Some(_) => feats.push("Mood=Ind"),
+            None => {}
+        }
+        match self.number {
+            Some(Number::Singular) => feats.push("Number=Sing"),
+            Some(Number::Plural) => feats.push("Number=Plur"),
+            None => {}
+        }
+        match self.person {
+            Some(Person::First) => feats.push("Person=1"),
+            Some(Person::Second) => feats.push("Person=2"),
+            Some(Person::Third) => feats.push("Person=3"),
+            None => {}
+        }
+        match self.tense {
+            Some(Tense::Present) => feats.push("Tense=Pres"),
+            Some(Tense::Past) => feats.push("Tense=Past"),
+            Some(Tense::Future) => feats.push("Tense=Fut"),
+            Some(Tense::Imperfect) => feats.push("Tense=Imp"),
+            _ => {}
+        }
+
+        if feats.is_empty() { "_".to_string() } else { feats.join("|") }
+    }
+}
+
+/// Palabra clasificada: forma, lema, categoría universal y rasgos
+#[derive(Debug, Clone)]
+pub struct TaggedToken {
+    /// Forma original
+    pub text: String,
+    pub lemma: String,
+    pub pos: Upos,
+    pub features: MorphFeatures,
 }
 
 /// Resultado de análisis gramatical
 #[derive(Debug, Clone)]
 pub struct GrammarAnalysis {
+    /// Palabras clasificadas, en orden
+    pub tokens: Vec<TaggedToken>,
     /// Estructura detectada
     pub structure: GrammaticalStructure,
+    /// Cláusulas principal, subordinadas y coordinadas
+    pub clauses: ClauseTree,
     /// Score de validez gramatical (0.0 - 1.0)
     pub validity_score: f64,
     /// Errores o warnings detectados
@@ -175,6 +266,12 @@ pub struct GrammarIssue {
     pub position: usize,
     pub severity: IssueSeverity,
     pub message: String,
+    /// Identificador de la regla que lo detectó (ver `checker::RULES`)
+    pub rule: &'static str,
+    /// Rango de bytes en el texto original (solo en `GrammarChecker::check`)
+    pub span: Option<(usize, usize)>,
+    /// Reemplazo sugerido para el span
+    pub suggestion: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -187,6 +284,11 @@ pub enum IssueSeverity {
 impl SpanishGrammar {
     /// Crea gramática con vocabulario base
     pub fn new() -> Self {
+        Self::with_lexicon(&Lexicon::default())
+    }
+
+    /// Crea gramática con un léxico de clases cerradas propio
+    pub fn with_lexicon(lexicon: &Lexicon) -> Self {
         let mut grammar = Self {
             verbs: HashMap::new(),
             nouns: HashMap::new(),
@@ -196,89 +298,52 @@ impl SpanishGrammar {
             pronouns: HashMap::new(),
             conjunctions: HashSet::new(),
             adverbs: HashSet::new(),
+            inflector: Inflector::new(),
+            tagger: None,
+            region: Region::default(),
         };
 
+        grammar.load_lexicon(lexicon);
         grammar.load_base_vocabulary();
         grammar
     }
 
-    /// Carga vocabulario base
-    fn load_base_vocabulary(&mut self) {
-        // === ARTÍCULOS ===
-        self.articles.insert("el".to_string(), ArticleInfo {
-            definite: true, gender: Gender::Masculine, number: Number::Singular
-        });
-        self.articles.insert("la".to_string(), ArticleInfo {
-            definite: true, gender: Gender::Feminine, number: Number::Singular
-        });
-        self.articles.insert("los".to_string(), ArticleInfo {
-            definite: true, gender: Gender::Masculine, number: Number::Plural
-        });
-        self.articles.insert("las".to_string(), ArticleInfo {
-            definite: true, gender: Gender::Feminine, number: Number::Plural
-        });
-        self.articles.insert("un".to_string(), ArticleInfo {
-            definite: false, gender: Gender::Masculine, number: Number::Singular
-        });
-        self.articles.insert("una".to_string(), ArticleInfo {
-            definite: false, gender: Gender::Feminine, number: Number::Singular
-        });
-        self.articles.insert("unos".to_string(), ArticleInfo {
-            definite: false, gender: Gender::Masculine, number: Number::Plural
-        });
-        self.articles.insert("unas".to_string(), ArticleInfo {
-            definite: false, gender: Gender::Feminine, number: Number::Plural
-        });
-
-        // === PREPOSICIONES ===
-        for prep in &["a", "ante", "bajo", "con", "contra", "de", "desde",
-                      "en", "entre", "hacia", "hasta", "para", "por",
-                      "según", "sin", "sobre", "tras"] {
-            self.prepositions.insert(prep.to_string());
+    /// Añade las palabras de un léxico de clases cerradas
+    pub fn load_lexicon(&mut self, lexicon: &Lexicon) {
+        for (word, class) in lexicon.entries() {
+            match class {
+                WordClass::Article(info) => { self.articles.insert(word.clone(), info.clone()); }
+                WordClass::Preposition => { self.prepositions.insert(word.clone()); }
+                WordClass::Pronoun(info) => { self.pronouns.insert(word.clone(), info.clone()); }
+                WordClass::Conjunction => { self.conjunctions.insert(word.clone()); }
+                WordClass::Adverb => { self.adverbs.insert(word.clone()); }
+            }
         }
+    }
 
-        // === PRONOMBRES ===
-        self.pronouns.insert("yo".to_string(), PronounInfo {
-            person: Person::First, number: Number::Singular, case: PronounCase::Subject
-        });
-        self.pronouns.insert("tú".to_string(), PronounInfo {
-            person: Person::Second, number: Number::Singular, case: PronounCase::Subject
-        });
-        self.pronouns.insert("él".to_string(), PronounInfo {
-            person: Person::Third, number: Number::Singular, case: PronounCase::Subject
-        });
-        self.pronouns.insert("ella".to_string(), PronounInfo {
-            person: Person::Third, number: Number::Singular, case: PronounCase::Subject
-        });
-        self.pronouns.insert("nosotros".to_string(), PronounInfo {
-            person: Person::First, number: Number::Plural, case: PronounCase::Subject
-        });
-        self.pronouns.insert("me".to_string(), PronounInfo {
-            person: Person::First, number: Number::Singular, case: PronounCase::DirectObj
-        });
-        self.pronouns.insert("te".to_string(), PronounInfo {
-            person: Person::Second, number: Number::Singular, case: PronounCase::DirectObj
-        });
-        self.pronouns.insert("le".to_string(), PronounInfo {
-            person: Person::Third, number: Number::Singular, case: PronounCase::IndirectObj
-        });
-        self.pronouns.insert("se".to_string(), PronounInfo {
-            person: Person::Third, number: Number::Singular, case: PronounCase::Reflexive
-        });
-
-        // === CONJUNCIONES ===
-        for conj in &["y", "e", "o", "u", "pero", "sino", "que", "porque",
-                      "aunque", "si", "cuando", "donde", "como"] {
-            self.conjunctions.insert(conj.to_string());
-        }
+    /// ¿Es artículo, preposición, pronombre, conjunción o adverbio común?
+    pub fn is_closed_class(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        self.articles.contains_key(&lower)
+            || self.prepositions.contains(&lower)
+            || self.pronouns.contains_key(&lower)
+            || self.conjunctions.contains(&lower)
+            || self.adverbs.contains(&lower)
+    }
 
-        // === ADVERBIOS COMUNES ===
-        for adv in &["muy", "bien", "mal", "mucho", "poco", "siempre",
-                     "nunca", "ya", "todavía", "aquí", "allí", "ahora",
-                     "después", "antes", "también", "tampoco", "sí", "no"] {
-            self.adverbs.insert(adv.to_string());
-        }
+    /// Palabras de clases cerradas conocidas (artículos, preposiciones, pronombres...)
+    pub fn closed_class_words(&self) -> Vec<&str> {
+        self.articles.keys()
+            .chain(self.prepositions.iter())
+            .chain(self.pronouns.keys())
+            .chain(self.conjunctions.iter())
+            .chain(self.adverbs.iter())
+            .map(|w| w.as_str())
+            .collect()
+    }
 
+    /// Carga vocabulario base (verbos)
+    fn load_base_vocabulary(&mut self) {
         // === VERBOS BASE ===
         self.add_verb_gustar();
         self.add_verb_ser();
@@ -286,6 +351,9 @@ impl SpanishGrammar {
         self.add_verb_visitar();
         self.add_verb_correr();
         self.add_verb_ir();
+        self.add_verb_dar();
+        self.add_verb_decir();
+        self.add_verb_vender();
     }
 
     fn add_verb_gustar(&mut self) {
@@ -378,6 +446,11 @@ impl SpanishGrammar {
             person: Person::Third, number: Number::Singular, tense: Tense::Past
         });
 
+        // Subjuntivo de tú: imperativo negado ("no lo visites")
+        conjugations.insert("visites".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Subjunctive
+        });
+
         self.verbs.insert("visitar".to_string(), VerbInfo {
             infinitive: "visitar".to_string(),
             transitive: true,
@@ -404,6 +477,11 @@ impl SpanishGrammar {
             person: Person::Third, number: Number::Plural, tense: Tense::Present
         });
 
+        // Subjuntivo de tú: imperativo negado ("no lo corras")
+        conjugations.insert("corras".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Subjunctive
+        });
+
         self.verbs.insert("correr".to_string(), VerbInfo {
             infinitive: "correr".to_string(),
             transitive: false,
@@ -436,6 +514,11 @@ impl SpanishGrammar {
             person: Person::Third, number: Number::Singular, tense: Tense::Past
         });
 
+        // Subjuntivo de tú: imperativo negado ("no lo vayas")
+        conjugations.insert("vayas".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Subjunctive
+        });
+
         self.verbs.insert("ir".to_string(), VerbInfo {
             infinitive: "ir".to_string(),
             transitive: false,
@@ -444,6 +527,111 @@ impl SpanishGrammar {
         });
     }
 
+    fn add_verb_dar(&mut self) {
+        let mut conjugations = HashMap::new();
+        conjugations.insert("doy".to_string(), Conjugation {
+            person: Person::First, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("das".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("da".to_string(), Conjugation {
+            person: Person::Third, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("damos".to_string(), Conjugation {
+            person: Person::First, number: Number::Plural, tense: Tense::Present
+        });
+        conjugations.insert("dan".to_string(), Conjugation {
+            person: Person::Third, number: Number::Plural, tense: Tense::Present
+        });
+        conjugations.insert("dio".to_string(), Conjugation {
+            person: Person::Third, number: Number::Singular, tense: Tense::Past
+        });
+
+        // Subjuntivo de tú: imperativo negado ("no lo des")
+        conjugations.insert("des".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Subjunctive
+        });
+
+        self.verbs.insert("dar".to_string(), VerbInfo {
+            infinitive: "dar".to_string(),
+            transitive: true,
+            conjugations,
+            category: VerbCategory::Action,
+        });
+    }
+
+    fn add_verb_decir(&mut self) {
+        let mut conjugations = HashMap::new();
+        conjugations.insert("digo".to_string(), Conjugation {
+            person: Person::First, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("dices".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("dice".to_string(), Conjugation {
+            person: Person::Third, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("decimos".to_string(), Conjugation {
+            person: Person::First, number: Number::Plural, tense: Tense::Present
+        });
+        conjugations.insert("dicen".to_string(), Conjugation {
+            person: Person::Third, number: Number::Plural, tense: Tense::Present
+        });
+        conjugations.insert("dije".to_string(), Conjugation {
+            person: Person::First, number: Number::Singular, tense: Tense::Past
+        });
+        conjugations.insert("dijo".to_string(), Conjugation {
+            person: Person::Third, number: Number::Singular, tense: Tense::Past
+        });
+
+        // Subjuntivo de tú: imperativo negado ("no lo digas")
+        conjugations.insert("digas".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Subjunctive
+        });
+
+        self.verbs.insert("decir".to_string(), VerbInfo {
+            infinitive: "decir".to_string(),
+            transitive: true,
+            conjugations,
+            category: VerbCategory::Cognitive,
+        });
+    }
+
+    fn add_verb_vender(&mut self) {
+        let mut conjugations = HashMap::new();
+        conjugations.insert("vendo".to_string(), Conjugation {
+            person: Person::First, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("vendes".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("vende".to_string(), Conjugation {
+            person: Person::Third, number: Number::Singular, tense: Tense::Present
+        });
+        conjugations.insert("vendemos".to_string(), Conjugation {
+            person: Person::First, number: Number::Plural, tense: Tense::Present
+        });
+        conjugations.insert("venden".to_string(), Conjugation {
+            person: Person::Third, number: Number::Plural, tense: Tense::Present
+        });
+        conjugations.insert("vendió".to_string(), Conjugation {
+            person: Person::Third, number: Number::Singular, tense: Tense::Past
+        });
+
+        // Subjuntivo de tú: imperativo negado ("no lo vendas")
+        conjugations.insert("vendas".to_string(), Conjugation {
+            person: Person::Second, number: Number::Singular, tense: Tense::Subjunctive
+        });
+
+        self.verbs.insert("vender".to_string(), VerbInfo {
+            infinitive: "vender".to_string(),
+            transitive: true,
+            conjugations,
+            category: VerbCategory::Action,
+        });
+    }
+
     /// Añade un sustantivo al vocabulario
     pub fn add_noun(&mut self, word: &str, info: NounInfo) {
         self.nouns.insert(word.to_lowercase(), info);
@@ -454,86 +642,281 @@ impl SpanishGrammar {
         self.adjectives.insert(word.to_lowercase());
     }
 
-    /// Analiza una oración tokenizada
-    pub fn analyze(&self, tokens: &[String]) -> GrammarAnalysis {
-        let mut components = Vec::new();
-        let issues: Vec<GrammarIssue> = Vec::new();
-        let mut expected_at = HashMap::new();
+    /// Añade un sustantivo con todas sus formas flexionadas.
+    /// Retorna las formas añadidas (singular, plural y femenino si aplica)
+    pub fn add_noun_forms(&mut self, lemma: &str, category: NounCategory) -> Vec<String> {
+        let forms = self.inflector.noun_forms(lemma, &category);
+        let mut added = Vec::with_capacity(forms.len());
+
+        for form in forms {
+            self.nouns.insert(form.form.clone(), NounInfo {
+                gender: form.gender,
+                number: form.number,
+                category: category.clone(),
+                can_be_subject: true,
+                can_be_object: true,
+            });
+            added.push(form.form);
+        }
 
-        // Identificar tipo de cada token
-        let token_types: Vec<TokenType> = tokens
-            .iter()
-            .map(|t| self.classify_token(t))
-            .collect();
+        added
+    }
 
-        // Buscar el verbo (núcleo de la oración)
-        let verb_positions: Vec<usize> = token_types
-            .iter()
-            .enumerate()
-            .filter(|(_, tt)| matches!(tt, TokenType::Verb(_)))
-            .map(|(i, _)| i)
+    /// Añade un adjetivo con sus formas de género y número.
+    /// Retorna las formas añadidas
+    pub fn add_adjective_forms(&mut self, lemma: &str) -> Vec<String> {
+        let forms: Vec<String> = self.inflector.adjective_forms(lemma)
+            .into_iter()
+            .map(|f| f.form)
             .collect();
 
-        let sentence_type = if verb_positions.is_empty() {
-            SentenceType::Unknown
-        } else {
-            self.determine_sentence_type(&token_types, &verb_positions)
-        };
+        for form in &forms {
+            self.adjectives.insert(form.clone());
+        }
 
-        // Construir componentes
-        for (i, tt) in token_types.iter().enumerate() {
-            let role = match tt {
-                TokenType::Verb(_) => Some(GrammaticalRole::Verb),
-                TokenType::Noun(_) => {
-                    // Determinar si es sujeto u objeto según posición
-                    if verb_positions.first().map_or(false, |&v| i < v) {
-                        Some(GrammaticalRole::Subject)
-                    } else {
-                        Some(GrammaticalRole::DirectObject)
-                    }
-                }
-                TokenType::Article(_) => Some(GrammaticalRole::Article),
-                TokenType::Adjective => Some(GrammaticalRole::Adjective),
-                TokenType::Preposition => Some(GrammaticalRole::Preposition),
-                TokenType::Pronoun(_) => {
-                    // Determinar rol del pronombre
-                    Some(GrammaticalRole::Subject)
-                }
-                TokenType::Adverb => Some(GrammaticalRole::Adverb),
-                TokenType::Conjunction => Some(GrammaticalRole::Conjunction),
-                TokenType::Unknown => None,
-            };
+        forms
+    }
 
-            if let Some(r) = role {
-                components.push(GrammaticalComponent {
-                    role: r,
-                    tokens: vec![i],
-                    head: Some(i),
-                });
-            }
-        }
+    /// Motor de flexión nominal y adjetival
+    pub fn inflector(&self) -> &Inflector {
+        &self.inflector
+    }
+
+    /// Instala un etiquetador: `analyze` clasificará por contexto
+    pub fn set_tagger(&mut self, tagger: PosTagger) {
+        self.tagger = Some(tagger);
+    }
+
+    /// Etiquetador instalado
+    pub fn tagger(&self) -> Option<&PosTagger> {
+        self.tagger.as_ref()
+    }
+
+    /// Define la variedad regional
+    pub fn set_region(&mut self, region: Region) {
+        self.region = region;
+    }
+
+    /// Variedad regional
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// Analiza una oración tokenizada
+    pub fn analyze(&self, tokens: &[String]) -> GrammarAnalysis {
+        let mut expected_at = HashMap::new();
+
+        // Identificar tipo de cada token
+        let token_types = self.classify_sentence(tokens);
+
+        let (clauses, components, sentence_type, issues) = self.structure(tokens, &token_types);
+        let mood = mood::detect_mood(tokens, &token_types);
 
         // Calcular score de validez
-        let validity_score = self.calculate_validity(&token_types, &components, &sentence_type);
+        let validity_score = self.calculate_validity(tokens, &token_types, &components, &sentence_type, &issues);
 
         // Determinar qué se espera en cada posición
         self.infer_expectations(&token_types, &mut expected_at);
 
         GrammarAnalysis {
+            tokens: self.tagged_tokens(tokens, &token_types),
             structure: GrammaticalStructure {
                 sentence_type,
+                mood,
                 components,
                 inferred_theme: None,  // Se llenará con semántica
             },
+            clauses,
             validity_score,
             issues,
             expected_at,
         }
     }
 
-    /// Clasifica un token individual
-    fn classify_token(&self, token: &str) -> TokenType {
+    /// Cláusulas, sintagmas, tipo de oración y problemas de concordancia
+    fn structure(
+        &self,
+        tokens: &[String],
+        token_types: &[TokenType],
+    ) -> (ClauseTree, Vec<GrammaticalComponent>, SentenceType, Vec<GrammarIssue>) {
+        // Segmentar en cláusulas y agrupar cada una en sintagmas (SN, SP, SV)
+        let clauses = clauses::analyze_clauses(tokens, token_types);
+        let mut components: Vec<GrammaticalComponent> = clauses.clauses.iter()
+            .flat_map(|c| c.components.iter().cloned())
+            .collect();
+        components.sort_by_key(|c| c.tokens.first().copied());
+
+        // El tipo de oración lo fija la principal
+        let main_components = clauses.main().map(|c| c.components.as_slice()).unwrap_or_default();
+        let sentence_type = self.determine_sentence_type(token_types, main_components);
+
+        // Concordancia de género, número y persona dentro de cada cláusula; uso de clíticos
+        let mut issues = Vec::new();
+        for clause in &clauses.clauses {
+            let sub_tokens: Vec<String> = clause.tokens.iter().map(|&i| tokens[i].clone()).collect();
+            let sub_types: Vec<TokenType> = clause.tokens.iter().map(|&i| token_types[i].clone()).collect();
+            issues.extend(agreement::check_agreement(&sub_tokens, &sub_types, &self.inflector)
+                .into_iter()
+                .map(|issue| GrammarIssue { position: clause.tokens[issue.position], ..issue }));
+        }
+        issues.extend(clitics::check_leismo(tokens, token_types, self.region));
+        issues.sort_by_key(|issue| issue.position);
+
+        (clauses, components, sentence_type, issues)
+    }
+
+    /// Score de validez sin construir el análisis completo (etiquetas, modo)
+    fn validity_of(&self, tokens: &[String], token_types: &[TokenType]) -> f64 {
+        let (_, components, sentence_type, issues) = self.structure(tokens, token_types);
+        self.calculate_validity(tokens, token_types, &components, &sentence_type, &issues)
+    }
+
+    /// Segmenta una oración en cláusulas con sus componentes
+    pub fn clauses(&self, tokens: &[String]) -> ClauseTree {
+        let types = self.classify_sentence(tokens);
+        clauses::analyze_clauses(tokens, &types)
+    }
+
+    /// Clasifica una oración: forma, lema, categoría y rasgos de cada palabra
+    pub fn tag(&self, tokens: &[String]) -> Vec<TaggedToken> {
+        let types = self.classify_sentence(tokens);
+        self.tagged_tokens(tokens, &types)
+    }
+
+    fn tagged_tokens(&self, tokens: &[String], types: &[TokenType]) -> Vec<TaggedToken> {
+        (0..tokens.len()).map(|i| {
+            let pos = token_upos(tokens, types, i);
+            TaggedToken {
+                text: tokens[i].clone(),
+                lemma: self.lemma(&tokens[i], &types[i], pos),
+                pos,
+                features: self.features(&tokens[i], &types[i]),
+            }
+        }).collect()
+    }
+
+    /// Lema según la clase léxica
+    pub(super) fn lemma(&self, token: &str, tt: &TokenType, upos: Upos) -> String {
         let lower = token.to_lowercase();
+        match tt {
+            TokenType::Verb(info) => info.infinitive.clone(),
+            TokenType::Noun(_) => self.inflector.analyze_noun(&lower).lemma,
+            TokenType::Adjective => self.inflector.analyze_adjective(&lower).lemma,
+            TokenType::Article(info) => if info.definite { "el".to_string() } else { "uno".to_string() },
+            _ if upos == Upos::Propn => token.to_string(),
+            _ => lower,
+        }
+    }
+
+    /// Rasgos morfológicos según la información léxica
+    pub(super) fn features(&self, token: &str, tt: &TokenType) -> MorphFeatures {
+        match tt {
+            TokenType::Article(info) => MorphFeatures {
+                gender: Some(info.gender.clone()),
+                number: Some(info.number.clone()),
+                ..Default::default()
+            },
+            TokenType::Noun(info) => MorphFeatures {
+                gender: Some(info.gender.clone()),
+                number: Some(info.number.clone()),
+                ..Default::default()
+            },
+            TokenType::Pronoun(info) => MorphFeatures {
+                person: Some(info.person.clone()),
+                number: Some(info.number.clone()),
+                ..Default::default()
+            },
+            TokenType::Verb(info) => match info.conjugations.get(&token.to_lowercase()) {
+                Some(c) => MorphFeatures {
+                    person: Some(c.person.clone()),
+                    number: Some(c.number.clone()),
+                    tense: Some(c.tense.clone()),
+                    ..Default::default()
+                },
+                None => MorphFeatures::default(),
+            },
+            TokenType::Adjective => {
+                let analysis = self.inflector.analyze_adjective(&token.to_lowercase());
+                MorphFeatures {
+                    // Neutral: adjetivo invariable en género ("azul")
+                    gender: Some(analysis.gender).filter(|g| *g != Gender::Neutral),
+                    number: Some(analysis.number),
+                    ..Default::default()
+                }
+            }
+            _ => MorphFeatures::default(),
+        }
+    }
+
+    /// Clasifica los tokens de una oración: léxico, corregido por el etiquetador si hay uno
+    pub(crate) fn classify_sentence(&self, tokens: &[String]) -> Vec<TokenType> {
+        let lexical = tokens.iter().map(|t| self.classify_token(t));
+
+        let mut types: Vec<TokenType> = match &self.tagger {
+            Some(tagger) => tagger.tag(tokens)
+                .into_iter()
+                .zip(lexical)
+                .zip(tokens)
+                .map(|((tag, tt), token)| self.resolve_tag(token, tt, tag))
+                .collect(),
+            None => lexical.collect(),
+        };
+
+        clitics::resolve_clitics(self, tokens, &mut types);
+        types
+    }
+
+    /// Concilia la clase léxica con la etiqueta UPOS del contexto.
+    /// Si son compatibles se conserva la léxica (tiene más información)
+    fn resolve_tag(&self, token: &str, lexical: TokenType, tag: Upos) -> TokenType {
+        let lower = token.to_lowercase();
+        let compatible = matches!(
+            (&lexical, tag),
+            (TokenType::Verb(_), Upos::Verb | Upos::Aux)
+                | (TokenType::Noun(_), Upos::Noun | Upos::Propn)
+                | (TokenType::Article(_), Upos::Det)
+                | (TokenType::Adjective, Upos::Adj)
+                | (TokenType::Preposition, Upos::Adp)
+                | (TokenType::Pronoun(_), Upos::Pron)
+                | (TokenType::Adverb, Upos::Adv)
+                | (TokenType::Conjunction, Upos::Cconj | Upos::Sconj)
+        );
+        if compatible {
+            return lexical;
+        }
+
+        match tag {
+            Upos::Verb | Upos::Aux => {
+                let verb = self.verbs.values().find(|v| v.conjugations.contains_key(&lower));
+                TokenType::Verb(verb.cloned().unwrap_or_else(|| tagger::guess_verb(&lower)))
+            }
+            Upos::Noun | Upos::Propn => {
+                let category = if tag == Upos::Propn { NounCategory::Person } else { NounCategory::Thing };
+                TokenType::Noun(self.nouns.get(&lower).cloned()
+                    .unwrap_or_else(|| self.inflector.noun_info(&lower, category)))
+            }
+            Upos::Det => match self.articles.get(&lower) {
+                Some(info) => TokenType::Article(info.clone()),
+                None => {
+                    let analysis = self.inflector.analyze_adjective(&lower);
+                    TokenType::Article(ArticleInfo { definite: true, gender: analysis.gender, number: analysis.number })
+                }
+            },
+            Upos::Pron => TokenType::Pronoun(self.pronouns.get(&lower).cloned()
+                .or_else(|| clitics::object_clitic(&lower))
+                .unwrap_or(PronounInfo { person: Person::Third, number: Number::Singular, case: PronounCase::Subject })),
+            Upos::Adj => TokenType::Adjective,
+            Upos::Adp => TokenType::Preposition,
+            Upos::Adv => TokenType::Adverb,
+            Upos::Cconj | Upos::Sconj => TokenType::Conjunction,
+            _ => lexical,
+        }
+    }
+
+    /// Clasifica un token individual (sin contexto)
+    pub fn classify_token(&self, token: &str) -> TokenType {
+        // Signos de interrogación/exclamación pegados: "¿Dónde", "corre!"
+        let lower = token.trim_matches(['¿', '?', '¡', '!']).to_lowercase();
 
         // Verificar en orden de especificidad
         if let Some(info) = self.articles.get(&lower) {
@@ -557,7 +940,7 @@ impl SpanishGrammar {
         }
 
         // Buscar si es conjugación de algún verbo
-        for (_, verb_info) in &self.verbs {
+        for verb_info in self.verbs.values() {
             if verb_info.conjugations.contains_key(&lower) {
                 return TokenType::Verb(verb_info.clone());
             }
@@ -571,45 +954,59 @@ impl SpanishGrammar {
             return TokenType::Noun(info.clone());
         }
 
+        // Verbo con enclíticos: "dámelo", "visitarlo"
+        if let Some(info) = clitics::enclitic_verb(self, &lower) {
+            return TokenType::Verb(info);
+        }
+
         // Por defecto, asumir sustantivo desconocido
         // (podría ser un nombre propio u otra palabra)
         TokenType::Unknown
     }
 
     /// Determina el tipo de oración basado en orden de componentes
-    fn determine_sentence_type(&self, types: &[TokenType], verb_pos: &[usize]) -> SentenceType {
-        if verb_pos.is_empty() {
-            return SentenceType::Unknown;
-        }
+    fn determine_sentence_type(&self, types: &[TokenType], components: &[GrammaticalComponent]) -> SentenceType {
+        let verb_idx = match components.iter().position(|c| c.role == GrammaticalRole::Verb) {
+            Some(v) => v,
+            None => return SentenceType::Unknown,
+        };
 
-        let first_verb = verb_pos[0];
+        let before = &components[..verb_idx];
+        let after = &components[verb_idx + 1..];
 
-        // Buscar sujeto (sustantivo o pronombre antes del verbo)
-        let has_subject_before = types[..first_verb].iter().any(|t| {
-            matches!(t, TokenType::Noun(_) | TokenType::Pronoun(_))
-        });
+        // Buscar sujeto (sintagma nominal antes del verbo)
+        let has_subject_before = before.iter().any(|c| c.role == GrammaticalRole::Subject);
 
-        // Buscar objeto después del verbo
-        let has_object_after = types[first_verb..].iter().skip(1).any(|t| {
-            matches!(t, TokenType::Noun(_))
+        // Buscar objeto (o sujeto pospuesto) después del verbo
+        let has_object_after = after.iter().any(|c| {
+            matches!(c.role, GrammaticalRole::DirectObject | GrammaticalRole::Subject)
         });
 
-        // Verbo especial tipo "gustar" con pronombre antes
-        let has_dative_pronoun_before = types[..first_verb].iter().any(|t| {
-            if let TokenType::Pronoun(info) = t {
-                info.case == PronounCase::IndirectObj || info.case == PronounCase::DirectObj
-            } else {
-                false
-            }
+        // "Se vive bien": "se" impersonal, sin sujeto
+        let vp_case = |case: PronounCase| components[verb_idx].tokens.iter().any(|&t| {
+            matches!(&types[t], TokenType::Pronoun(info) if info.case == case)
         });
+        if vp_case(PronounCase::Impersonal) {
+            return SentenceType::Impersonal;
+        }
+
+        // Verbo especial tipo "gustar" con pronombre en el sintagma verbal,
+        // o pasiva refleja ("se venden casas"): el sujeto va pospuesto
+        let has_dative_pronoun = vp_case(PronounCase::IndirectObj)
+            || vp_case(PronounCase::DirectObj)
+            || vp_case(PronounCase::Passive);
 
-        if has_dative_pronoun_before && !has_subject_before {
+        if has_dative_pronoun && !has_subject_before {
             // "Me gusta X" - el sujeto está después
             return SentenceType::VSO;
         }
 
-        if first_verb == 0 {
-            // Verbo al inicio
+        // Verbo inicial: ningún sintagma nominal antes (complementos sí)
+        let verb_initial = !before.iter().any(|c| {
+            matches!(c.role, GrammaticalRole::Subject | GrammaticalRole::DirectObject)
+        });
+
+        if verb_initial {
             if has_object_after {
                 SentenceType::VSO
             } else {
@@ -630,9 +1027,11 @@ impl SpanishGrammar {
     /// Calcula score de validez gramatical
     fn calculate_validity(
         &self,
-        _types: &[TokenType],
+        tokens: &[String],
+        types: &[TokenType],
         components: &[GrammaticalComponent],
         sentence_type: &SentenceType,
+        issues: &[GrammarIssue],
     ) -> f64 {
         let mut score: f64 = 0.5;  // Base
 
@@ -651,35 +1050,55 @@ impl SpanishGrammar {
             score += 0.1;
         }
 
-        // +0.05 por concordancia artículo-sustantivo (simplificado)
-        // TODO: verificar género y número
+        // +0.05 por concordancia artículo-sustantivo verificada
+        let has_article_noun = types.windows(2).any(|w| {
+            matches!((&w[0], &w[1]), (TokenType::Article(_), TokenType::Noun(_)))
+        });
+        if has_article_noun && issues.is_empty() {
+            score += 0.05;
+        }
+
+        // -0.3 por artículo seguido de conjunción coordinante ("el sino"):
+        // ningún problema de concordancia se arregla así
+        let article_conjunction = types.windows(2).zip(tokens.windows(2)).any(|(w, t)| {
+            matches!((&w[0], &w[1]), (TokenType::Article(_), TokenType::Conjunction))
+                && is_coordinating(&t[1])
+        });
+        if article_conjunction {
+            score -= 0.3;
+        }
 
-        score.min(1.0)
+        // Penalización por cada problema detectado
+        for issue in issues {
+            score -= match issue.severity {
+                IssueSeverity::Error => 0.15,
+                IssueSeverity::Warning => 0.05,
+                IssueSeverity::Info => 0.0,
+            };
+        }
+
+        score.clamp(0.0, 1.0)
     }
 
     /// Infiere qué se espera en cada posición
     fn infer_expectations(&self, types: &[TokenType], expected: &mut HashMap<usize, ExpectedWord>) {
         for (i, tt) in types.iter().enumerate() {
             match tt {
-                TokenType::Preposition => {
-                    // Después de preposición se espera sintagma nominal
-                    if i + 1 < types.len() {
-                        expected.insert(i + 1, ExpectedWord {
-                            roles: vec![GrammaticalRole::DirectObject],
-                            categories: vec!["lugar".to_string(), "cosa".to_string(), "persona".to_string()],
-                            required: true,
-                        });
-                    }
+                // Después de preposición se espera sintagma nominal
+                TokenType::Preposition if i + 1 < types.len() => {
+                    expected.insert(i + 1, ExpectedWord {
+                        roles: vec![GrammaticalRole::DirectObject],
+                        categories: vec!["lugar".to_string(), "cosa".to_string(), "persona".to_string()],
+                        required: true,
+                    });
                 }
-                TokenType::Article(_) => {
-                    // Después de artículo se espera sustantivo o adjetivo
-                    if i + 1 < types.len() {
-                        expected.insert(i + 1, ExpectedWord {
-                            roles: vec![GrammaticalRole::Subject, GrammaticalRole::DirectObject],
-                            categories: vec!["sustantivo".to_string(), "adjetivo".to_string()],
-                            required: true,
-                        });
-                    }
+                // Después de artículo se espera sustantivo o adjetivo
+                TokenType::Article(_) if i + 1 < types.len() => {
+                    expected.insert(i + 1, ExpectedWord {
+                        roles: vec![GrammaticalRole::Subject, GrammaticalRole::DirectObject],
+                        categories: vec!["sustantivo".to_string(), "adjetivo".to_string()],
+                        required: true,
+                    });
                 }
                 _ => {}
             }
@@ -693,42 +1112,93 @@ impl SpanishGrammar {
         position: usize,
         sentence: &[String],
     ) -> f64 {
-        // Analizar oración con la palabra
-        let mut test_sentence = sentence.to_vec();
+        // Analizar la ventana alrededor de la posición con la palabra
+        let (start, end) = validity_window(position, sentence.len());
+        let mut test_sentence = sentence[start..end].to_vec();
+        let position = position - start;
         if position < test_sentence.len() {
             test_sentence[position] = word.to_string();
         } else {
             test_sentence.push(word.to_string());
         }
 
-        let analysis = self.analyze(&test_sentence);
+        let token_types = self.classify_sentence(&test_sentence);
+        self.validity_of(&test_sentence, &token_types)
+            + self.expectation_bonus(&token_types, position, &self.classify_token(word))
+    }
 
-        // Verificar si la posición tiene expectativas
-        if let Some(expected) = analysis.expected_at.get(&position) {
-            let word_type = self.classify_token(word);
+    /// +0.1 si la posición tiene expectativas y el tipo de palabra coincide
+    fn expectation_bonus(&self, token_types: &[TokenType], position: usize, word_type: &TokenType) -> f64 {
+        let mut expected_at = HashMap::new();
+        self.infer_expectations(token_types, &mut expected_at);
+        let Some(expected) = expected_at.get(&position) else {
+            return 0.0;
+        };
 
-            // Verificar si el tipo de palabra coincide con lo esperado
-            let matches_role = match &word_type {
-                TokenType::Noun(_) => expected.roles.iter().any(|r|
-                    *r == GrammaticalRole::Subject || *r == GrammaticalRole::DirectObject
-                ),
-                TokenType::Adjective => expected.roles.contains(&GrammaticalRole::Adjective),
-                _ => false,
-            };
+        let matches_role = match word_type {
+            TokenType::Noun(_) => expected.roles.iter().any(|r|
+                *r == GrammaticalRole::Subject || *r == GrammaticalRole::DirectObject
+            ),
+            TokenType::Adjective => expected.roles.contains(&GrammaticalRole::Adjective),
+            _ => false,
+        };
 
-            if matches_role {
-                return analysis.validity_score + 0.1;
-            }
+        if matches_role { 0.1 } else { 0.0 }
+    }
+
+    /// Como `is_valid_at_position`, pero si la palabra es desconocida para
+    /// el léxico se evalúa en su mejor lectura (sustantivo, verbo o
+    /// adjetivo). Así una palabra correcta que falta en el léxico no pierde
+    /// contra cualquier palabra conocida que encaje en la posición.
+    pub fn best_reading_validity(&self, word: &str, position: usize, sentence: &[String]) -> f64 {
+        let as_is = self.is_valid_at_position(word, position, sentence);
+        if !matches!(self.classify_token(word), TokenType::Unknown) {
+            return as_is;
         }
 
-        analysis.validity_score
+        let (start, end) = validity_window(position, sentence.len());
+        let mut sentence = sentence[start..end].to_vec();
+        let position = position - start;
+        if position < sentence.len() {
+            sentence[position] = word.to_string();
+        } else {
+            sentence.push(word.to_string());
+        }
+
+        let lower = word.to_lowercase();
+        let mut token_types = self.classify_sentence(&sentence);
+        let mut readings = vec![
+            TokenType::Noun(self.inflector.noun_info(&lower, NounCategory::Thing)),
+            TokenType::Adjective,
+        ];
+        if tagger::has_verb_ending(&lower) {
+            readings.push(TokenType::Verb(tagger::guess_verb(&lower)));
+        }
+
+        readings.into_iter()
+            .map(|reading| {
+                let bonus = self.expectation_bonus(&token_types, position, &reading);
+                token_types[position] = reading;
+                self.validity_of(&sentence, &token_types) + bonus
+            })
+            .fold(as_is, f64::max)
     }
 }
 
-/// Tipo de token identificado (campos usados en análisis futuro)
+/// Tokens a cada lado de la posición evaluada por `is_valid_at_position`:
+/// la validez es local y así el coste no crece con la longitud del texto
+const VALIDITY_WINDOW: usize = 10;
+
+/// Ventana [inicio, fin) alrededor de `position`
+fn validity_window(position: usize, len: usize) -> (usize, usize) {
+    let start = position.saturating_sub(VALIDITY_WINDOW).min(len);
+    let end = (position + VALIDITY_WINDOW + 1).min(len);
+    (start, end)
+}
+
+/// Clase léxica de un token con su información gramatical
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
-enum TokenType {
+pub enum TokenType {
     Verb(VerbInfo),
     Noun(NounInfo),
     Article(ArticleInfo),
@@ -740,6 +1210,29 @@ enum TokenType {
     Unknown,
 }
 
+/// Categoría universal según la clase léxica (y la forma, para desconocidos)
+pub(super) fn token_upos(tokens: &[String], types: &[TokenType], i: usize) -> Upos {
+    match &types[i] {
+        TokenType::Verb(_) => Upos::Verb,
+        TokenType::Noun(_) => Upos::Noun,
+        TokenType::Article(_) => Upos::Det,
+        TokenType::Adjective => Upos::Adj,
+        TokenType::Preposition => Upos::Adp,
+        TokenType::Pronoun(_) => Upos::Pron,
+        TokenType::Adverb => Upos::Adv,
+        TokenType::Conjunction if is_coordinating(&tokens[i]) => Upos::Cconj,
+        TokenType::Conjunction => Upos::Sconj,
+        TokenType::Unknown if !tokens[i].chars().any(|c| c.is_alphanumeric()) => Upos::Punct,
+        TokenType::Unknown if tokens[i].chars().all(|c| c.is_ascii_digit()) => Upos::Num,
+        TokenType::Unknown if i > 0 && tokens[i].starts_with(char::is_uppercase) => Upos::Propn,
+        TokenType::Unknown => Upos::X,
+    }
+}
+
+pub(super) fn is_coordinating(token: &str) -> bool {
+    matches!(token.to_lowercase().as_str(), "y" | "e" | "o" | "u" | "pero" | "sino")
+}
+
 impl Default for SpanishGrammar {
     fn default() -> Self {
         Self::new()
@@ -808,4 +1301,84 @@ mod tests {
         // Debería reconocer estructura válida
         assert!(analysis1.validity_score > 0.5);
     }
+
+    #[test]
+    fn test_agreement_ranks_candidates() {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("coliseo", NounCategory::Place);
+
+        let sentence: Vec<String> = vec!["visité", "xl", "coliseo"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        // "el coliseo" debe superar a "la coliseo"
+        let el = grammar.is_valid_at_position("el", 1, &sentence);
+        let la = grammar.is_valid_at_position("la", 1, &sentence);
+        assert!(el > la);
+    }
+
+    #[test]
+    fn test_conjunction_after_article() {
+        let grammar = SpanishGrammar::new();
+        let sentence: Vec<String> = "el niño juega en la casa de Rosita"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        let nino = grammar.best_reading_validity("niño", 1, &sentence);
+        let sino = grammar.best_reading_validity("sino", 1, &sentence);
+        assert!(nino > sino);
+    }
+
+    #[test]
+    fn test_phrase_components() {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar.add_adjective_forms("azul");
+
+        let tokens: Vec<String> = vec!["la", "casa", "azul", "de", "Rosita", "me", "gusta"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let analysis = grammar.analyze(&tokens);
+        let components = &analysis.structure.components;
+
+        // Un sintagma nominal sujeto y un sintagma verbal
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].role, GrammaticalRole::Subject);
+        assert_eq!(components[0].tokens, vec![0, 1, 2, 3, 4]);
+        assert_eq!(components[0].head, Some(1));
+        assert_eq!(components[1].role, GrammaticalRole::Verb);
+        assert_eq!(analysis.structure.sentence_type, SentenceType::SV);
+    }
+
+    #[test]
+    fn test_tagged_tokens() {
+        let mut grammar = SpanishGrammar::new();
+        grammar.add_noun_forms("casa", NounCategory::Thing);
+        grammar.add_adjective_forms("romano");
+
+        let tokens: Vec<String> = vec!["Las", "casas", "romanas", "me", "gustan"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let tagged = grammar.analyze(&tokens).tokens;
+
+        assert_eq!(tagged.len(), 5);
+        assert_eq!(tagged[0].text, "Las");
+        assert_eq!(tagged[0].pos, Upos::Det);
+        assert_eq!(tagged[0].features.gender, Some(Gender::Feminine));
+        assert_eq!(tagged[1].lemma, "casa");
+        assert_eq!(tagged[1].features.number, Some(Number::Plural));
+        assert_eq!(tagged[2].lemma, "romano");
+        assert_eq!(tagged[2].features.to_ud_string(), "Gender=Fem|Number=Plur");
+        assert_eq!(tagged[3].features.person, Some(Person::First));
+        assert_eq!(tagged[4].lemma, "gustar");
+        assert_eq!(tagged[4].features.to_ud_string(), "Mood=Ind|Number=Plur|Person=3|Tense=Pres");
+
+        // Clasificación pública de una palabra suelta
+        assert!(matches!(grammar.classify_token("gustan"), TokenType::Verb(_)));
+    }
 }
@@ -0,0 +1,184 @@
+//! # Mood Module
+//!
+//! Modalidad de la oración, independiente del orden de palabras:
+//! - Interrogativa parcial: "¿Dónde está el Coliseo?", "por qué llueve"
+//! - Interrogativa total: "¿Visitaste Roma?"
+//! - Exclamativa: "¡Qué bonito!"
+//! - Imperativa: "Dámelo", "Ven aquí", "No me lo digas"
+//!
+//! Los signos pueden venir como tokens sueltos ("¿", "?") o pegados a la
+//! palabra ("¿Dónde"). Sin signos, un interrogativo con tilde al inicio
+//! basta para reconocer la pregunta ("qué es el propofol").
+
+use crate::tao::{Interrogative, SentenceMood};
+use super::clitics::split_enclitics;
+use super::{Person, PronounCase, Tense, TokenType};
+
+/// Imperativos irregulares de tú ("ven", "haz", "di")
+const IRREGULAR_IMPERATIVES: [&str; 8] = ["ven", "di", "haz", "pon", "sal", "ten", "ve", "sé"];
+
+const QUESTION_MARKS: [char; 2] = ['¿', '?'];
+const EXCLAMATION_MARKS: [char; 2] = ['¡', '!'];
+
+/// Interrogativo al inicio de la oración, tras una preposición opcional
+/// ("¿de dónde...?", "¿a quién...?"). "por qué" es `Why`
+pub fn leading_interrogative(tokens: &[String]) -> Option<Interrogative> {
+    let words: Vec<String> = tokens.iter()
+        .map(|t| strip_marks(t).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let first = words.first()?;
+    if let Some(kind) = Interrogative::from_word(first) {
+        return Some(kind);
+    }
+
+    let second = Interrogative::from_word(words.get(1)?)?;
+    match (first.as_str(), second) {
+        ("por", Interrogative::What) => Some(Interrogative::Why),
+        ("a" | "ante" | "con" | "contra" | "de" | "desde" | "en" | "hacia" | "hasta" | "para" | "por" | "sin" | "sobre", kind) => Some(kind),
+        _ => None,
+    }
+}
+
+/// ¿Algún token abre o cierra con signos de interrogación? Un "?" en medio
+/// del token ("x.com/a?b=c") no cuenta
+pub fn has_question_marks(tokens: &[String]) -> bool {
+    tokens.iter().any(|t| {
+        let t = t.trim_matches(EXCLAMATION_MARKS);
+        t.starts_with(QUESTION_MARKS[0]) || t.ends_with(QUESTION_MARKS[1])
+    })
+}
+
+/// Modalidad de una oración clasificada
+pub(crate) fn detect_mood(tokens: &[String], types: &[TokenType]) -> SentenceMood {
+    let question = has_question_marks(tokens);
+    let exclamation = tokens.iter().any(|t| t.contains(EXCLAMATION_MARKS));
+
+    // 1. Preguntas (sin signos, solo con interrogativo; "¡qué bonito!" es exclamativa)
+    match leading_interrogative(tokens) {
+        Some(kind) if question || !exclamation => return SentenceMood::Question(kind),
+        _ if question => return SentenceMood::YesNoQuestion,
+        _ => {}
+    }
+
+    // 2. Imperativo
+    if is_imperative(tokens, types, exclamation) {
+        return SentenceMood::Imperative;
+    }
+
+    if exclamation {
+        SentenceMood::Exclamative
+    } else {
+        SentenceMood::Declarative
+    }
+}
+
+/// Verbo inicial en imperativo: con enclíticos, irregular de tú,
+/// subjuntivo negado ("no lo digas") o forma regular entre signos de exclamación
+fn is_imperative(tokens: &[String], types: &[TokenType], exclamation: bool) -> bool {
+    let words: Vec<usize> = (0..tokens.len()).filter(|&i| !strip_marks(&tokens[i]).is_empty()).collect();
+    let first = match words.first() {
+        Some(&i) => i,
+        None => return false,
+    };
+
+    let first_word = strip_marks(&tokens[first]).to_lowercase();
+    if IRREGULAR_IMPERATIVES.contains(&first_word.as_str()) {
+        return true;
+    }
+
+    // Negación y clíticos antes del verbo
+    let negated = first_word == "no";
+    let verb = words.iter().copied().find(|&i| {
+        let is_clitic = matches!(&types[i], TokenType::Pronoun(info) if info.case != PronounCase::Subject);
+        !(is_clitic || (i == first && negated))
+    });
+    let verb = match verb {
+        Some(v) => v,
+        None => return false,
+    };
+    let info = match &types[verb] {
+        TokenType::Verb(info) => info,
+        _ => return false,
+    };
+    let word = strip_marks(&tokens[verb]).to_lowercase();
+    let conjugation = info.conjugations.get(&word);
+
+    // "no me lo digas": subjuntivo de 2ª persona negado
+    if negated {
+        return conjugation.is_some_and(|c| c.tense == Tense::Subjunctive && c.person == Person::Second);
+    }
+
+    // Los clíticos antepuestos descartan el imperativo afirmativo ("me lo dijo")
+    if verb != first {
+        return false;
+    }
+
+    // "dámelo", "ayúdame": enclíticos en forma personal (no infinitivo ni gerundio)
+    if let Some((base, _)) = split_enclitics(&word) {
+        let non_finite = ["ar", "er", "ir", "ndo"].iter().any(|e| base.ends_with(e));
+        if !non_finite && conjugation.is_some() {
+            return true;
+        }
+    }
+
+    // "¡Corre!": 3ª singular de presente = imperativo de tú, solo con exclamación
+    exclamation && conjugation.is_some_and(|c| {
+        c.person == Person::Third && c.tense == Tense::Present && c.number == super::Number::Singular
+    })
+}
+
+fn strip_marks(token: &str) -> &str {
+    token.trim_matches(|c: char| QUESTION_MARKS.contains(&c) || EXCLAMATION_MARKS.contains(&c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::SpanishGrammar;
+
+    fn mood(grammar: &SpanishGrammar, sentence: &str) -> SentenceMood {
+        let tokens: Vec<String> = sentence.split_whitespace().map(String::from).collect();
+        grammar.analyze(&tokens).structure.mood
+    }
+
+    #[test]
+    fn test_questions() {
+        let grammar = SpanishGrammar::new();
+
+        assert_eq!(mood(&grammar, "¿Dónde está el coliseo?"), SentenceMood::Question(Interrogative::Where));
+        assert_eq!(mood(&grammar, "¿ Quién es ?"), SentenceMood::Question(Interrogative::Who));
+        assert_eq!(mood(&grammar, "por qué corres"), SentenceMood::Question(Interrogative::Why));
+        assert_eq!(mood(&grammar, "¿De dónde eres?"), SentenceMood::Question(Interrogative::Where));
+        assert_eq!(mood(&grammar, "¿Visitaste Roma?"), SentenceMood::YesNoQuestion);
+        // "donde" sin tilde es relativo
+        assert_eq!(mood(&grammar, "donde vas corro"), SentenceMood::Declarative);
+    }
+
+    #[test]
+    fn test_exclamative_and_imperative() {
+        let grammar = SpanishGrammar::new();
+
+        assert_eq!(mood(&grammar, "¡Qué bonito!"), SentenceMood::Exclamative);
+        assert_eq!(mood(&grammar, "Dámelo"), SentenceMood::Imperative);
+        assert_eq!(mood(&grammar, "Ven aquí"), SentenceMood::Imperative);
+        assert_eq!(mood(&grammar, "¡Corre!"), SentenceMood::Imperative);
+        // Sin exclamación, "corre mucho" es enunciativa (sujeto tácito)
+        assert_eq!(mood(&grammar, "corre mucho"), SentenceMood::Declarative);
+        assert_eq!(mood(&grammar, "Me lo dijo"), SentenceMood::Declarative);
+        assert_eq!(mood(&grammar, "visitarlo es fácil"), SentenceMood::Declarative);
+        assert_eq!(mood(&grammar, "No me lo digas"), SentenceMood::Imperative);
+    }
+
+    #[test]
+    fn test_marks_only_at_edges() {
+        let grammar = SpanishGrammar::new();
+        let tokens = |s: &str| -> Vec<String> { s.split_whitespace().map(String::from).collect() };
+
+        assert!(has_question_marks(&tokens("¿Visitaste Roma?")));
+        assert!(has_question_marks(&tokens("¡¿En serio?!")));
+        assert!(!has_question_marks(&tokens("mira https://x.com/a?b=c")));
+        assert_eq!(mood(&grammar, "mira https://x.com/a?b=c"), SentenceMood::Declarative);
+    }
+}
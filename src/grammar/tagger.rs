@@ -0,0 +1,407 @@
+//! # POS Tagger Module
+//!
+//! Etiquetador morfosintáctico con perceptrón promediado, sin dependencias.
+//! Se entrena con un treebank CoNLL-U (UD Spanish-AnCora) y desambigua por
+//! contexto lo que el léxico no puede:
+//! - "la casa" (DET) / "la veo" (PRON)
+//! - "como pan" (VERB) / "como tú" (SCONJ)
+//!
+//! Las palabras desconocidas se etiquetan por sus sufijos y prefijos
+//! ("-mente" → ADV, "-ción" → NOUN). El entrenamiento es determinista:
+//! mismo corpus y mismas iteraciones producen el mismo modelo.
+
+use std::collections::HashMap;
+use std::path::Path;
+use super::conllu::{read_conllu_file, ConlluError, ConlluSentence, Upos};
+use super::{Conjugation, Number, Person, Tense, VerbCategory, VerbInfo};
+
+/// Número de categorías UPOS
+const CLASSES: usize = Upos::ALL.len();
+
+/// Pesos por categoría para un rasgo
+type Weights = [f64; CLASSES];
+
+/// Etiquetador de perceptrón promediado
+#[derive(Debug, Clone, Default)]
+pub struct PosTagger {
+    /// Rasgo → peso por categoría
+    weights: HashMap<String, Weights>,
+
+    /// Palabras frecuentes y no ambiguas: etiqueta directa
+    tag_dict: HashMap<String, Upos>,
+}
+
+/// Acumuladores para promediar los pesos durante el entrenamiento
+#[derive(Default)]
+struct Averager {
+    totals: HashMap<String, Weights>,
+    timestamps: HashMap<String, [usize; CLASSES]>,
+    instances: usize,
+}
+
+impl PosTagger {
+    /// Entrena con oraciones anotadas
+    pub fn train(sentences: &[ConlluSentence], iterations: usize) -> Self {
+        let mut tagger = Self {
+            weights: HashMap::new(),
+            tag_dict: build_tag_dict(sentences),
+        };
+        let mut averager = Averager::default();
+
+        for _ in 0..iterations {
+            for sentence in sentences {
+                let words: Vec<String> = sentence.tokens.iter().map(|t| t.form.clone()).collect();
+                let mut prev = ("-START-".to_string(), "-START2-".to_string());
+
+                for (i, token) in sentence.tokens.iter().enumerate() {
+                    // Las palabras del diccionario de etiquetas no se entrenan
+                    let guess = match tagger.tag_dict.get(&words[i].to_lowercase()) {
+                        Some(&tag) => tag,
+                        None => {
+                            let features = extract_features(&words, i, &prev.0, &prev.1);
+                            let guess = tagger.predict(&features);
+                            tagger.update(&mut averager, token.upos, guess, &features);
+                            guess
+                        }
+                    };
+                    prev = (guess.as_str().to_string(), prev.0);
+                }
+            }
+        }
+
+        tagger.average(averager);
+        tagger
+    }
+
+    /// Entrena desde un archivo CoNLL-U
+    pub fn train_from_file(path: &Path, iterations: usize) -> Result<Self, ConlluError> {
+        let sentences = read_conllu_file(path)?;
+        Ok(Self::train(&sentences, iterations))
+    }
+
+    /// Etiqueta una oración tokenizada
+    pub fn tag(&self, tokens: &[String]) -> Vec<Upos> {
+        let mut tags = Vec::with_capacity(tokens.len());
+        let mut prev = ("-START-".to_string(), "-START2-".to_string());
+
+        for i in 0..tokens.len() {
+            let tag = match self.tag_dict.get(&tokens[i].to_lowercase()) {
+                Some(&tag) => tag,
+                None => self.predict(&extract_features(tokens, i, &prev.0, &prev.1)),
+            };
+            prev = (tag.as_str().to_string(), prev.0);
+            tags.push(tag);
+        }
+
+        tags
+    }
+
+    /// Precisión contra oraciones anotadas
+    pub fn accuracy(&self, gold: &[ConlluSentence]) -> f64 {
+        let mut total = 0;
+        let mut correct = 0;
+
+        for sentence in gold {
+            let predicted = self.tag(&sentence.forms());
+            for (p, g) in predicted.iter().zip(&sentence.tokens) {
+                total += 1;
+                if *p == g.upos {
+                    correct += 1;
+                }
+            }
+        }
+
+        if total == 0 { 0.0 } else { correct as f64 / total as f64 }
+    }
+
+    /// Número de rasgos aprendidos
+    pub fn feature_count(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Categoría con mayor puntuación (empates: orden de `Upos::ALL`)
+    fn predict(&self, features: &[String]) -> Upos {
+        let mut scores = [0.0; CLASSES];
+        for feature in features {
+            if let Some(w) = self.weights.get(feature) {
+                for (score, weight) in scores.iter_mut().zip(w) {
+                    *score += weight;
+                }
+            }
+        }
+
+        let mut best = 0;
+        for c in 1..CLASSES {
+            if scores[c] > scores[best] {
+                best = c;
+            }
+        }
+        Upos::ALL[best]
+    }
+
+    /// Actualización del perceptrón con acumulación para el promedio
+    fn update(&mut self, averager: &mut Averager, truth: Upos, guess: Upos, features: &[String]) {
+        averager.instances += 1;
+        if truth == guess {
+            return;
+        }
+
+        for feature in features {
+            for (class, delta) in [(truth as usize, 1.0), (guess as usize, -1.0)] {
+                let weights = self.weights.entry(feature.clone()).or_insert([0.0; CLASSES]);
+                let totals = averager.totals.entry(feature.clone()).or_insert([0.0; CLASSES]);
+                let stamps = averager.timestamps.entry(feature.clone()).or_insert([0; CLASSES]);
+
+                totals[class] += (averager.instances - stamps[class]) as f64 * weights[class];
+                stamps[class] = averager.instances;
+                weights[class] += delta;
+            }
+        }
+    }
+
+    /// Reemplaza cada peso por su promedio a lo largo del entrenamiento
+    fn average(&mut self, mut averager: Averager) {
+        let instances = averager.instances.max(1);
+
+        for (feature, weights) in self.weights.iter_mut() {
+            let totals = averager.totals.entry(feature.clone()).or_insert([0.0; CLASSES]);
+            let stamps = averager.timestamps.entry(feature.clone()).or_insert([0; CLASSES]);
+
+            for c in 0..CLASSES {
+                let total = totals[c] + (instances - stamps[c]) as f64 * weights[c];
+                weights[c] = total / instances as f64;
+            }
+        }
+
+        self.weights.retain(|_, w| w.iter().any(|&x| x != 0.0));
+    }
+}
+
+/// Palabras frecuentes (≥ 20) con una etiqueta en ≥ 97% de los casos
+fn build_tag_dict(sentences: &[ConlluSentence]) -> HashMap<String, Upos> {
+    let mut counts: HashMap<String, [usize; CLASSES]> = HashMap::new();
+    for sentence in sentences {
+        for token in &sentence.tokens {
+            counts.entry(token.form.to_lowercase()).or_insert([0; CLASSES])[token.upos as usize] += 1;
+        }
+    }
+
+    counts.into_iter()
+        .filter_map(|(word, c)| {
+            let total: usize = c.iter().sum();
+            let (best, &n) = c.iter().enumerate().max_by_key(|&(i, n)| (n, std::cmp::Reverse(i)))?;
+            (total >= 20 && n as f64 / total as f64 >= 0.97).then_some((word, Upos::ALL[best]))
+        })
+        .collect()
+}
+
+/// Rasgos de la palabra i en contexto (etiquetas previas ya asignadas)
+fn extract_features(words: &[String], i: usize, prev: &str, prev2: &str) -> Vec<String> {
+    let word = &words[i];
+    let lower = word.to_lowercase();
+    let prev_word = if i > 0 { words[i - 1].to_lowercase() } else { "-START-".to_string() };
+    let next_word = words.get(i + 1).map(|w| w.to_lowercase()).unwrap_or_else(|| "-END-".to_string());
+
+    let mut features = vec![
+        "bias".to_string(),
+        format!("w={}", lower),
+        format!("s1={}", suffix(&lower, 1)),
+        format!("s2={}", suffix(&lower, 2)),
+        format!("s3={}", suffix(&lower, 3)),
+        format!("s4={}", suffix(&lower, 4)),
+        format!("p1={}", prefix(&lower, 1)),
+        format!("t-1={}", prev),
+        format!("t-2={}", prev2),
+        format!("t-1,t-2={},{}", prev, prev2),
+        format!("t-1,w={},{}", prev, lower),
+        format!("w-1={}", prev_word),
+        format!("w+1={}", next_word),
+        format!("s3+1={}", suffix(&next_word, 3)),
+    ];
+
+    if i > 0 && word.starts_with(char::is_uppercase) {
+        features.push("mayúscula".to_string());
+    }
+    if word.chars().any(|c| c.is_ascii_digit()) {
+        features.push("dígito".to_string());
+    }
+    if !word.chars().any(|c| c.is_alphanumeric()) {
+        features.push("puntuación".to_string());
+    }
+
+    features
+}
+
+/// Desinencias verbales (desinencia, persona, número, tiempo), de más larga a más corta
+const VERB_ENDINGS: &[(&str, Person, Number, Tense)] = &[
+    ("amos", Person::First, Number::Plural, Tense::Present),
+    ("emos", Person::First, Number::Plural, Tense::Present),
+    ("imos", Person::First, Number::Plural, Tense::Present),
+    ("ió", Person::Third, Number::Singular, Tense::Past),
+    ("an", Person::Third, Number::Plural, Tense::Present),
+    ("en", Person::Third, Number::Plural, Tense::Present),
+    ("as", Person::Second, Number::Singular, Tense::Present),
+    ("es", Person::Second, Number::Singular, Tense::Present),
+    ("é", Person::First, Number::Singular, Tense::Past),
+    ("í", Person::First, Number::Singular, Tense::Past),
+    ("ó", Person::Third, Number::Singular, Tense::Past),
+    ("o", Person::First, Number::Singular, Tense::Present),
+    ("a", Person::Third, Number::Singular, Tense::Present),
+    ("e", Person::Third, Number::Singular, Tense::Present),
+];
+
+/// Indica si la forma termina en una desinencia verbal conocida
+pub(super) fn has_verb_ending(form: &str) -> bool {
+    VERB_ENDINGS.iter().any(|(ending, ..)| form.ends_with(ending) && form.len() > ending.len())
+}
+
+/// Verbo fuera del léxico etiquetado como VERB: persona, número y tiempo
+/// por la desinencia; el infinitivo es aproximado (la vocal temática se pierde en 1ª persona)
+pub(super) fn guess_verb(form: &str) -> VerbInfo {
+    let (stem, conjugation) = VERB_ENDINGS.iter()
+        .find(|(ending, ..)| form.ends_with(ending) && form.len() > ending.len())
+        .map(|(ending, person, number, tense)| (
+            &form[..form.len() - ending.len()],
+            Conjugation { person: person.clone(), number: number.clone(), tense: tense.clone() },
+        ))
+        .unwrap_or((form, Conjugation { person: Person::Third, number: Number::Singular, tense: Tense::Present }));
+
+    // Vocal temática: -e-/-i- → 2ª/3ª conjugación, resto → 1ª
+    let infinitive = if form.ends_with("emos") || form.ends_with("es") || form.ends_with("en")
+        || form.ends_with('e') || form.ends_with("ió") || form.ends_with('í')
+    {
+        format!("{}er", stem)
+    } else {
+        format!("{}ar", stem)
+    };
+
+    let mut conjugations = HashMap::new();
+    conjugations.insert(form.to_string(), conjugation);
+
+    VerbInfo {
+        infinitive,
+        transitive: true,
+        conjugations,
+        category: VerbCategory::Action,
+    }
+}
+
+fn suffix(word: &str, n: usize) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    chars[chars.len().saturating_sub(n)..].iter().collect()
+}
+
+fn prefix(word: &str, n: usize) -> String {
+    word.chars().take(n).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::conllu::parse_conllu;
+
+    /// Corpus mínimo con las ambigüedades la (DET/PRON) y como (VERB/SCONJ)
+    fn corpus() -> Vec<ConlluSentence> {
+        let rows: &[&[(&str, &str)]] = &[
+            &[("la", "DET"), ("casa", "NOUN"), ("es", "AUX"), ("grande", "ADJ")],
+            &[("yo", "PRON"), ("la", "PRON"), ("veo", "VERB"), ("siempre", "ADV")],
+            &[("la", "DET"), ("niña", "NOUN"), ("come", "VERB"), ("pan", "NOUN")],
+            &[("ella", "PRON"), ("la", "PRON"), ("compra", "VERB"), ("hoy", "ADV")],
+            &[("yo", "PRON"), ("como", "VERB"), ("pan", "NOUN")],
+            &[("corre", "VERB"), ("como", "SCONJ"), ("un", "DET"), ("perro", "NOUN")],
+            &[("tú", "PRON"), ("como", "SCONJ"), ("yo", "PRON")],
+            &[("nosotros", "PRON"), ("lo", "PRON"), ("tenemos", "VERB")],
+            &[("la", "DET"), ("canción", "NOUN"), ("suena", "VERB"), ("rápidamente", "ADV")],
+            &[("el", "DET"), ("perro", "NOUN"), ("corre", "VERB"), ("lentamente", "ADV")],
+            &[("la", "DET"), ("nación", "NOUN"), ("canta", "VERB")],
+            &[("ellos", "PRON"), ("la", "PRON"), ("buscan", "VERB")],
+        ];
+
+        let mut text = String::new();
+        for row in rows {
+            for (i, (form, upos)) in row.iter().enumerate() {
+                text.push_str(&format!("{}\t{}\t_\t{}\t_\t_\t0\tdep\t_\t_\n", i + 1, form, upos));
+            }
+            text.push('\n');
+        }
+        parse_conllu(&text).unwrap()
+    }
+
+    fn tokens(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_contextual_disambiguation() {
+        let tagger = PosTagger::train(&corpus(), 10);
+
+        assert_eq!(tagger.tag(&tokens("la casa es grande")), vec![Upos::Det, Upos::Noun, Upos::Aux, Upos::Adj]);
+        assert_eq!(tagger.tag(&tokens("yo la veo"))[1], Upos::Pron);
+        assert_eq!(tagger.tag(&tokens("yo como pan"))[1], Upos::Verb);
+        assert_eq!(tagger.tag(&tokens("tú como yo"))[1], Upos::Sconj);
+    }
+
+    #[test]
+    fn test_unknown_words_by_suffix() {
+        let tagger = PosTagger::train(&corpus(), 10);
+
+        // Palabras nunca vistas: se etiquetan por su terminación
+        let tags = tagger.tag(&tokens("la población canta tranquilamente"));
+        assert_eq!(tags[1], Upos::Noun);
+        assert_eq!(tags[3], Upos::Adv);
+    }
+
+    #[test]
+    fn test_training_is_deterministic() {
+        let a = PosTagger::train(&corpus(), 5);
+        let b = PosTagger::train(&corpus(), 5);
+
+        assert_eq!(a.feature_count(), b.feature_count());
+        for sentence in corpus() {
+            assert_eq!(a.tag(&sentence.forms()), b.tag(&sentence.forms()));
+        }
+        assert!(a.accuracy(&corpus()) > 0.9);
+    }
+
+    #[test]
+    fn test_analyze_uses_tagger() {
+        use crate::grammar::SpanishGrammar;
+        use crate::tao::GrammaticalRole;
+
+        let mut grammar = SpanishGrammar::new();
+        let sentence = tokens("yo la veo");
+
+        // Sin etiquetador, "la" es artículo y "veo" desconocido: no hay verbo
+        let lexical = grammar.analyze(&sentence);
+        assert!(!lexical.structure.components.iter().any(|c| c.role == GrammaticalRole::Verb));
+
+        // Con etiquetador, "la" es clítico dentro del sintagma verbal
+        grammar.set_tagger(PosTagger::train(&corpus(), 10));
+        let tagged = grammar.analyze(&sentence);
+        let verb = tagged.structure.components.iter()
+            .find(|c| c.role == GrammaticalRole::Verb)
+            .expect("sintagma verbal");
+        assert_eq!(verb.tokens, vec![1, 2]);
+        assert_eq!(tagged.structure.components[0].role, GrammaticalRole::Subject);
+
+        // "como" conjunción en el léxico, verbo por contexto
+        let como = grammar.analyze(&tokens("yo como pan"));
+        assert!(como.structure.components.iter()
+            .any(|c| c.role == GrammaticalRole::Verb && c.tokens == vec![1]));
+    }
+
+    #[test]
+    #[ignore = "requiere el treebank UD Spanish-AnCora (NLSRE_UD_DIR)"]
+    fn test_train_from_treebank_file() {
+        // Treebank opcional (no se distribuye con el repo):
+        // NLSRE_UD_DIR=ruta cargo test -- --ignored
+        let dir = std::env::var("NLSRE_UD_DIR").unwrap_or_else(|_| "data/ud".to_string());
+        let train = Path::new(&dir).join("es_ancora-ud-train.conllu");
+        let test = Path::new(&dir).join("es_ancora-ud-test.conllu");
+
+        let tagger = PosTagger::train_from_file(&train, 5).expect("treebank de entrenamiento en NLSRE_UD_DIR");
+        let gold = read_conllu_file(&test).expect("treebank de prueba en NLSRE_UD_DIR");
+        let accuracy = tagger.accuracy(&gold);
+        assert!(accuracy > 0.9, "precisión UPOS {:.3} por debajo del mínimo", accuracy);
+    }
+}
@@ -26,18 +26,35 @@ pub mod disambiguator;
 pub mod chars;
 pub mod dictionary;
 pub mod command_parser;
+pub mod ngram;
+pub mod ner;
+pub mod protect;
+pub mod userdict;
+pub mod feedback;
 
 // Re-exports principales
-pub use disambiguator::SemanticDisambiguator;
+pub use disambiguator::{RequestContext, SemanticDisambiguator};
+pub use disambiguator::model::DisambiguationModel;
+pub use disambiguator::batch::BatchStats;
 pub use uniform::UnifyContext;
 pub use applog::SharedContext;
-pub use grammar::SpanishGrammar;
+pub use grammar::{SpanishGrammar, TaggedToken, TokenType};
+pub use grammar::checker::GrammarChecker;
+pub use grammar::dependency::{DependencyParser, DependencyTree};
+pub use grammar::tagger::PosTagger;
+pub use grammar::lexicon::Lexicon;
 pub use semantic::{SemanticDB, SemanticCategory};
 pub use dictionary::{SpanishDictionary, DictionaryEntry};
 pub use command_parser::{CommandParser, ParsedCommand, ParserStats};
+pub use ngram::NgramModel;
+pub use chars::channel::ErrorModel;
+pub use ner::{Entity, EntityKind, EntityRecognizer, Gazetteer};
+pub use protect::{ProtectedKind, ProtectedSpan, SpanProtector};
+pub use userdict::{DictionaryLayer, LayeredDictionary, UserDictionary, WordAction};
+pub use feedback::{FeedbackEvent, FeedbackStore};
 
 /// Resultado de procesamiento de una oración
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProcessedSentence {
     /// Oración original
     pub original: String,
@@ -47,10 +64,12 @@ pub struct ProcessedSentence {
     pub confidence: f64,
     /// Correcciones individuales aplicadas
     pub corrections: Vec<Correction>,
+    /// Entidades con nombre reconocidas (no se corrigen)
+    pub entities: Vec<Entity>,
 }
 
 /// Una corrección individual
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Correction {
     /// Posición en la oración (índice de token)
     pub position: usize,
@@ -65,7 +84,7 @@ pub struct Correction {
 }
 
 /// Explicación detallada de una corrección
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CorrectionExplanation {
     /// Score de similitud de caracteres
     pub char_score: f64,
@@ -73,6 +92,12 @@ pub struct CorrectionExplanation {
     pub grammar_score: f64,
     /// Score de contexto semántico
     pub context_score: f64,
+    /// Score del modelo de lenguaje (relativo al mejor candidato; 0.0 sin modelo)
+    pub lm_score: f64,
+    /// Prior de frecuencia de uso (0.0 sin datos de frecuencia)
+    pub frequency_score: f64,
+    /// Ajuste por feedback del usuario en (-1, 1) (0.0 sin feedback)
+    pub feedback_score: f64,
     /// Candidatos considerados con sus scores
     pub candidates: Vec<(String, f64)>,
     /// Razón en texto legible
@@ -88,6 +113,12 @@ pub struct Config {
     pub beta: f64,
     /// Peso para contexto semántico (γ)
     pub gamma: f64,
+    /// Peso para el modelo de n-gramas (δ), solo si hay modelo cargado
+    pub delta: f64,
+    /// Peso para el prior de frecuencia (ε), solo si hay frecuencias
+    pub epsilon: f64,
+    /// Tope del ajuste por feedback del usuario (ζ), se suma sin renormalizar
+    pub zeta: f64,
     /// Umbral mínimo de confianza para aceptar corrección
     pub min_confidence: f64,
     /// Número máximo de candidatos a considerar
@@ -100,6 +131,9 @@ impl Default for Config {
             alpha: 0.30,  // 30% peso a caracteres
             beta: 0.30,   // 30% peso a gramática
             gamma: 0.40,  // 40% peso a contexto semántico
+            delta: 0.25,  // se renormaliza con α+β+γ si hay modelo
+            epsilon: 0.10, // ídem, con frecuencias del diccionario
+            zeta: 0.15,   // ±15% como máximo por feedback
             min_confidence: 0.60,
             max_candidates: 10,
         }
@@ -12,15 +12,33 @@
 //! cargo run --release -- --full
 //! ```
 //!
+//! ## Revisor gramatical (sin corregir)
+//! ```
+//! cargo run --release -- revisar "Me gusta la casa azules"
+//! cargo run --release -- revisar --desactivar contraccion < texto.txt
+//! ```
+//!
 //! ## Autor
 //! Francisco Molina-Burgos, Avermex Research Division
 
-use nl_sre_semantico::{SemanticDisambiguator, SpanishDictionary, Config, info, CommandParser};
+use nl_sre_semantico::{SemanticDisambiguator, SpanishDictionary, Config, info, CommandParser, GrammarChecker};
+use nl_sre_semantico::grammar::checker::RULES;
+use nl_sre_semantico::semantic::wordnet::WordNet;
+use nl_sre_semantico::semantic::embeddings::WordVectors;
+use nl_sre_semantico::{NgramModel, ErrorModel};
 use std::env;
 use std::path::Path;
-use std::io::{self, Write};
+use std::io::{self, BufRead, Write};
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    // Subcomando revisor: reporta problemas sin modificar el texto
+    if matches!(args.get(1).map(|a| a.as_str()), Some("revisar") | Some("check")) {
+        run_checker(&args[2..]);
+        return;
+    }
+
     println!("╔══════════════════════════════════════════════════════════════════╗");
     println!("║     NL-SRE-SEMANTICO - Motor de Desambiguación Semántica         ║");
     println!("║     Francisco Molina-Burgos, Avermex Research Division           ║");
@@ -30,7 +48,6 @@ fn main() {
     println!();
 
     // Check for flags
-    let args: Vec<String> = env::args().collect();
     let use_full_dictionary = args.iter().any(|a| a == "--full" || a == "-f");
     let interactive_mode = args.iter().any(|a| a == "--repl" || a == "-i" || a == "--interactive");
 
@@ -147,7 +164,7 @@ fn main() {
         ..Config::default()
     };
 
-    let mut motor_chars = SemanticDisambiguator::with_config(config_chars);
+    let motor_chars = SemanticDisambiguator::with_config(config_chars);
 
     let sentence = "Visité el Coliseo romano en smor";
     let result_chars = motor_chars.process(sentence);
@@ -168,7 +185,7 @@ fn main() {
         ..Config::default()
     };
 
-    let mut motor_context = SemanticDisambiguator::with_config(config_context);
+    let motor_context = SemanticDisambiguator::with_config(config_context);
 
     let result_context = motor_context.process(sentence);
 
@@ -284,10 +301,82 @@ fn main() {
     println!("╚══════════════════════════════════════════════════════════════════╝");
 }
 
-/// Carga el motor con diccionario completo RAE
+/// Revisor gramatical: `revisar [--full] [--desactivar REGLA]... [--reglas] [TEXTO]`.
+/// Sin TEXTO lee líneas de la entrada estándar
+fn run_checker(args: &[String]) {
+    let mut use_full_dictionary = false;
+    let mut disabled = Vec::new();
+    let mut words = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--full" | "-f" => use_full_dictionary = true,
+            "--desactivar" | "--disable" => {
+                if let Some(rule) = iter.next() {
+                    disabled.push(rule.clone());
+                }
+            }
+            "--reglas" | "--rules" => {
+                for rule in RULES {
+                    println!("{:<28} {}", rule.id, rule.description);
+                }
+                return;
+            }
+            _ => words.push(arg.clone()),
+        }
+    }
+
+    let motor = if use_full_dictionary {
+        load_full_motor()
+    } else {
+        SemanticDisambiguator::new()
+    };
+
+    let mut checker = GrammarChecker::with_grammar(motor.grammar().clone());
+    for rule in &disabled {
+        if !checker.disable_rule(rule) {
+            eprintln!("Regla desconocida: {} (ver --reglas)", rule);
+        }
+    }
+
+    if !words.is_empty() {
+        print_check_report(&checker, &words.join(" "));
+        return;
+    }
+
+    for line in io::stdin().lock().lines().map_while(Result::ok) {
+        if !line.trim().is_empty() {
+            print_check_report(&checker, &line);
+        }
+    }
+}
+
+/// Imprime los problemas detectados en un texto
+fn print_check_report(checker: &GrammarChecker, text: &str) {
+    let issues = checker.check(text);
+
+    println!("{}", text);
+    if issues.is_empty() {
+        println!("  ✓ Sin problemas");
+        return;
+    }
+
+    for issue in &issues {
+        let (start, end) = issue.span.unwrap_or((0, 0));
+        print!("  [{}] {}..{} \"{}\": {}", issue.rule, start, end, &text[start..end], issue.message);
+        if let Some(suggestion) = &issue.suggestion {
+            print!(" → \"{}\"", suggestion);
+        }
+        println!();
+    }
+}
+
+/// Carga el motor con diccionario completo RAE (el progreso va a stderr,
+/// para no mezclarse con la salida de `revisar`)
 fn load_full_motor() -> SemanticDisambiguator {
-    println!("Cargando diccionario completo RAE/LATAM...");
-    println!();
+    eprintln!("Cargando diccionario completo RAE/LATAM...");
+    eprintln!();
 
     // Try different data paths
     let possible_paths = [
@@ -301,26 +390,126 @@ fn load_full_motor() -> SemanticDisambiguator {
         if path.exists() {
             match SpanishDictionary::load_from_directory(path) {
                 Ok(dict) => {
-                    println!("Diccionario cargado exitosamente desde: {}", data_path);
-                    println!("  - Palabras válidas: {}", dict.len());
-                    println!("  - Entradas RAE: {}", dict.stats.rae_entries);
-                    println!("  - Conjugaciones: {}", dict.stats.total_conjugations);
-                    println!();
-                    return SemanticDisambiguator::with_dictionary(dict);
+                    eprintln!("Diccionario cargado exitosamente desde: {}", data_path);
+                    eprintln!("  - Palabras válidas: {}", dict.len());
+                    eprintln!("  - Entradas RAE: {}", dict.stats.rae_entries);
+                    eprintln!("  - Conjugaciones: {}", dict.stats.total_conjugations);
+                    eprintln!();
+                    let mut motor = SemanticDisambiguator::with_dictionary(dict);
+                    load_wordnet(&mut motor, path);
+                    load_vectors(&mut motor, path);
+                    load_language_model(&mut motor, path);
+                    load_error_model(&mut motor, path);
+                    return motor;
                 }
                 Err(e) => {
-                    println!("Error cargando desde {}: {}", data_path, e);
+                    eprintln!("Error cargando desde {}: {}", data_path, e);
                 }
             }
         }
     }
 
     // Fallback to basic dictionary
-    println!("No se encontró diccionario completo, usando básico");
-    println!();
+    eprintln!("No se encontró diccionario completo, usando básico");
+    eprintln!();
     SemanticDisambiguator::new()
 }
 
+/// Importa WordNet español si está en `data/wordnet` (OMW tab o LMF) o `data/mcr`
+fn load_wordnet(motor: &mut SemanticDisambiguator, data_path: &Path) {
+    let mut wordnet = WordNet::new();
+    let wordnet_dir = data_path.join("wordnet");
+
+    let result = if wordnet_dir.join("omw-es.xml").exists() {
+        wordnet.load_lmf(&wordnet_dir.join("omw-es.xml"))
+    } else if wordnet_dir.join("wn-data-spa.tab").exists() {
+        wordnet.load_omw_tab(&wordnet_dir.join("wn-data-spa.tab")).and_then(|_| {
+            let lexnames = wordnet_dir.join("lexnames.tab");
+            if lexnames.exists() { wordnet.load_lexnames(&lexnames) } else { Ok(()) }
+        })
+    } else if data_path.join("mcr").exists() {
+        wordnet.load_mcr_dir(&data_path.join("mcr"))
+    } else {
+        return;
+    };
+
+    match result {
+        Ok(()) => {
+            let stats = motor.semantic_db_mut().import_wordnet(&wordnet);
+            eprintln!("WordNet importado: {} palabras, {} relaciones", stats.words, stats.relations);
+            eprintln!();
+        }
+        Err(e) => eprintln!("Error cargando WordNet: {}", e),
+    }
+}
+
+/// Carga el primer archivo `.vec` de `data/vectors` (200K palabras más frecuentes)
+fn load_vectors(motor: &mut SemanticDisambiguator, data_path: &Path) {
+    let Ok(entries) = std::fs::read_dir(data_path.join("vectors")) else {
+        return;
+    };
+    let Some(file) = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| p.extension().is_some_and(|ext| ext == "vec"))
+    else {
+        return;
+    };
+
+    match WordVectors::load(&file, Some(200_000)) {
+        Ok(vectors) => {
+            eprintln!("Vectores cargados: {} palabras ({} dimensiones)", vectors.len(), vectors.dim());
+            eprintln!();
+            motor.semantic_db_mut().set_vectors(vectors);
+        }
+        Err(e) => eprintln!("Error cargando vectores: {}", e),
+    }
+}
+
+/// Carga `data/ngram/model.ngram`, o lo entrena desde `data/ngram/corpus.txt`
+fn load_language_model(motor: &mut SemanticDisambiguator, data_path: &Path) {
+    let ngram_dir = data_path.join("ngram");
+
+    let result = if ngram_dir.join("model.ngram").exists() {
+        NgramModel::load(&ngram_dir.join("model.ngram"))
+    } else if ngram_dir.join("corpus.txt").exists() {
+        NgramModel::from_corpus(&ngram_dir.join("corpus.txt"), 3)
+    } else {
+        return;
+    };
+
+    match result {
+        Ok(model) => {
+            eprintln!("Modelo de n-gramas: orden {}, {} n-gramas", model.order(), model.len());
+            eprintln!();
+            motor.set_language_model(model);
+        }
+        Err(e) => eprintln!("Error cargando modelo de n-gramas: {}", e),
+    }
+}
+
+/// Carga `data/typos/model.channel`, o lo entrena desde `data/typos/pairs.txt`
+fn load_error_model(motor: &mut SemanticDisambiguator, data_path: &Path) {
+    let typos_dir = data_path.join("typos");
+
+    let result = if typos_dir.join("model.channel").exists() {
+        ErrorModel::load(&typos_dir.join("model.channel"))
+    } else if typos_dir.join("pairs.txt").exists() {
+        ErrorModel::from_pairs_file(&typos_dir.join("pairs.txt"))
+    } else {
+        return;
+    };
+
+    match result {
+        Ok(model) => {
+            eprintln!("Modelo de errores: {} pares de entrenamiento", model.pairs());
+            eprintln!();
+            motor.set_error_model(model);
+        }
+        Err(e) => eprintln!("Error cargando modelo de errores: {}", e),
+    }
+}
+
 /// REPL interactivo con diccionario completo (218K palabras)
 fn run_repl_with_dictionary() {
     println!("═══════════════════════════════════════════════════════════════════");
@@ -509,7 +698,7 @@ mod integration_tests {
 
     #[test]
     fn test_roma_vs_amor_with_context() {
-        let mut motor = SemanticDisambiguator::new();
+        let motor = SemanticDisambiguator::new();
 
         // Contexto de arquitectura → debe elegir "Roma"
         let result = motor.process("Visité el Coliseo romano en smor");
@@ -531,7 +720,7 @@ mod integration_tests {
 
     #[test]
     fn test_spanish_word_order_flexibility() {
-        let mut motor = SemanticDisambiguator::new();
+        let motor = SemanticDisambiguator::new();
 
         // Todas estas variaciones son válidas en español
         let sentences = [
@@ -560,7 +749,7 @@ mod integration_tests {
             ..Config::default()
         };
 
-        let mut motor = SemanticDisambiguator::with_config(config_extreme_chars);
+        let motor = SemanticDisambiguator::with_config(config_extreme_chars);
         let result = motor.process("smor");
 
         // Con 95% peso en caracteres, el candidato con mejor match de
@@ -576,7 +765,7 @@ mod integration_tests {
             let dict = SpanishDictionary::load_from_directory(data_path);
             assert!(dict.is_ok());
             let dict = dict.unwrap();
-            assert!(dict.len() > 0);
+            assert!(!dict.is_empty());
         }
     }
 }
@@ -0,0 +1,463 @@
+//! # Named Entity Module
+//!
+//! Reconocimiento de entidades con nombre (lugares, personas,
+//! organizaciones) antes de normalizar, mientras las mayúsculas siguen
+//! disponibles:
+//!
+//! 1. Gazetteer (`data/ner/gazetteer.txt` embebido, ampliable desde archivo),
+//!    coincidencia más larga: "Ciudad de México"
+//! 2. `SemanticDB`: palabras con categoría `Place` o `Person` ("roma")
+//! 3. Mayúscula fuera del inicio de oración: nombre propio aunque no esté
+//!    en ningún diccionario ("Xochitl", "Universidad de Sonora"). No cuenta
+//!    si toda la oración va en mayúsculas, ni en una palabra suelta a un
+//!    error de otra conocida ("Colizeo")
+//!
+//! El desambiguador no corrige las palabras dentro de una entidad y usa
+//! las entidades reconocidas (y sus tags) para inferir el tema.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::chars::levenshtein_distance;
+use crate::dictionary::normalize_word;
+use crate::semantic::{PlaceType, SemanticCategory, SemanticDB};
+
+/// Gazetteer por defecto
+const EMBEDDED_GAZETTEER: &str = include_str!("../../data/ner/gazetteer.txt");
+
+/// Conectores dentro de un nombre propio ("Ciudad de México")
+const NAME_CONNECTORS: &[&str] = &["de", "del", "la", "las", "los"];
+
+/// Primeras palabras de nombres de lugar
+const PLACE_HEADS: &[&str] = &[
+    "ciudad", "rio", "monte", "lago", "calle", "avenida", "plaza", "estado", "provincia", "isla",
+];
+
+/// Primeras palabras de nombres de organización
+const ORGANIZATION_HEADS: &[&str] = &[
+    "universidad", "instituto", "banco", "ministerio", "secretaria", "hospital", "museo",
+    "academia", "empresa", "club",
+];
+
+/// Tratamientos que preceden a un nombre de persona
+const PERSON_TITLES: &[&str] = &["don", "dona", "senor", "senora", "sr", "sra", "doctor", "doctora"];
+
+/// Tipo de entidad
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityKind {
+    Place,
+    Person,
+    Organization,
+    /// Nombre propio de tipo desconocido
+    Unknown,
+}
+
+/// Cómo se reconoció la entidad
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntitySource {
+    Gazetteer,
+    Semantic,
+    Capitalization,
+}
+
+/// Entidad reconocida: tramo de tokens [start, end)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub kind: EntityKind,
+    pub start: usize,
+    pub end: usize,
+    /// Texto original del tramo
+    pub text: String,
+    pub source: EntitySource,
+    /// Tags del gazetteer (contexto para el tema)
+    pub tags: Vec<String>,
+}
+
+impl Entity {
+    /// ¿El token `i` pertenece a la entidad?
+    pub fn contains(&self, i: usize) -> bool {
+        (self.start..self.end).contains(&i)
+    }
+}
+
+/// Entrada del gazetteer
+#[derive(Debug, Clone)]
+struct GazetteerEntry {
+    kind: EntityKind,
+    tags: Vec<String>,
+}
+
+/// Lista de nombres conocidos por tipo
+#[derive(Debug, Clone)]
+pub struct Gazetteer {
+    /// Tokens normalizados → entrada
+    entries: HashMap<Vec<String>, GazetteerEntry>,
+    /// Longitud (en tokens) del nombre más largo
+    max_len: usize,
+}
+
+/// Error de lectura del gazetteer
+#[derive(Debug)]
+pub enum NerError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for NerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NerError::IoError(e) => write!(f, "IO error: {}", e),
+            NerError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for NerError {}
+
+impl Gazetteer {
+    /// Gazetteer vacío
+    pub fn new() -> Self {
+        Self { entries: HashMap::new(), max_len: 0 }
+    }
+
+    /// Gazetteer embebido en el binario
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED_GAZETTEER).expect("gazetteer embebido mal formado")
+    }
+
+    /// Lee un gazetteer desde archivo
+    pub fn from_file(path: &Path) -> Result<Self, NerError> {
+        let content = fs::read_to_string(path).map_err(|e| NerError::IoError(e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Parsea líneas `tipo nombre [| tag, tag]`
+    pub fn parse(content: &str) -> Result<Self, NerError> {
+        let mut gazetteer = Self::new();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name_part, tags) = match line.split_once('|') {
+                Some((name, tags)) => (name, tags.split(',').map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()).collect()),
+                None => (line, Vec::new()),
+            };
+            let (kind, name) = name_part.trim().split_once(char::is_whitespace)
+                .ok_or_else(|| NerError::ParseError(line_no + 1, "se esperaba: tipo nombre".to_string()))?;
+            let kind = match kind {
+                "place" => EntityKind::Place,
+                "person" => EntityKind::Person,
+                "org" => EntityKind::Organization,
+                other => return Err(NerError::ParseError(line_no + 1, format!("tipo desconocido: {}", other))),
+            };
+
+            gazetteer.add(kind, name.trim(), tags);
+        }
+
+        Ok(gazetteer)
+    }
+
+    /// Añade (o reemplaza) un nombre
+    pub fn add(&mut self, kind: EntityKind, name: &str, tags: Vec<String>) {
+        let key: Vec<String> = name.split_whitespace().map(normalize_word).collect();
+        if key.is_empty() {
+            return;
+        }
+        self.max_len = self.max_len.max(key.len());
+        self.entries.insert(key, GazetteerEntry { kind, tags });
+    }
+
+    /// Añade los nombres de otro gazetteer
+    pub fn extend(&mut self, other: Gazetteer) {
+        self.max_len = self.max_len.max(other.max_len);
+        self.entries.extend(other.entries);
+    }
+
+    /// Coincidencia más larga al inicio de `tokens`: (longitud, entrada)
+    fn longest_match(&self, tokens: &[String]) -> Option<(usize, &GazetteerEntry)> {
+        (1..=self.max_len.min(tokens.len())).rev().find_map(|len| {
+            let key: Vec<String> = tokens[..len].iter().map(|t| normalize_word(t)).collect();
+            self.entries.get(&key).map(|entry| (len, entry))
+        })
+    }
+
+    /// ¿Palabra a una sola edición de un nombre de una palabra del
+    /// gazetteer? ("Colizeo" → "Coliseo")
+    pub fn has_close_name(&self, word: &str) -> bool {
+        let word = normalize_word(word);
+        self.entries.keys().any(|key| {
+            key.len() == 1 && key[0] != word && levenshtein_distance(&key[0], &word) <= 1
+        })
+    }
+
+    /// Tipo de un nombre, si está en el gazetteer
+    pub fn kind_of(&self, name: &str) -> Option<EntityKind> {
+        let key: Vec<String> = name.split_whitespace().map(normalize_word).collect();
+        self.entries.get(&key).map(|entry| entry.kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for Gazetteer {
+    fn default() -> Self {
+        Self::embedded()
+    }
+}
+
+/// Reconocedor de entidades
+#[derive(Debug, Clone, Default)]
+pub struct EntityRecognizer {
+    gazetteer: Gazetteer,
+}
+
+impl EntityRecognizer {
+    /// Reconocedor con el gazetteer embebido
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reconocedor con un gazetteer propio
+    pub fn with_gazetteer(gazetteer: Gazetteer) -> Self {
+        Self { gazetteer }
+    }
+
+    /// Acceso al gazetteer (para ampliarlo)
+    pub fn gazetteer_mut(&mut self) -> &mut Gazetteer {
+        &mut self.gazetteer
+    }
+
+    /// Entidades de una oración tokenizada, sin solaparse y en orden
+    pub fn recognize(&self, tokens: &[String], semantic_db: &SemanticDB) -> Vec<Entity> {
+        self.recognize_with(tokens, semantic_db, |_| false)
+    }
+
+    /// Como `recognize`; `close_word` marca las palabras a un error de una
+    /// del diccionario: con mayúscula no se protegen, son erratas probables
+    /// ("el Colizeo romano")
+    pub fn recognize_with<F: Fn(&str) -> bool>(
+        &self,
+        tokens: &[String],
+        semantic_db: &SemanticDB,
+        close_word: F,
+    ) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        let mut i = 0;
+
+        // Oración toda en mayúsculas: la mayúscula no distingue nombres propios
+        let shouting = !tokens.iter().any(|t| t.chars().any(char::is_lowercase));
+
+        while i < tokens.len() {
+            if !is_word(&tokens[i]) {
+                i += 1;
+                continue;
+            }
+
+            // 1. Gazetteer
+            if let Some((len, entry)) = self.gazetteer.longest_match(&tokens[i..]) {
+                entities.push(entity(tokens, i, i + len, entry.kind, EntitySource::Gazetteer, entry.tags.clone()));
+                i += len;
+                continue;
+            }
+
+            // 2. Lugares y personas de la base semántica: con mayúscula, o en
+            // minúscula si la subcategoría es de nombre propio ("madrid", no "casa")
+            let capitalized = !shouting && is_capitalized(&tokens[i]);
+            let semantic_kind = semantic_db.lookup(&tokens[i]).and_then(|e| match &e.category {
+                SemanticCategory::Place { place_type, .. }
+                    if capitalized || matches!(place_type, PlaceType::City | PlaceType::Country | PlaceType::Region) =>
+                {
+                    Some(EntityKind::Place)
+                }
+                SemanticCategory::Person { role } if capitalized || role.is_none() => Some(EntityKind::Person),
+                _ => None,
+            });
+            if let Some(kind) = semantic_kind {
+                entities.push(entity(tokens, i, i + 1, kind, EntitySource::Semantic, Vec::new()));
+                i += 1;
+                continue;
+            }
+
+            // 3. Mayúscula fuera del inicio de oración, o al inicio si se
+            // coordina con otro nombre ("Juan y María")
+            if capitalized && (!is_sentence_start(tokens, i) || is_coordinated_name(tokens, i)) {
+                let end = proper_name_end(tokens, i);
+                let misspelled = end == i + 1
+                    && (self.gazetteer.has_close_name(&tokens[i]) || close_word(&tokens[i]));
+                if misspelled {
+                    i += 1;
+                    continue;
+                }
+                let kind = guess_kind(tokens, i);
+                entities.push(entity(tokens, i, end, kind, EntitySource::Capitalization, Vec::new()));
+                i = end;
+                continue;
+            }
+
+            i += 1;
+        }
+
+        entities
+    }
+}
+
+fn entity(tokens: &[String], start: usize, end: usize, kind: EntityKind, source: EntitySource, tags: Vec<String>) -> Entity {
+    Entity { kind, start, end, text: tokens[start..end].join(" "), source, tags }
+}
+
+fn is_word(token: &str) -> bool {
+    token.chars().any(|c| c.is_alphabetic())
+}
+
+fn is_capitalized(token: &str) -> bool {
+    token.starts_with(char::is_uppercase)
+}
+
+/// Primer token o tras puntuación de apertura/cierre de oración
+fn is_sentence_start(tokens: &[String], i: usize) -> bool {
+    i == 0 || matches!(tokens[i - 1].as_str(), "." | "!" | "?" | "¿" | "¡" | ":" | "\"" | "«" | "—")
+}
+
+/// Seguido de conjunción copulativa y otra palabra con mayúscula
+fn is_coordinated_name(tokens: &[String], i: usize) -> bool {
+    matches!(tokens.get(i + 1).map(|t| t.to_lowercase()).as_deref(), Some("y" | "e" | "o" | "u"))
+        && tokens.get(i + 2).is_some_and(|t| is_capitalized(t))
+}
+
+/// Fin de un nombre propio: mayúsculas seguidas, unidas por conectores
+/// solo si detrás vuelve a haber mayúscula
+fn proper_name_end(tokens: &[String], start: usize) -> usize {
+    let mut end = start + 1;
+    loop {
+        if end < tokens.len() && is_capitalized(&tokens[end]) {
+            end += 1;
+            continue;
+        }
+        let mut next = end;
+        while next < tokens.len() && NAME_CONNECTORS.contains(&tokens[next].to_lowercase().as_str()) {
+            next += 1;
+        }
+        if next > end && next < tokens.len() && is_capitalized(&tokens[next]) {
+            end = next + 1;
+            continue;
+        }
+        return end;
+    }
+}
+
+/// Tipo por la primera palabra o el tratamiento anterior
+fn guess_kind(tokens: &[String], start: usize) -> EntityKind {
+    let head = normalize_word(&tokens[start]);
+    let title = start.checked_sub(1).map(|i| normalize_word(&tokens[i])).unwrap_or_default();
+
+    if PLACE_HEADS.contains(&head.as_str()) {
+        EntityKind::Place
+    } else if ORGANIZATION_HEADS.contains(&head.as_str()) {
+        EntityKind::Organization
+    } else if PERSON_TITLES.contains(&title.as_str()) {
+        EntityKind::Person
+    } else {
+        EntityKind::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(sentence: &str) -> Vec<String> {
+        sentence.split_whitespace().map(String::from).collect()
+    }
+
+    fn spans(entities: &[Entity]) -> Vec<(&str, EntityKind, EntitySource)> {
+        entities.iter().map(|e| (e.text.as_str(), e.kind, e.source)).collect()
+    }
+
+    #[test]
+    fn test_gazetteer_longest_match() {
+        let recognizer = EntityRecognizer::new();
+        let db = SemanticDB::new();
+
+        let entities = recognizer.recognize(&tokens("Viajé a Ciudad de Mexico con Rosita"), &db);
+        assert_eq!(spans(&entities), vec![
+            ("Ciudad de Mexico", EntityKind::Place, EntitySource::Gazetteer),
+            ("Rosita", EntityKind::Person, EntitySource::Gazetteer),
+        ]);
+        assert_eq!(entities[0].tags, vec!["mexico", "viajes"]);
+        assert!(entities[0].contains(4) && !entities[0].contains(5));
+    }
+
+    #[test]
+    fn test_capitalized_names() {
+        let recognizer = EntityRecognizer::new();
+        let db = SemanticDB::new();
+
+        let entities = recognizer.recognize(&tokens("Ayer vi a Xochitl en la Universidad de Sonora con don Tlaloc"), &db);
+        assert_eq!(spans(&entities), vec![
+            ("Xochitl", EntityKind::Unknown, EntitySource::Capitalization),
+            ("Universidad de Sonora", EntityKind::Organization, EntitySource::Capitalization),
+            ("Tlaloc", EntityKind::Person, EntitySource::Capitalization),
+        ]);
+
+        // Inicio de oración: mayúscula sin valor de nombre propio
+        assert!(recognizer.recognize(&tokens("Smor es bonito . Xyz"), &db).is_empty());
+
+        // Minúsculas: lugares de la base semántica
+        let entities = recognizer.recognize(&tokens("fui a madrid"), &db);
+        assert_eq!(spans(&entities), vec![("madrid", EntityKind::Place, EntitySource::Gazetteer)]);
+        // Nombres comunes de la base semántica no son entidades
+        assert!(recognizer.recognize(&tokens("mi casa es azul"), &db).is_empty());
+
+        // Inicio de oración coordinado con otro nombre
+        let entities = recognizer.recognize(&tokens("Juan y María corren"), &db);
+        assert_eq!(spans(&entities), vec![
+            ("Juan", EntityKind::Unknown, EntitySource::Capitalization),
+            ("María", EntityKind::Unknown, EntitySource::Capitalization),
+        ]);
+    }
+
+    #[test]
+    fn test_shouting_and_misspelled_names() {
+        let recognizer = EntityRecognizer::new();
+        let db = SemanticDB::new();
+
+        // Toda en mayúsculas: sin entidades por mayúscula
+        let entities = recognizer.recognize(&tokens("VISITÉ EL COLISEO ROMANO EN SMOR"), &db);
+        assert!(entities.iter().all(|e| e.source != EntitySource::Capitalization));
+        assert!(!entities.iter().any(|e| e.contains(5)));
+
+        // Errata de un nombre del gazetteer o de una palabra del diccionario
+        assert!(recognizer.recognize(&tokens("visité el Colizeo romano"), &db).is_empty());
+        let close = |w: &str| normalize_word(w) == "romanp";
+        assert!(recognizer.recognize_with(&tokens("el arco Romanp"), &db, close).is_empty());
+
+        // Los nombres sin palabra cercana siguen protegidos
+        let entities = recognizer.recognize_with(&tokens("vi a Xochitl"), &db, close);
+        assert_eq!(spans(&entities), vec![("Xochitl", EntityKind::Unknown, EntitySource::Capitalization)]);
+    }
+
+    #[test]
+    fn test_gazetteer_file_format() {
+        let mut gazetteer = Gazetteer::parse("# extra\norg  Avermex Research | investigacion\nplace Hermosillo\n").unwrap();
+        assert_eq!(gazetteer.len(), 2);
+        assert_eq!(gazetteer.kind_of("avermex research"), Some(EntityKind::Organization));
+
+        gazetteer.extend(Gazetteer::embedded());
+        assert_eq!(gazetteer.kind_of("París"), Some(EntityKind::Place));
+        assert_eq!(gazetteer.kind_of("Paris"), Some(EntityKind::Place));
+
+        assert!(matches!(Gazetteer::parse("city Roma"), Err(NerError::ParseError(1, _))));
+        assert!(matches!(Gazetteer::parse("\nplace"), Err(NerError::ParseError(2, _))));
+    }
+}
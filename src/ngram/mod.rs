@@ -0,0 +1,465 @@
+//! # N-gram Module
+//!
+//! Modelo de lenguaje de n-gramas de palabras con suavizado Kneser-Ney
+//! interpolado, entrenado a partir de un corpus de texto plano.
+//!
+//! Responde a "¿qué palabra suele seguir a *viajé a*?":
+//!
+//! ```text
+//! P(roma | viajé a) = max(c(viajé a roma) - D, 0) / c(viajé a)
+//!                   + D · N1+(viajé a ·) / c(viajé a) · P(roma | a)
+//! ```
+//!
+//! Los órdenes inferiores usan conteos de continuación (en cuántos
+//! contextos distintos aparece la palabra), no frecuencias crudas.
+//!
+//! ## Formato en disco
+//!
+//! Solo se guardan el vocabulario y los conteos del orden máximo (con
+//! índices de palabra); los órdenes inferiores se derivan al cargar:
+//!
+//! ```text
+//! @ngram 1 3
+//! @vocab
+//! <s>
+//! </s>
+//! viajé
+//! @grams
+//! 0 0 2 4
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Versión del formato en disco que entiende este parser
+pub const NGRAM_FORMAT_VERSION: u32 = 1;
+
+/// Marca de inicio de oración
+pub const BOS: &str = "<s>";
+
+/// Marca de fin de oración
+pub const EOS: &str = "</s>";
+
+/// Modelo de n-gramas con suavizado Kneser-Ney interpolado
+#[derive(Debug, Clone)]
+pub struct NgramModel {
+    /// Orden del modelo (3 = trigramas)
+    order: usize,
+    /// Vocabulario (índice → palabra)
+    vocab: Vec<String>,
+    /// Palabra → índice
+    ids: HashMap<String, u32>,
+    /// Por orden (índice k-1): conteo crudo en el orden máximo (lo único
+    /// que se guarda) y de continuación en los inferiores
+    grams: Vec<HashMap<Vec<u32>, u64>>,
+    /// Por orden: contexto → (suma de conteos, palabras distintas que lo siguen)
+    contexts: Vec<HashMap<Vec<u32>, (u64, u64)>>,
+    /// Descuento absoluto por orden
+    discounts: Vec<f64>,
+}
+
+/// Error de lectura del modelo
+#[derive(Debug)]
+pub enum NgramError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for NgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NgramError::IoError(e) => write!(f, "IO error: {}", e),
+            NgramError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for NgramError {}
+
+impl NgramModel {
+    /// Modelo vacío de orden `order` (mínimo 1)
+    pub fn new(order: usize) -> Self {
+        let mut model = Self {
+            order: order.max(1),
+            vocab: Vec::new(),
+            ids: HashMap::new(),
+            grams: vec![HashMap::new(); order.max(1)],
+            contexts: Vec::new(),
+            discounts: Vec::new(),
+        };
+        model.intern(BOS);
+        model.intern(EOS);
+        model.rebuild();
+        model
+    }
+
+    /// Entrena un modelo a partir de texto plano
+    pub fn train(order: usize, text: &str) -> Self {
+        let mut model = Self::new(order);
+        model.add_text(text);
+        model
+    }
+
+    /// Entrena un modelo a partir de un archivo de corpus
+    pub fn from_corpus(path: &Path, order: usize) -> Result<Self, NgramError> {
+        let text = fs::read_to_string(path).map_err(|e| NgramError::IoError(e.to_string()))?;
+        Ok(Self::train(order, &text))
+    }
+
+    /// Añade texto al modelo (una oración por línea o separadas por . ! ?)
+    pub fn add_text(&mut self, text: &str) {
+        for sentence in sentences(text) {
+            let words: Vec<&str> = sentence.iter().map(|w| w.as_str()).collect();
+            self.count_sentence(&words);
+        }
+        self.rebuild();
+    }
+
+    fn count_sentence(&mut self, words: &[&str]) {
+        if words.is_empty() {
+            return;
+        }
+
+        let mut ids = vec![self.ids[BOS]; self.order - 1];
+        for word in words {
+            ids.push(self.intern(word));
+        }
+        ids.push(self.ids[EOS]);
+
+        for gram in ids.windows(self.order) {
+            *self.grams[self.order - 1].entry(gram.to_vec()).or_insert(0) += 1;
+        }
+    }
+
+    fn intern(&mut self, word: &str) -> u32 {
+        if let Some(&id) = self.ids.get(word) {
+            return id;
+        }
+        let id = self.vocab.len() as u32;
+        self.vocab.push(word.to_string());
+        self.ids.insert(word.to_string(), id);
+        id
+    }
+
+    /// Deriva órdenes inferiores, contextos y descuentos de los conteos
+    fn rebuild(&mut self) {
+        let n = self.order;
+
+        // N1+(· g): contextos izquierdos distintos de cada g
+        for k in (1..n).rev() {
+            let mut lower: HashMap<Vec<u32>, u64> = HashMap::new();
+            for gram in self.grams[k].keys() {
+                *lower.entry(gram[1..].to_vec()).or_insert(0) += 1;
+            }
+            self.grams[k - 1] = lower;
+        }
+
+        self.contexts = self.grams.iter()
+            .map(|grams| {
+                let mut contexts: HashMap<Vec<u32>, (u64, u64)> = HashMap::new();
+                for (gram, &count) in grams {
+                    let entry = contexts.entry(gram[..gram.len() - 1].to_vec()).or_insert((0, 0));
+                    entry.0 += count;
+                    entry.1 += 1;
+                }
+                contexts
+            })
+            .collect();
+
+        // D = n1 / (n1 + 2·n2)
+        self.discounts = self.grams.iter()
+            .map(|grams| {
+                let n1 = grams.values().filter(|&&c| c == 1).count() as f64;
+                let n2 = grams.values().filter(|&&c| c == 2).count() as f64;
+                if n1 > 0.0 && n2 > 0.0 {
+                    (n1 / (n1 + 2.0 * n2)).clamp(0.1, 0.9)
+                } else {
+                    0.5
+                }
+            })
+            .collect();
+    }
+
+    /// Orden del modelo
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Tamaño del vocabulario (incluye las marcas de oración)
+    pub fn vocab_size(&self) -> usize {
+        self.vocab.len()
+    }
+
+    /// Número de n-gramas distintos del orden máximo
+    pub fn len(&self) -> usize {
+        self.grams[self.order - 1].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.grams[self.order - 1].is_empty()
+    }
+
+    /// P(palabra | contexto); usa las últimas `order - 1` palabras del contexto
+    pub fn probability(&self, context: &[&str], word: &str) -> f64 {
+        let context: Vec<Option<u32>> = context.iter().map(|w| self.id(w)).collect();
+        self.probability_ids(&context, self.id(word))
+    }
+
+    /// ln P(palabra | izquierda, derecha): la palabra y las `order - 1`
+    /// siguientes dado lo anterior. `left` y `right` son el resto de la
+    /// oración (se añaden las marcas de inicio y fin).
+    pub fn log_prob_in_context(&self, left: &[&str], word: &str, right: &[&str]) -> f64 {
+        let bos = self.id(BOS);
+        let mut sequence: Vec<Option<u32>> = vec![bos; self.order - 1];
+        sequence.extend(left.iter().map(|w| self.id(w)));
+
+        let start = sequence.len();
+        sequence.push(self.id(word));
+        sequence.extend(right.iter().take(self.order - 1).map(|w| self.id(w)));
+        if right.len() < self.order - 1 {
+            sequence.push(self.id(EOS));
+        }
+
+        (start..sequence.len())
+            .map(|i| self.probability_ids(&sequence[..i], sequence[i]).ln())
+            .sum()
+    }
+
+    fn id(&self, word: &str) -> Option<u32> {
+        match word {
+            BOS | EOS => self.ids.get(word).copied(),
+            _ => self.ids.get(&word.to_lowercase()).copied(),
+        }
+    }
+
+    fn probability_ids(&self, context: &[Option<u32>], word: Option<u32>) -> f64 {
+        let context = &context[context.len().saturating_sub(self.order - 1)..];
+        let k = context.len() + 1;
+
+        let lower = if context.is_empty() {
+            // Base uniforme (con hueco para palabras fuera del vocabulario)
+            1.0 / (self.vocab.len() as f64 + 1.0)
+        } else {
+            self.probability_ids(&context[1..], word)
+        };
+
+        // Contexto con palabras desconocidas o nunca visto: solo orden inferior
+        let Some(ctx) = context.iter().copied().collect::<Option<Vec<u32>>>() else {
+            return lower;
+        };
+        let Some(&(total, distinct)) = self.contexts[k - 1].get(&ctx) else {
+            return lower;
+        };
+
+        let count = word
+            .and_then(|w| {
+                let mut gram = ctx.clone();
+                gram.push(w);
+                self.grams[k - 1].get(&gram).copied()
+            })
+            .unwrap_or(0);
+
+        let discount = self.discounts[k - 1];
+        ((count as f64 - discount).max(0.0) + discount * distinct as f64 * lower) / total as f64
+    }
+
+    /// Guarda el modelo en disco
+    pub fn save(&self, path: &Path) -> Result<(), NgramError> {
+        fs::write(path, self.to_text()).map_err(|e| NgramError::IoError(e.to_string()))
+    }
+
+    /// Lee un modelo guardado con `save`
+    pub fn load(path: &Path) -> Result<Self, NgramError> {
+        let content = fs::read_to_string(path).map_err(|e| NgramError::IoError(e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Serializa el modelo (n-gramas ordenados, salida determinista)
+    pub fn to_text(&self) -> String {
+        let mut out = format!("@ngram {} {}\n@vocab\n", NGRAM_FORMAT_VERSION, self.order);
+        for word in &self.vocab {
+            out.push_str(word);
+            out.push('\n');
+        }
+
+        out.push_str("@grams\n");
+        let mut grams: Vec<(&Vec<u32>, &u64)> = self.grams[self.order - 1].iter().collect();
+        grams.sort();
+        for (gram, count) in grams {
+            let ids: Vec<String> = gram.iter().map(|id| id.to_string()).collect();
+            out.push_str(&format!("{} {}\n", ids.join(" "), count));
+        }
+        out
+    }
+
+    /// Parsea el formato de texto
+    pub fn parse(content: &str) -> Result<Self, NgramError> {
+        let mut lines = content.lines().enumerate().map(|(i, l)| (i + 1, l.trim()));
+
+        let (line_no, header) = lines.next()
+            .ok_or_else(|| NgramError::ParseError(1, "archivo vacío".to_string()))?;
+        let fields: Vec<&str> = header.split_whitespace().collect();
+        let (version, order) = match fields.as_slice() {
+            ["@ngram", version, order] => (
+                version.parse::<u32>().map_err(|_| NgramError::ParseError(line_no, "versión inválida".to_string()))?,
+                order.parse::<usize>().map_err(|_| NgramError::ParseError(line_no, "orden inválido".to_string()))?,
+            ),
+            _ => return Err(NgramError::ParseError(line_no, "se esperaba: @ngram versión orden".to_string())),
+        };
+        if version > NGRAM_FORMAT_VERSION {
+            return Err(NgramError::ParseError(line_no, format!("versión {} no soportada", version)));
+        }
+        if order == 0 {
+            return Err(NgramError::ParseError(line_no, "orden inválido".to_string()));
+        }
+
+        let mut model = Self::new(order);
+        model.vocab.clear();
+        model.ids.clear();
+
+        let mut section = "";
+        for (line_no, line) in lines {
+            if line.is_empty() {
+                continue;
+            }
+            if line == "@vocab" || line == "@grams" {
+                section = line;
+                continue;
+            }
+
+            match section {
+                "@vocab" => {
+                    model.intern(line);
+                }
+                "@grams" => {
+                    let values = line.split_whitespace()
+                        .map(|v| v.parse::<u64>())
+                        .collect::<Result<Vec<u64>, _>>()
+                        .map_err(|_| NgramError::ParseError(line_no, "valor no numérico".to_string()))?;
+                    if values.len() != order + 1 {
+                        return Err(NgramError::ParseError(line_no, format!("se esperaban {} índices y un conteo", order)));
+                    }
+                    let gram: Vec<u32> = values[..order].iter().map(|&id| id as u32).collect();
+                    if gram.iter().any(|&id| id as usize >= model.vocab.len()) {
+                        return Err(NgramError::ParseError(line_no, "índice fuera del vocabulario".to_string()));
+                    }
+                    model.grams[order - 1].insert(gram, values[order]);
+                }
+                _ => return Err(NgramError::ParseError(line_no, "línea fuera de sección".to_string())),
+            }
+        }
+
+        if model.ids.get(BOS) != Some(&0) || model.ids.get(EOS) != Some(&1) {
+            return Err(NgramError::ParseError(0, format!("el vocabulario debe empezar por {} y {}", BOS, EOS)));
+        }
+
+        model.rebuild();
+        Ok(model)
+    }
+}
+
+impl Default for NgramModel {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Divide texto plano en oraciones de palabras en minúsculas
+fn sentences(text: &str) -> Vec<Vec<String>> {
+    text.split(['\n', '.', '!', '?', ';'])
+        .map(|sentence| {
+            sentence
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(|w| w.to_lowercase())
+                .collect::<Vec<String>>()
+        })
+        .filter(|words| !words.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: &str = "Viajé a Roma en verano.\n\
+        Viajé a Roma con mi familia.\n\
+        Viajé a Madrid el año pasado.\n\
+        Vivo en Roma desde hace años.\n\
+        El amor es ciego. Mi amor vive en Madrid.";
+
+    #[test]
+    fn test_kneser_ney_probabilities() {
+        let model = NgramModel::train(3, CORPUS);
+        assert_eq!(model.order(), 3);
+
+        let roma = model.probability(&["viajé", "a"], "roma");
+        let madrid = model.probability(&["viajé", "a"], "madrid");
+        let amor = model.probability(&["viajé", "a"], "amor");
+        assert!(roma > madrid);
+        assert!(madrid > amor);
+        assert!(amor > 0.0);
+
+        // Palabra fuera del vocabulario: probabilidad pequeña pero no nula
+        let unknown = model.probability(&["viajé", "a"], "xyz");
+        assert!(unknown > 0.0 && unknown < amor);
+    }
+
+    #[test]
+    fn test_distribution_sums_to_one() {
+        let model = NgramModel::train(3, CORPUS);
+        let words: Vec<String> = model.vocab.clone();
+
+        for context in [vec!["viajé", "a"], vec!["en"], vec![]] {
+            let total: f64 = words.iter().map(|w| model.probability(&context, w)).sum::<f64>()
+                + model.probability(&context, "fuera_de_vocabulario");
+            assert!((total - 1.0).abs() < 1e-9, "contexto {:?}: {}", context, total);
+        }
+    }
+
+    #[test]
+    fn test_left_and_right_context() {
+        let model = NgramModel::train(3, CORPUS);
+
+        // "a ___ en": roma aparece seguido de "en"
+        let roma = model.log_prob_in_context(&["viajé", "a"], "roma", &["en", "verano"]);
+        let amor = model.log_prob_in_context(&["viajé", "a"], "amor", &["en", "verano"]);
+        assert!(roma > amor);
+
+        // Fin de oración: "... en Madrid"
+        let madrid = model.log_prob_in_context(&["vive", "en"], "madrid", &[]);
+        let ciego = model.log_prob_in_context(&["vive", "en"], "ciego", &[]);
+        assert!(madrid > ciego);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let model = NgramModel::train(3, CORPUS);
+        let text = model.to_text();
+        assert!(text.starts_with("@ngram 1 3\n@vocab\n<s>\n</s>\n"));
+
+        let loaded = NgramModel::parse(&text).unwrap();
+        assert_eq!(loaded.len(), model.len());
+        assert_eq!(loaded.vocab_size(), model.vocab_size());
+        let p = model.probability(&["viajé", "a"], "roma");
+        assert!((loaded.probability(&["viajé", "a"], "roma") - p).abs() < 1e-12);
+        assert_eq!(loaded.to_text(), text);
+
+        // Seguir entrenando tras cargar equivale a entrenar con todo el texto
+        let mut extended = NgramModel::parse(&text).unwrap();
+        extended.add_text("viajé a madrid");
+        let full = NgramModel::train(3, &format!("{}\nviajé a madrid", CORPUS));
+        assert_eq!(extended.len(), full.len());
+        let p = full.probability(&["viajé", "a"], "madrid");
+        assert!((extended.probability(&["viajé", "a"], "madrid") - p).abs() < 1e-12);
+
+        assert!(matches!(NgramModel::parse("@ngram 9 3\n"), Err(NgramError::ParseError(1, _))));
+        assert!(matches!(
+            NgramModel::parse("@ngram 1 2\n@vocab\n<s>\n</s>\n@grams\n0 7 1\n"),
+            Err(NgramError::ParseError(6, _))
+        ));
+    }
+}
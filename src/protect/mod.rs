@@ -0,0 +1,253 @@
+//! # Protected Spans Module
+//!
+//! Paso previo a la tokenización que detecta tramos que no son lenguaje
+//! natural y deben pasar sin cambios: URLs, correos, números con unidad,
+//! fechas, horas, hashtags, menciones, código en línea y texto entre
+//! comillas.
+//!
+//! ```text
+//! "escríbeme a yo@mail.com antes de las 18:30"
+//!              ^^^^^^^^^^^              ^^^^^  → un token protegido cada uno
+//! ```
+//!
+//! Los patrones son expresiones regulares mínimas (`pattern`); se pueden
+//! añadir patrones propios con `SpanProtector::add_pattern`.
+
+pub mod pattern;
+
+use pattern::{IndexedText, Pattern, PatternError};
+
+/// Tipo de tramo protegido
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtectedKind {
+    Url,
+    Email,
+    Number,
+    Date,
+    Time,
+    Hashtag,
+    Mention,
+    Code,
+    Quoted,
+    /// Patrón del usuario, con su nombre
+    Custom(String),
+}
+
+/// Tramo protegido: offsets en bytes [start, end)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedSpan {
+    pub kind: ProtectedKind,
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+/// Trozo de una oración tras el paso de protección
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a> {
+    /// Texto normal, a tokenizar
+    Text(&'a str),
+    /// Tramo protegido, un solo token
+    Protected(ProtectedSpan),
+}
+
+/// Patrones por defecto, en orden de prioridad
+const BUILTIN_PATTERNS: &[(&str, &str)] = &[
+    ("code", r"`[^`]+`"),
+    ("quoted", r#""[^"]+"|«[^»]+»|“[^”]+”"#),
+    ("url", r"(https?://|www\.)[^\s]*[^\s.,;:!?)»”]"),
+    ("email", r"[\w.+-]+@\w[\w-]*(\.\w[\w-]*)+"),
+    ("date", r"\d{4}-\d{2}-\d{2}|\d{1,2}[/-]\d{1,2}[/-]\d{2,4}"),
+    ("time", r"\d{1,2}:\d{2}(:\d{2})?"),
+    ("number", r"[-+]?\d+([.,]\d+)*( ?(%|°C|°F|km|cm|mm|m|kg|mg|g|ml|l|h|min|s|€|\$|MXN|USD))?"),
+    ("hashtag", r"#\w+"),
+    ("mention", r"@\w+"),
+];
+
+/// Detector de tramos protegidos
+#[derive(Debug, Clone)]
+pub struct SpanProtector {
+    /// Patrones del usuario (se prueban antes que los de por defecto)
+    custom: Vec<(String, Pattern)>,
+    builtin: Vec<(ProtectedKind, Pattern)>,
+}
+
+impl SpanProtector {
+    /// Detector con los patrones por defecto
+    pub fn new() -> Self {
+        let builtin = BUILTIN_PATTERNS
+            .iter()
+            .map(|(name, source)| {
+                let kind = match *name {
+                    "code" => ProtectedKind::Code,
+                    "quoted" => ProtectedKind::Quoted,
+                    "url" => ProtectedKind::Url,
+                    "email" => ProtectedKind::Email,
+                    "date" => ProtectedKind::Date,
+                    "time" => ProtectedKind::Time,
+                    "number" => ProtectedKind::Number,
+                    "hashtag" => ProtectedKind::Hashtag,
+                    _ => ProtectedKind::Mention,
+                };
+                (kind, Pattern::new(source).expect("patrón por defecto inválido"))
+            })
+            .collect();
+
+        Self { custom: Vec::new(), builtin }
+    }
+
+    /// Detector sin patrones
+    pub fn empty() -> Self {
+        Self { custom: Vec::new(), builtin: Vec::new() }
+    }
+
+    /// Añade un patrón propio (tiene prioridad sobre los de por defecto)
+    pub fn add_pattern(&mut self, name: &str, source: &str) -> Result<(), PatternError> {
+        let pattern = Pattern::new(source)?;
+        self.custom.push((name.to_string(), pattern));
+        Ok(())
+    }
+
+    /// Tramos protegidos del texto, en orden y sin solaparse. Solo empiezan
+    /// y terminan en límite de palabra.
+    pub fn find_spans(&self, text: &str) -> Vec<ProtectedSpan> {
+        let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+        let boundary_after = |end: usize| end > 0 && !is_word_char(text[end..].chars().next());
+
+        let patterns = self.custom.iter()
+            .map(|(name, p)| (ProtectedKind::Custom(name.clone()), p))
+            .chain(self.builtin.iter().map(|(kind, p)| (kind.clone(), p)));
+        let patterns: Vec<(ProtectedKind, &Pattern)> = patterns.collect();
+
+        // Caracteres y offsets una sola vez para todas las posiciones
+        let indexed = IndexedText::new(text);
+        let mut spans = Vec::new();
+        let mut previous: Option<char> = None;
+        let mut skip_until = 0;
+
+        for (begin, &c) in indexed.chars.iter().enumerate() {
+            let start = indexed.offsets[begin];
+            let at_boundary = !is_word_char(previous);
+            previous = Some(c);
+            if start < skip_until || !at_boundary || c.is_whitespace() {
+                continue;
+            }
+
+            let found = patterns.iter().find_map(|(kind, pattern)| {
+                pattern.match_indexed(&indexed, begin, |end| end > start && boundary_after(end))
+                    .map(|end| (kind.clone(), end))
+            });
+
+            if let Some((kind, end)) = found {
+                spans.push(ProtectedSpan { kind, start, end, text: text[start..end].to_string() });
+                skip_until = end;
+            }
+        }
+
+        spans
+    }
+
+    /// Divide el texto en trozos normales y tramos protegidos
+    pub fn segment<'a>(&self, text: &'a str) -> Vec<Segment<'a>> {
+        let mut segments = Vec::new();
+        let mut last = 0;
+
+        for span in self.find_spans(text) {
+            if span.start > last {
+                segments.push(Segment::Text(&text[last..span.start]));
+            }
+            last = span.end;
+            segments.push(Segment::Protected(span));
+        }
+        if last < text.len() {
+            segments.push(Segment::Text(&text[last..]));
+        }
+
+        segments
+    }
+}
+
+impl Default for SpanProtector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &str) -> Vec<(ProtectedKind, String)> {
+        SpanProtector::new().find_spans(text).into_iter().map(|s| (s.kind, s.text)).collect()
+    }
+
+    #[test]
+    fn test_urls_and_emails() {
+        assert_eq!(spans("mira https://x.com/a?b=1. y escribe a user.name@mail.com."), vec![
+            (ProtectedKind::Url, "https://x.com/a?b=1".to_string()),
+            (ProtectedKind::Email, "user.name@mail.com".to_string()),
+        ]);
+        assert_eq!(spans("en www.rae.es hay más"), vec![(ProtectedKind::Url, "www.rae.es".to_string())]);
+    }
+
+    #[test]
+    fn test_numbers_dates_and_tags() {
+        assert_eq!(spans("corrí 5 km el 12/03/2024 a las 18:30 con 20% más #maratón @juan"), vec![
+            (ProtectedKind::Number, "5 km".to_string()),
+            (ProtectedKind::Date, "12/03/2024".to_string()),
+            (ProtectedKind::Time, "18:30".to_string()),
+            (ProtectedKind::Number, "20%".to_string()),
+            (ProtectedKind::Hashtag, "#maratón".to_string()),
+            (ProtectedKind::Mention, "@juan".to_string()),
+        ]);
+
+        // "5 kilos": la unidad no puede cortar una palabra
+        assert_eq!(spans("5 kilos"), vec![(ProtectedKind::Number, "5".to_string())]);
+        // Dígitos dentro de una palabra no se protegen
+        assert!(spans("abc123").is_empty());
+    }
+
+    #[test]
+    fn test_code_quotes_and_custom_patterns() {
+        assert_eq!(spans("usa `cargo build` y di \"hola mundo\""), vec![
+            (ProtectedKind::Code, "`cargo build`".to_string()),
+            (ProtectedKind::Quoted, "\"hola mundo\"".to_string()),
+        ]);
+
+        let mut protector = SpanProtector::new();
+        protector.add_pattern("expediente", r"EXP-\d{4}").unwrap();
+        let found = protector.find_spans("revisa el EXP-2024 hoy");
+        assert_eq!(found[0].kind, ProtectedKind::Custom("expediente".to_string()));
+        assert_eq!(found[0].text, "EXP-2024");
+        assert!(protector.add_pattern("roto", "(abc").is_err());
+
+        let segments = protector.segment("ver EXP-2024 ya");
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0], Segment::Text("ver "));
+    }
+
+    #[test]
+    fn test_long_inputs() {
+        // Repeticiones de un carácter sin recursión: no desbordan la pila
+        let url = format!("https://{}", "a".repeat(100_000));
+        assert_eq!(spans(&url), vec![(ProtectedKind::Url, url.clone())]);
+
+        let unclosed = format!("\"{}", "palabra ".repeat(40_000));
+        assert!(spans(&unclosed).is_empty());
+    }
+
+    #[test]
+    fn test_long_group_repetitions() {
+        // Repeticiones de grupo ("([.,]\d+)*", "(\.\w[\w-]*)+") en un hilo
+        // con la pila por defecto, como los trabajadores de `process_batch`
+        std::thread::spawn(|| {
+            let number = format!("1{}", ",1".repeat(100_000));
+            assert_eq!(spans(&number), vec![(ProtectedKind::Number, number.clone())]);
+
+            let email = format!("yo@mail{}", ".com".repeat(50_000));
+            assert_eq!(spans(&email), vec![(ProtectedKind::Email, email.clone())]);
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,448 @@
+//! # Pattern Module
+//!
+//! Expresiones regulares mínimas (sin dependencias) para los tramos
+//! protegidos. Sintaxis soportada:
+//!
+//! | Elemento | Significado |
+//! |----------|-------------|
+//! | `a` `\.` | carácter literal (`\` escapa metacaracteres) |
+//! | `.` | cualquier carácter |
+//! | `[a-z_]` `[^"]` | clase de caracteres (negada con `^`) |
+//! | `\d` `\w` `\s` | dígito, alfanumérico o `_`, espacio (`\D` `\W` `\S` niegan) |
+//! | `*` `+` `?` `{n}` `{n,}` `{n,m}` | repeticiones (voraces) |
+//! | `(a|b)` | grupo con alternativas |
+//! | `^` `$` | inicio y fin del texto |
+//!
+//! La búsqueda es por retroceso (backtracking) sobre caracteres; las
+//! repeticiones (`[^"]+`, `\d*`, `([.,]\d+)*`) son iterativas, así la
+//! pila no crece con la longitud del texto.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Patrón compilado
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    alternatives: Vec<Vec<Piece>>,
+}
+
+/// Elemento con su repetición
+#[derive(Debug, Clone)]
+struct Piece {
+    node: Node,
+    min: usize,
+    max: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class { items: Vec<ClassItem>, negated: bool },
+    Start,
+    End,
+    Group(Vec<Vec<Piece>>),
+}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Digit(negated) => c.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => (c.is_alphanumeric() || c == '_') != negated,
+            ClassItem::Space(negated) => c.is_whitespace() != negated,
+        }
+    }
+}
+
+/// Error de sintaxis (posición en caracteres, descripción)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternError(pub usize, pub String);
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pattern error (posición {}): {}", self.0, self.1)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl Pattern {
+    /// Compila un patrón
+    pub fn new(source: &str) -> Result<Self, PatternError> {
+        let mut parser = Parser { chars: source.chars().collect(), pos: 0 };
+        let alternatives = parser.alternatives()?;
+        if parser.pos < parser.chars.len() {
+            return Err(PatternError(parser.pos, "')' sin abrir".to_string()));
+        }
+        Ok(Self { source: source.to_string(), alternatives })
+    }
+
+    /// Texto del patrón
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// ¿Hay alguna coincidencia en el texto?
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// Primera coincidencia (offsets en bytes)
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        let indexed = IndexedText::new(text);
+        (0..=indexed.chars.len()).find_map(|begin| {
+            self.match_indexed(&indexed, begin, |_| true).map(|end| (indexed.offsets[begin], end))
+        })
+    }
+
+    /// Coincidencia que empieza en `start` (offset en bytes) y cuyo final
+    /// cumple `accept`; devuelve el offset del final. Si el final voraz no
+    /// se acepta, se prueban finales más cortos.
+    pub fn match_at<F: Fn(usize) -> bool>(&self, text: &str, start: usize, accept: F) -> Option<usize> {
+        let indexed = IndexedText::new(text);
+        let begin = indexed.offsets.iter().position(|&o| o == start)?;
+        self.match_indexed(&indexed, begin, accept)
+    }
+
+    /// Como `match_at`, sobre un texto ya indexado y con `begin` en
+    /// caracteres; para buscar en muchas posiciones del mismo texto
+    pub fn match_indexed<F: Fn(usize) -> bool>(&self, text: &IndexedText, begin: usize, accept: F) -> Option<usize> {
+        let found = Cell::new(None);
+        let cont = |pos: usize| {
+            if accept(text.offsets[pos]) {
+                found.set(Some(text.offsets[pos]));
+                true
+            } else {
+                false
+            }
+        };
+        let matcher = Matcher { chars: &text.chars };
+        matcher.alternatives(&self.alternatives, begin, &cont);
+        found.get()
+    }
+}
+
+/// Texto preparado para buscar: caracteres y su offset en bytes (más el
+/// final del texto)
+#[derive(Debug, Clone)]
+pub struct IndexedText {
+    pub chars: Vec<char>,
+    pub offsets: Vec<usize>,
+}
+
+impl IndexedText {
+    pub fn new(text: &str) -> Self {
+        let (mut offsets, chars): (Vec<usize>, Vec<char>) = text.char_indices().unzip();
+        offsets.push(text.len());
+        Self { chars, offsets }
+    }
+}
+
+struct Matcher<'a> {
+    chars: &'a [char],
+}
+
+impl Matcher<'_> {
+    fn alternatives(&self, alternatives: &[Vec<Piece>], pos: usize, cont: &dyn Fn(usize) -> bool) -> bool {
+        alternatives.iter().any(|seq| self.sequence(seq, pos, cont))
+    }
+
+    fn sequence(&self, pieces: &[Piece], pos: usize, cont: &dyn Fn(usize) -> bool) -> bool {
+        match pieces.split_first() {
+            None => cont(pos),
+            Some((piece, rest)) if piece.node.is_single_char() => {
+                self.repeat_chars(piece, pos, &|p| self.sequence(rest, p, cont))
+            }
+            Some((piece, rest)) => self.repeat(piece, pos, &|p| self.sequence(rest, p, cont)),
+        }
+    }
+
+    /// Repetición voraz de un solo carácter, sin recursión: avanza lo más
+    /// posible y cede de uno en uno. La pila no crece con el texto
+    fn repeat_chars(&self, piece: &Piece, pos: usize, cont: &dyn Fn(usize) -> bool) -> bool {
+        let limit = piece.max.map_or(self.chars.len(), |max| (pos + max).min(self.chars.len()));
+        let mut end = pos;
+        while end < limit && piece.node.matches_char(self.chars[end]) {
+            end += 1;
+        }
+        (pos + piece.min..=end).rev().any(cont)
+    }
+
+    /// Repetición voraz de un grupo, sin recursión por iteración: calcula
+    /// por niveles las posiciones alcanzables tras 1, 2, ... iteraciones y
+    /// prueba la continuación desde el nivel más profundo. Tras el mínimo,
+    /// una posición ya alcanzada no se vuelve a expandir (los grupos vacíos
+    /// no entran en bucle)
+    fn repeat(&self, piece: &Piece, pos: usize, cont: &dyn Fn(usize) -> bool) -> bool {
+        let mut seen = HashSet::new();
+        if piece.min == 0 {
+            seen.insert(pos);
+        }
+        let mut levels = vec![vec![pos]];
+
+        while piece.max.is_none_or(|max| levels.len() <= max) {
+            let count = levels.len();
+            let ends = RefCell::new(Vec::new());
+            for &start in &levels[count - 1] {
+                self.node(&piece.node, start, &|end| {
+                    ends.borrow_mut().push(end);
+                    false
+                });
+            }
+
+            let mut next = Vec::new();
+            for end in ends.into_inner() {
+                let fresh = if count >= piece.min { seen.insert(end) } else { !next.contains(&end) };
+                if fresh {
+                    next.push(end);
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            levels.push(next);
+        }
+
+        levels.iter().enumerate().rev()
+            .filter(|(count, _)| *count >= piece.min)
+            .any(|(_, positions)| positions.iter().any(|&p| cont(p)))
+    }
+
+    fn node(&self, node: &Node, pos: usize, cont: &dyn Fn(usize) -> bool) -> bool {
+        match node {
+            Node::Start => pos == 0 && cont(pos),
+            Node::End => pos == self.chars.len() && cont(pos),
+            Node::Group(alternatives) => self.alternatives(alternatives, pos, cont),
+            single => self.chars.get(pos).is_some_and(|&c| single.matches_char(c)) && cont(pos + 1),
+        }
+    }
+}
+
+impl Node {
+    /// Consume exactamente un carácter
+    fn is_single_char(&self) -> bool {
+        matches!(self, Node::Char(_) | Node::Any | Node::Class { .. })
+    }
+
+    fn matches_char(&self, c: char) -> bool {
+        match self {
+            Node::Char(expected) => c == *expected,
+            Node::Any => true,
+            Node::Class { items, negated } => items.iter().any(|item| item.matches(c)) != *negated,
+            Node::Start | Node::End | Node::Group(_) => false,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> PatternError {
+        PatternError(self.pos, message.to_string())
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Piece>>, PatternError> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Piece>, PatternError> {
+        let mut pieces = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let node = self.atom()?;
+            let (min, max) = self.quantifier()?;
+            pieces.push(Piece { node, min, max });
+        }
+        Ok(pieces)
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        let c = self.peek().ok_or_else(|| self.error("patrón incompleto"))?;
+        self.pos += 1;
+        match c {
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Start),
+            '$' => Ok(Node::End),
+            '(' => {
+                let alternatives = self.alternatives()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("falta ')'"));
+                }
+                self.pos += 1;
+                Ok(Node::Group(alternatives))
+            }
+            '[' => self.class(),
+            '\\' => match self.escape()? {
+                Ok(c) => Ok(Node::Char(c)),
+                Err(item) => Ok(Node::Class { items: vec![item], negated: false }),
+            },
+            '*' | '+' | '?' | '{' => Err(PatternError(self.pos - 1, format!("'{}' sin nada que repetir", c))),
+            c => Ok(Node::Char(c)),
+        }
+    }
+
+    /// Escape tras `\`: carácter literal o clase abreviada
+    fn escape(&mut self) -> Result<Result<char, ClassItem>, PatternError> {
+        let c = self.peek().ok_or_else(|| self.error("'\\' al final del patrón"))?;
+        self.pos += 1;
+        Ok(match c {
+            'd' => Err(ClassItem::Digit(false)),
+            'D' => Err(ClassItem::Digit(true)),
+            'w' => Err(ClassItem::Word(false)),
+            'W' => Err(ClassItem::Word(true)),
+            's' => Err(ClassItem::Space(false)),
+            'S' => Err(ClassItem::Space(true)),
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            other => Ok(other),
+        })
+    }
+
+    fn class(&mut self) -> Result<Node, PatternError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+
+        let mut items = Vec::new();
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("falta ']'"))?;
+            self.pos += 1;
+            let lo = match c {
+                ']' if !items.is_empty() => break,
+                '\\' => match self.escape()? {
+                    Ok(c) => c,
+                    Err(item) => {
+                        items.push(item);
+                        continue;
+                    }
+                },
+                c => c,
+            };
+
+            // Rango a-z (un '-' final es literal)
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&c| c != ']') {
+                self.pos += 1;
+                let hi = self.peek().ok_or_else(|| self.error("rango incompleto"))?;
+                self.pos += 1;
+                if hi < lo {
+                    return Err(self.error("rango invertido"));
+                }
+                items.push(ClassItem::Range(lo, hi));
+            } else {
+                items.push(ClassItem::Range(lo, lo));
+            }
+        }
+
+        Ok(Node::Class { items, negated })
+    }
+
+    fn quantifier(&mut self) -> Result<(usize, Option<usize>), PatternError> {
+        let quantifier = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.braces(),
+            _ => return Ok((1, Some(1))),
+        };
+        self.pos += 1;
+        Ok(quantifier)
+    }
+
+    /// `{n}`, `{n,}` o `{n,m}`
+    fn braces(&mut self) -> Result<(usize, Option<usize>), PatternError> {
+        let start = self.pos;
+        let close = self.chars[start..].iter().position(|&c| c == '}')
+            .ok_or_else(|| self.error("falta '}'"))?;
+        let body: String = self.chars[start + 1..start + close].iter().collect();
+        self.pos = start + close + 1;
+
+        let number = |s: &str| s.trim().parse::<usize>().map_err(|_| PatternError(start, format!("repetición inválida: {{{}}}", body)));
+        let (min, max) = match body.split_once(',') {
+            None => {
+                let n = number(&body)?;
+                (n, Some(n))
+            }
+            Some((min, "")) => (number(min)?, None),
+            Some((min, max)) => (number(min)?, Some(number(max)?)),
+        };
+        if max.is_some_and(|max| max < min) {
+            return Err(PatternError(start, format!("repetición inválida: {{{}}}", body)));
+        }
+        Ok((min, max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(pattern: &str, text: &'a str) -> Option<&'a str> {
+        Pattern::new(pattern).unwrap().find(text).map(|(s, e)| &text[s..e])
+    }
+
+    #[test]
+    fn test_basic_matching() {
+        assert_eq!(find(r"\d+", "tengo 42 años"), Some("42"));
+        assert_eq!(find(r"[a-c]+x", "zzabcabx!"), Some("abcabx"));
+        assert_eq!(find(r"co(lis|m)eo", "el Coliseo o el comeo"), Some("comeo"));
+        assert_eq!(find(r"^\w+", "año nuevo"), Some("año"));
+        assert_eq!(find(r"\w+$", "año nuevo"), Some("nuevo"));
+        assert_eq!(find(r"\d{2,3}", "1 1234"), Some("123"));
+        assert_eq!(find(r"[^ ]+@[^ ]+", "escribe a yo@mail.com hoy"), Some("yo@mail.com"));
+        assert_eq!(find(r"colou?r", "color"), Some("color"));
+        assert_eq!(find(r"\d+", "sin números"), None);
+    }
+
+    #[test]
+    fn test_backtracking_and_accept() {
+        // Retroceso: .* debe ceder para que "b" coincida
+        assert_eq!(find(r"a.*b", "xaxxbxxbx"), Some("axxbxxb"));
+
+        // accept rechaza el final voraz: se prueba uno más corto
+        let pattern = Pattern::new(r"\d+(km)?").unwrap();
+        let text = "5kmh";
+        assert_eq!(pattern.match_at(text, 0, |_| true), Some(3));
+        assert_eq!(pattern.match_at(text, 0, |end| end == 1), Some(1));
+        assert_eq!(pattern.match_at(text, 0, |end| end == 2), None);
+
+        // Grupos que pueden ser vacíos no entran en bucle
+        assert_eq!(find(r"(a*)*b", "aaab"), Some("aaab"));
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(Pattern::new("(ab").unwrap_err().0, 3);
+        assert!(Pattern::new("ab)").is_err());
+        assert!(Pattern::new("[a-").is_err());
+        assert!(Pattern::new("*a").is_err());
+        assert!(Pattern::new(r"a{3,1}").is_err());
+        assert!(Pattern::new(r"a\").is_err());
+        assert!(Pattern::new(r"[z-a]").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+//! # Embeddings Module
+//!
+//! Vectores de palabras (word2vec / fastText en formato texto `.vec`)
+//! como señal de contexto para las palabras que la base semántica no
+//! conoce:
+//!
+//! ```text
+//! 3 4
+//! roma 0.12 -0.40 0.33 0.05
+//! coliseo 0.10 -0.35 0.41 0.02
+//! amor -0.30 0.22 0.01 0.44
+//! ```
+//!
+//! La primera línea (número de palabras y dimensión) es opcional. Los
+//! vectores se normalizan al cargar, así el coseno es un producto punto.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Vectores de palabras normalizados
+#[derive(Debug, Clone, Default)]
+pub struct WordVectors {
+    dim: usize,
+    vectors: HashMap<String, Vec<f32>>,
+}
+
+/// Error de lectura de vectores
+#[derive(Debug)]
+pub enum EmbeddingsError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+    /// Vector de otra dimensión (palabra, dimensión, dimensión esperada)
+    DimensionMismatch(String, usize, usize),
+}
+
+impl fmt::Display for EmbeddingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbeddingsError::IoError(e) => write!(f, "IO error: {}", e),
+            EmbeddingsError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+            EmbeddingsError::DimensionMismatch(word, found, expected) => {
+                write!(f, "'{}' tiene {} dimensiones, se esperaban {}", word, found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EmbeddingsError {}
+
+impl WordVectors {
+    /// Lee un archivo `.vec`; `limit` conserva solo las primeras palabras
+    /// (los archivos de fastText vienen ordenados por frecuencia)
+    pub fn load(path: &Path, limit: Option<usize>) -> Result<Self, EmbeddingsError> {
+        let file = File::open(path).map_err(|e| EmbeddingsError::IoError(e.to_string()))?;
+        Self::from_reader(BufReader::new(file), limit)
+    }
+
+    /// Parsea vectores en texto
+    pub fn parse(content: &str) -> Result<Self, EmbeddingsError> {
+        Self::from_reader(content.as_bytes(), None)
+    }
+
+    fn from_reader<R: BufRead>(reader: R, limit: Option<usize>) -> Result<Self, EmbeddingsError> {
+        let mut vectors = Self::default();
+
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| EmbeddingsError::IoError(e.to_string()))?;
+            let mut fields = line.split_whitespace();
+            let word = match fields.next() {
+                Some(w) => w,
+                None => continue,
+            };
+            let values: Vec<&str> = fields.collect();
+
+            // Cabecera "palabras dimensión"
+            if line_no == 0 && values.len() == 1 && word.parse::<usize>().is_ok() {
+                vectors.dim = values[0].parse()
+                    .map_err(|_| EmbeddingsError::ParseError(1, "dimensión inválida".to_string()))?;
+                continue;
+            }
+
+            if limit.is_some_and(|max| vectors.len() >= max) {
+                break;
+            }
+
+            let vector = values.iter()
+                .map(|v| v.parse::<f32>())
+                .collect::<Result<Vec<f32>, _>>()
+                .map_err(|_| EmbeddingsError::ParseError(line_no + 1, format!("valor no numérico en '{}'", word)))?;
+
+            vectors.insert(word, vector)
+                .map_err(|e| EmbeddingsError::ParseError(line_no + 1, e.to_string()))?;
+        }
+
+        Ok(vectors)
+    }
+
+    /// Añade el vector de una palabra, normalizado. Si la palabra ya tiene
+    /// vector (también con otras mayúsculas: "Roma" tras "roma") se conserva
+    /// el primero, que en los archivos ordenados es el más frecuente.
+    /// Retorna si se añadió
+    pub fn insert(&mut self, word: &str, mut vector: Vec<f32>) -> Result<bool, EmbeddingsError> {
+        if self.dim == 0 {
+            self.dim = vector.len();
+        }
+        if vector.len() != self.dim {
+            return Err(EmbeddingsError::DimensionMismatch(word.to_string(), vector.len(), self.dim));
+        }
+
+        let key = word.to_lowercase();
+        if self.vectors.contains_key(&key) {
+            return Ok(false);
+        }
+
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+        self.vectors.insert(key, vector);
+        Ok(true)
+    }
+
+    /// Vector normalizado de una palabra
+    pub fn vector(&self, word: &str) -> Option<&[f32]> {
+        self.vectors.get(&word.to_lowercase()).map(|v| v.as_slice())
+    }
+
+    /// Dimensión de los vectores
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Número de palabras con vector
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    /// Coseno entre dos palabras (None si alguna no tiene vector)
+    pub fn cosine(&self, a: &str, b: &str) -> Option<f64> {
+        Some(dot(self.vector(a)?, self.vector(b)?))
+    }
+
+    /// Centroide normalizado de las palabras con vector
+    pub fn centroid(&self, words: &[String]) -> Option<Vec<f32>> {
+        let mut sum = vec![0.0_f32; self.dim];
+        let mut found = false;
+
+        for vector in words.iter().filter_map(|w| self.vector(w)) {
+            sum.iter_mut().zip(vector).for_each(|(s, x)| *s += x);
+            found = true;
+        }
+
+        let norm = sum.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if !found || norm == 0.0 {
+            return None;
+        }
+        sum.iter_mut().for_each(|x| *x /= norm);
+        Some(sum)
+    }
+
+    /// Score de contexto (0.0 - 1.0): coseno entre la palabra y el centroide
+    /// del contexto llevado a [0, 1]; ortogonal = 0.5 (neutral)
+    pub fn context_score(&self, word: &str, context: &[String]) -> Option<f64> {
+        let context: Vec<String> = context.iter()
+            .filter(|w| !w.eq_ignore_ascii_case(word))
+            .cloned()
+            .collect();
+        let centroid = self.centroid(&context)?;
+        let cosine = dot(self.vector(word)?, &centroid);
+        Some(((cosine + 1.0) / 2.0).clamp(0.0, 1.0))
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (*x as f64) * (*y as f64)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS: &str = "4 3\nroma 0.9 0.1 0.0\ncoliseo 0.8 0.2 0.0\nromano 1.0 0.0 0.1\namor 0.0 0.1 0.9\n";
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_and_cosine() {
+        let vectors = WordVectors::parse(VECTORS).unwrap();
+        assert_eq!(vectors.len(), 4);
+        assert_eq!(vectors.dim(), 3);
+
+        assert!((vectors.cosine("roma", "roma").unwrap() - 1.0).abs() < 1e-6);
+        assert!(vectors.cosine("roma", "coliseo").unwrap() > 0.9);
+        assert!(vectors.cosine("roma", "amor").unwrap() < 0.2);
+        assert_eq!(vectors.cosine("roma", "paris"), None);
+
+        // Sin cabecera también es válido
+        assert_eq!(WordVectors::parse("a 1 0\nb 0 1\n").unwrap().dim(), 2);
+    }
+
+    #[test]
+    fn test_context_score() {
+        let vectors = WordVectors::parse(VECTORS).unwrap();
+        let context = words(&["visité", "el", "coliseo", "romano"]);
+
+        let roma = vectors.context_score("roma", &context).unwrap();
+        let amor = vectors.context_score("amor", &context).unwrap();
+        assert!(roma > 0.9);
+        assert!(amor < 0.6);
+        assert_eq!(vectors.context_score("smor", &context), None);
+        assert_eq!(vectors.context_score("roma", &words(&["visité"])), None);
+    }
+
+    #[test]
+    fn test_errors_and_limit() {
+        assert!(matches!(WordVectors::parse("2 3\na 1 0 0\nb 1 0\n"), Err(EmbeddingsError::ParseError(3, _))));
+        assert!(matches!(WordVectors::parse("a 1 x\n"), Err(EmbeddingsError::ParseError(1, _))));
+
+        let limited = WordVectors::from_reader(VECTORS.as_bytes(), Some(2)).unwrap();
+        assert_eq!(limited.len(), 2);
+        assert!(limited.vector("coliseo").is_some());
+        assert!(limited.vector("amor").is_none());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut vectors = WordVectors::default();
+        assert!(vectors.insert("roma", vec![1.0, 0.0]).unwrap());
+        assert!(matches!(
+            vectors.insert("paris", vec![1.0, 0.0, 0.0]),
+            Err(EmbeddingsError::DimensionMismatch(_, 3, 2))
+        ));
+        assert!(vectors.vector("paris").is_none());
+
+        // "Roma" después de "roma" no reemplaza su vector
+        assert!(!vectors.insert("Roma", vec![0.0, 1.0]).unwrap());
+        assert_eq!(vectors.vector("roma"), Some(&[1.0_f32, 0.0][..]));
+
+        let parsed = WordVectors::parse("roma 1 0\nRoma 0 1\namor 0 1\n").unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.cosine("roma", "amor").unwrap() < 1e-6);
+    }
+}
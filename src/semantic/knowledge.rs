@@ -0,0 +1,606 @@
+//! # Knowledge Module
+//!
+//! Formato declarativo para la base semántica: hechos estilo Prolog con
+//! argumentos opcionales por nombre.
+//!
+//! ```text
+//! word(roma, place(city, region=italia, country=italia), tags=[historia]).
+//! theme(viajes, "Viajes y geografía", compatible=[any_place], keywords=[viajé], prior=1.5).
+//! rule(viajes, any_place, 0.95).
+//! relation(anfiteatro, edificio, hyponym, 0.9).
+//! ```
+//!
+//! La base por defecto (`data/semantic/base.kb`) va embebida en el binario.
+//! Los errores de sintaxis y de validación indican la línea del hecho.
+
+use std::fmt;
+use super::{
+    ActionType, CategoryMatcher, CompatibilityRule, ObjectType, PlaceType, RelationType,
+    SemanticCategory, SemanticEntry, SemanticRelation, ThemeInfo, TimeType, Valence,
+};
+
+/// Base de conocimiento por defecto
+pub(super) const EMBEDDED_KNOWLEDGE: &str = include_str!("../../data/semantic/base.kb");
+
+/// Hecho declarado en un archivo de conocimiento
+#[derive(Debug, Clone)]
+pub enum Fact {
+    Word(SemanticEntry),
+    Theme(ThemeInfo),
+    Rule(CompatibilityRule),
+    Relation(SemanticRelation),
+}
+
+/// Error de lectura o validación de conocimiento
+#[derive(Debug)]
+pub enum KnowledgeError {
+    IoError(String),
+    /// Sintaxis inválida (línea desde 1, descripción)
+    ParseError(usize, String),
+    /// Hecho bien formado pero inválido: categoría desconocida, score fuera
+    /// de rango, regla de un tema no declarado...
+    ValidationError(usize, String),
+}
+
+impl fmt::Display for KnowledgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KnowledgeError::IoError(e) => write!(f, "IO error: {}", e),
+            KnowledgeError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+            KnowledgeError::ValidationError(line, e) => write!(f, "Validation error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for KnowledgeError {}
+
+/// Parsea un archivo de conocimiento: hechos con su línea de inicio
+pub fn parse_knowledge(content: &str) -> Result<Vec<(usize, Fact)>, KnowledgeError> {
+    let tokens = lex(content)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let mut facts = Vec::new();
+
+    while !parser.at_end() {
+        let line = parser.line();
+        let term = parser.term()?;
+        parser.expect(&Token::Dot, "'.' al final del hecho")?;
+
+        let fact = to_fact(&term).map_err(|e| KnowledgeError::ValidationError(line, e))?;
+        facts.push((line, fact));
+    }
+
+    Ok(facts)
+}
+
+// ============================================================================
+// Léxico y términos
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Atom(String),
+    Number(f64),
+    Str(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Eq,
+    Dot,
+}
+
+/// Término: átomo, número, cadena, lista o compuesto con argumentos
+/// posicionales y nombrados (`place(city, region=italia)`)
+#[derive(Debug, Clone)]
+enum Term {
+    Atom(String),
+    Number(f64),
+    Str(String),
+    List(Vec<Term>),
+    Compound(String, Vec<(Option<String>, Term)>),
+}
+
+fn lex(content: &str) -> Result<Vec<(Token, usize)>, KnowledgeError> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '\n' => { line += 1; chars.next(); }
+            c if c.is_whitespace() => { chars.next(); }
+            '%' => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '(' | ')' | '[' | ']' | ',' | '=' | '.' => {
+                chars.next();
+                let token = match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    ',' => Token::Comma,
+                    '=' => Token::Eq,
+                    _ => Token::Dot,
+                };
+                tokens.push((token, line));
+            }
+            '"' => {
+                chars.next();
+                let start = line;
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\n') | None => {
+                            return Err(KnowledgeError::ParseError(start, "cadena sin cerrar".to_string()));
+                        }
+                        Some(c) => text.push(c),
+                    }
+                }
+                tokens.push((Token::Str(text), start));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut text = String::new();
+                text.push(c);
+                chars.next();
+                while let Some(&c) = chars.peek() {
+                    // "0.98." : el punto es decimal solo si le sigue un dígito
+                    let decimal = c == '.' && chars.clone().nth(1).is_some_and(|d| d.is_ascii_digit());
+                    if c.is_ascii_digit() || decimal {
+                        text.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let value = text.parse::<f64>()
+                    .map_err(|_| KnowledgeError::ParseError(line, format!("número inválido: {}", text)))?;
+                tokens.push((Token::Number(value), line));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        text.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push((Token::Atom(text.to_lowercase()), line));
+            }
+            other => {
+                return Err(KnowledgeError::ParseError(line, format!("carácter inesperado '{}'", other)));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|(_, line)| *line)
+            .unwrap_or(1)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, expected: &str) -> KnowledgeError {
+        let found = match self.peek() {
+            Some(token) => format!("{:?}", token),
+            None => "fin de archivo".to_string(),
+        };
+        KnowledgeError::ParseError(self.line(), format!("se esperaba {}, se encontró {}", expected, found))
+    }
+
+    fn expect(&mut self, token: &Token, expected: &str) -> Result<(), KnowledgeError> {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, KnowledgeError> {
+        match self.peek().cloned() {
+            Some(Token::Number(n)) => { self.pos += 1; Ok(Term::Number(n)) }
+            Some(Token::Str(s)) => { self.pos += 1; Ok(Term::Str(s)) }
+            Some(Token::LBracket) => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.peek() != Some(&Token::RBracket) {
+                    loop {
+                        items.push(self.term()?);
+                        if self.peek() != Some(&Token::Comma) {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                self.expect(&Token::RBracket, "']'")?;
+                Ok(Term::List(items))
+            }
+            Some(Token::Atom(name)) => {
+                self.pos += 1;
+                if self.peek() != Some(&Token::LParen) {
+                    return Ok(Term::Atom(name));
+                }
+                self.pos += 1;
+
+                let mut args = Vec::new();
+                loop {
+                    // Argumento nombrado: clave=valor
+                    let named = matches!(self.peek(), Some(Token::Atom(_)))
+                        && self.tokens.get(self.pos + 1).map(|(t, _)| t) == Some(&Token::Eq);
+                    let key = if named {
+                        let key = match self.next() {
+                            Some(Token::Atom(key)) => key,
+                            _ => unreachable!(),
+                        };
+                        self.pos += 1;
+                        Some(key)
+                    } else {
+                        None
+                    };
+                    args.push((key, self.term()?));
+
+                    if self.peek() != Some(&Token::Comma) {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                self.expect(&Token::RParen, "')'")?;
+                Ok(Term::Compound(name, args))
+            }
+            _ => Err(self.error("un término")),
+        }
+    }
+}
+
+// ============================================================================
+// Términos → hechos
+// ============================================================================
+
+impl Term {
+    fn name(&self) -> &str {
+        match self {
+            Term::Atom(name) | Term::Compound(name, _) => name,
+            _ => "",
+        }
+    }
+
+    fn positional(&self) -> Vec<&Term> {
+        match self {
+            Term::Compound(_, args) => args.iter().filter(|(k, _)| k.is_none()).map(|(_, t)| t).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn named(&self, key: &str) -> Option<&Term> {
+        match self {
+            Term::Compound(_, args) => args.iter()
+                .find(|(k, _)| k.as_deref() == Some(key))
+                .map(|(_, t)| t),
+            _ => None,
+        }
+    }
+
+    /// Rechaza argumentos nombrados no previstos (errores de escritura)
+    fn check_keys(&self, allowed: &[&str]) -> Result<(), String> {
+        if let Term::Compound(name, args) = self {
+            for key in args.iter().filter_map(|(k, _)| k.as_deref()) {
+                if !allowed.contains(&key) {
+                    return Err(format!("argumento '{}' no válido en {}", key, name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn as_text(&self) -> Result<String, String> {
+        match self {
+            Term::Atom(s) | Term::Str(s) => Ok(s.clone()),
+            other => Err(format!("se esperaba un átomo, se encontró {:?}", other)),
+        }
+    }
+
+    fn as_list(&self) -> Result<&[Term], String> {
+        match self {
+            Term::List(items) => Ok(items),
+            other => Err(format!("se esperaba una lista, se encontró {:?}", other)),
+        }
+    }
+}
+
+fn text_list(term: Option<&Term>) -> Result<Vec<String>, String> {
+    match term {
+        Some(t) => t.as_list()?.iter().map(Term::as_text).collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn arity(term: &Term, n: usize) -> Result<Vec<&Term>, String> {
+    let args = term.positional();
+    if args.len() == n {
+        Ok(args)
+    } else {
+        Err(format!("{} espera {} argumentos posicionales, tiene {}", term.name(), n, args.len()))
+    }
+}
+
+fn to_fact(term: &Term) -> Result<Fact, String> {
+    match term.name() {
+        "word" => {
+            term.check_keys(&["sub", "tags", "related"])?;
+            let args = arity(term, 2)?;
+            Ok(Fact::Word(SemanticEntry {
+                word: args[0].as_text()?.to_lowercase(),
+                category: parse_category(args[1])?,
+                subcategory: term.named("sub").map(Term::as_text).transpose()?,
+                tags: text_list(term.named("tags"))?,
+                related: text_list(term.named("related"))?,
+            }))
+        }
+        "theme" => {
+            term.check_keys(&["compatible", "keywords", "prior"])?;
+            let args = term.positional();
+            if args.is_empty() || args.len() > 2 {
+                return Err("theme espera nombre y descripción opcional".to_string());
+            }
+            let compatible = match term.named("compatible") {
+                Some(t) => t.as_list()?.iter().map(parse_matcher).collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            };
+            let prior = match term.named("prior") {
+                None => None,
+                Some(Term::Number(n)) if *n > 0.0 => Some(*n),
+                Some(other) => return Err(format!("prior debe ser un número positivo, se encontró {:?}", other)),
+            };
+            Ok(Fact::Theme(ThemeInfo {
+                name: args[0].as_text()?,
+                description: args.get(1).map(|t| t.as_text()).transpose()?.unwrap_or_default(),
+                compatible_categories: compatible,
+                keywords: text_list(term.named("keywords"))?,
+                prior,
+            }))
+        }
+        "rule" => {
+            term.check_keys(&[])?;
+            let args = arity(term, 3)?;
+            let score = match args[2] {
+                Term::Number(n) if (0.0..=1.0).contains(n) => *n,
+                Term::Number(n) => return Err(format!("score {} fuera de [0, 1]", n)),
+                other => return Err(format!("se esperaba un score, se encontró {:?}", other)),
+            };
+            Ok(Fact::Rule(CompatibilityRule {
+                theme: args[0].as_text()?,
+                matcher: parse_matcher(args[1])?,
+                score,
+            }))
+        }
+        "relation" => {
+            term.check_keys(&[])?;
+            let args = term.positional();
+            if args.len() != 3 && args.len() != 4 {
+                return Err("relation espera palabra, palabra, tipo y fuerza opcional".to_string());
+            }
+            let strength = match args.get(3) {
+                None => 1.0,
+                Some(Term::Number(n)) if (0.0..=1.0).contains(n) => *n,
+                Some(Term::Number(n)) => return Err(format!("fuerza {} fuera de [0, 1]", n)),
+                Some(other) => return Err(format!("se esperaba una fuerza, se encontró {:?}", other)),
+            };
+            Ok(Fact::Relation(SemanticRelation {
+                word1: args[0].as_text()?,
+                word2: args[1].as_text()?,
+                relation_type: parse_relation_type(&args[2].as_text()?)?,
+                strength,
+            }))
+        }
+        other => Err(format!("hecho desconocido: {}", other)),
+    }
+}
+
+fn parse_category(term: &Term) -> Result<SemanticCategory, String> {
+    let first = term.positional().first().map(|t| t.as_text()).transpose()?;
+    let variant = |kind: &str| first.clone().ok_or_else(|| format!("{} requiere un tipo", kind));
+
+    let category = match term.name() {
+        "place" => {
+            term.check_keys(&["region", "country"])?;
+            SemanticCategory::Place {
+                place_type: match variant("place")?.as_str() {
+                    "city" => PlaceType::City,
+                    "country" => PlaceType::Country,
+                    "building" => PlaceType::Building,
+                    "monument" => PlaceType::Monument,
+                    "natural_feature" => PlaceType::NaturalFeature,
+                    "region" => PlaceType::Region,
+                    "generic" => PlaceType::Generic,
+                    other => return Err(format!("tipo de lugar desconocido: {}", other)),
+                },
+                region: term.named("region").map(Term::as_text).transpose()?,
+                country: term.named("country").map(Term::as_text).transpose()?,
+            }
+        }
+        "person" => {
+            term.check_keys(&["role"])?;
+            SemanticCategory::Person { role: term.named("role").map(Term::as_text).transpose()? }
+        }
+        "object" => SemanticCategory::Object { object_type: parse_object_type(&variant("object")?)? },
+        "emotion" => SemanticCategory::Emotion { valence: parse_valence(&variant("emotion")?)? },
+        "concept" => {
+            term.check_keys(&["domain"])?;
+            SemanticCategory::Concept { domain: term.named("domain").map(Term::as_text).transpose()? }
+        }
+        "action" => SemanticCategory::Action {
+            action_type: match variant("action")?.as_str() {
+                "physical" => ActionType::Physical,
+                "mental" => ActionType::Mental,
+                "social" => ActionType::Social,
+                "movement" => ActionType::Movement,
+                other => return Err(format!("tipo de acción desconocido: {}", other)),
+            },
+        },
+        "time" => SemanticCategory::Time {
+            time_type: match variant("time")?.as_str() {
+                "duration" => TimeType::Duration,
+                "point" => TimeType::Point,
+                "frequency" => TimeType::Frequency,
+                "season" => TimeType::Season,
+                other => return Err(format!("tipo de tiempo desconocido: {}", other)),
+            },
+        },
+        "quantity" => SemanticCategory::Quantity,
+        "quality" => SemanticCategory::Quality,
+        "unknown" => SemanticCategory::Unknown,
+        other => return Err(format!("categoría desconocida: {}", other)),
+    };
+
+    Ok(category)
+}
+
+fn parse_matcher(term: &Term) -> Result<CategoryMatcher, String> {
+    let arg = || -> Result<String, String> {
+        arity(term, 1)?[0].as_text()
+    };
+
+    match term.name() {
+        "any" => Ok(CategoryMatcher::Any),
+        "any_place" => Ok(CategoryMatcher::AnyPlace),
+        "place_in" => Ok(CategoryMatcher::PlaceInRegion(arg()?)),
+        "object" => Ok(CategoryMatcher::ObjectOfType(parse_object_type(&arg()?)?)),
+        "emotion" => Ok(CategoryMatcher::EmotionWithValence(parse_valence(&arg()?)?)),
+        "concept_in" => Ok(CategoryMatcher::ConceptInDomain(arg()?)),
+        other => Err(format!("matcher desconocido: {}", other)),
+    }
+}
+
+fn parse_relation_type(value: &str) -> Result<RelationType, String> {
+    match value {
+        "hyponym" => Ok(RelationType::Hyponym),
+        "hypernym" => Ok(RelationType::Hypernym),
+        "synonym" => Ok(RelationType::Synonym),
+        "antonym" => Ok(RelationType::Antonym),
+        "meronym" => Ok(RelationType::Meronym),
+        "holonym" => Ok(RelationType::Holonym),
+        "related" => Ok(RelationType::Related),
+        other => Err(format!("tipo de relación desconocido: {}", other)),
+    }
+}
+
+fn parse_object_type(value: &str) -> Result<ObjectType, String> {
+    match value {
+        "food" => Ok(ObjectType::Food),
+        "plant" => Ok(ObjectType::Plant),
+        "animal" => Ok(ObjectType::Animal),
+        "artifact" => Ok(ObjectType::Artifact),
+        "natural" => Ok(ObjectType::Natural),
+        "abstract" => Ok(ObjectType::Abstract),
+        other => Err(format!("tipo de objeto desconocido: {}", other)),
+    }
+}
+
+fn parse_valence(value: &str) -> Result<Valence, String> {
+    match value {
+        "positive" => Ok(Valence::Positive),
+        "negative" => Ok(Valence::Negative),
+        "neutral" => Ok(Valence::Neutral),
+        other => Err(format!("valencia desconocida: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::SemanticDB;
+
+    #[test]
+    fn test_parse_facts() {
+        let facts = parse_knowledge(
+            "% comentario\nword(tiburon, object(animal), tags=[mar]).\n\nrule(naturaleza, object(animal),\n  0.85).\n"
+        ).unwrap();
+
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[0].0, 2);
+        assert!(matches!(&facts[0].1, Fact::Word(e)
+            if e.category == SemanticCategory::Object { object_type: ObjectType::Animal } && e.tags == ["mar"]));
+        assert_eq!(facts[1].0, 4);
+        assert!(matches!(&facts[1].1, Fact::Rule(r) if r.score == 0.85));
+    }
+
+    #[test]
+    fn test_errors_have_line_numbers() {
+        let err = |src: &str| parse_knowledge(src).unwrap_err();
+
+        assert!(matches!(err("word(a, quality).\nword(b, quality)\n"), KnowledgeError::ParseError(2, _)));
+        assert!(matches!(err("\nword(a, color(azul))."), KnowledgeError::ValidationError(2, _)));
+        assert!(matches!(err("rule(viajes, any_place, 1.5)."), KnowledgeError::ValidationError(1, _)));
+        assert!(matches!(err("word(a, quality, tgs=[x])."), KnowledgeError::ValidationError(1, _)));
+        assert!(matches!(err("theme(viajes, prior=0)."), KnowledgeError::ValidationError(1, _)));
+        assert!(matches!(err("theme(t, \"sin cerrar)."), KnowledgeError::ParseError(1, _)));
+
+        // Regla de un tema no declarado en la base ni en el archivo
+        let mut db = SemanticDB::new();
+        let result = db.load_knowledge("\n\nrule(medicina, any, 0.9).");
+        assert!(matches!(result, Err(KnowledgeError::ValidationError(3, _))));
+    }
+
+    #[test]
+    fn test_embedded_matches_defaults() {
+        let facts = parse_knowledge(EMBEDDED_KNOWLEDGE).unwrap();
+        let themes = facts.iter().filter(|(_, f)| matches!(f, Fact::Theme(_))).count();
+        assert_eq!(themes, 5);
+
+        let db = SemanticDB::new();
+        assert_eq!(db.word_count(), 13);
+        assert!(db.compatibility_score("roma", "viajes") > 0.9);
+    }
+
+    #[test]
+    fn test_merge_domain_files() {
+        let mut db = SemanticDB::new();
+        let medicina = "
+            theme(medicina, \"Fármacos y anestesia\", compatible=[concept_in(farmacologia)], keywords=[anestesia]).
+            word(propofol, concept(domain=farmacologia), tags=[anestesia]).
+            rule(medicina, concept_in(farmacologia), 0.95).
+        ";
+        assert_eq!(db.load_knowledge(medicina).unwrap(), 3);
+
+        // Otro archivo amplía un tema existente y ajusta una regla
+        let viajes = "theme(viajes, keywords=[aeropuerto]).\nrule(viajes, emotion(positive), 0.4).";
+        db.load_knowledge(viajes).unwrap();
+
+        let context = vec!["anestesia".to_string()];
+        assert_eq!(db.infer_theme(&context).unwrap().0, "medicina");
+        assert!(db.compatibility_score("propofol", "medicina") > 0.9);
+        assert_eq!(db.infer_theme(&["aeropuerto".to_string()]).unwrap().0, "viajes");
+        assert_eq!(db.infer_theme(&["visité".to_string()]).unwrap().0, "viajes");
+        assert_eq!(db.compatibility_score("amor", "viajes"), 0.4);
+    }
+}
@@ -7,7 +7,16 @@
 //! - "Roma" → Lugar(Italia) → compatible con tema "arquitectura_romana"
 //! - "amor" → Emoción(positiva) → incompatible con tema "arquitectura"
 
+pub mod knowledge;
+pub mod wordnet;
+pub mod embeddings;
+
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use knowledge::{Fact, KnowledgeError};
+use wordnet::{ImportStats, WordNet};
+use embeddings::WordVectors;
 
 /// Base de datos semántica
 #[derive(Debug, Clone)]
@@ -18,12 +27,17 @@ pub struct SemanticDB {
     /// Temas conocidos
     themes: HashMap<String, ThemeInfo>,
 
-    /// Relaciones semánticas (hiponimia, sinonimia, etc.) - para expansión futura
-    #[allow(dead_code)]
+    /// Relaciones semánticas (hiponimia, sinonimia, etc.)
     relations: Vec<SemanticRelation>,
 
+    /// Palabra -> índices de sus relaciones (en ambos extremos)
+    relation_index: HashMap<String, Vec<usize>>,
+
     /// Reglas de compatibilidad tema-categoría
     compatibility_rules: Vec<CompatibilityRule>,
+
+    /// Vectores de palabras (opcionales) para palabras sin conocimiento
+    vectors: Option<WordVectors>,
 }
 
 /// Entrada semántica para una palabra
@@ -135,10 +149,12 @@ pub struct ThemeInfo {
     pub compatible_categories: Vec<CategoryMatcher>,
     /// Palabras clave que sugieren este tema
     pub keywords: Vec<String>,
+    /// Peso a priori del tema (relativo; 1.0 si no se declara)
+    pub prior: Option<f64>,
 }
 
 /// Matcher para categorías compatibles
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CategoryMatcher {
     /// Lugar con región específica
     PlaceInRegion(String),
@@ -207,336 +223,252 @@ pub struct SemanticAnalysis {
 }
 
 impl SemanticDB {
-    /// Crea base de datos con vocabulario inicial
+    /// Crea base de datos con vocabulario inicial (`data/semantic/base.kb`)
     pub fn new() -> Self {
-        let mut db = Self {
+        let mut db = Self::empty();
+        db.load_knowledge(knowledge::EMBEDDED_KNOWLEDGE)
+            .expect("base de conocimiento embebida mal formada");
+        db
+    }
+
+    /// Crea base de datos vacía (sin temas ni vocabulario)
+    pub fn empty() -> Self {
+        Self {
             words: HashMap::new(),
             themes: HashMap::new(),
             relations: Vec::new(),
+            relation_index: HashMap::new(),
             compatibility_rules: Vec::new(),
-        };
-
-        db.load_base_vocabulary();
-        db.load_themes();
-        db.load_compatibility_rules();
-
-        db
+            vectors: None,
+        }
     }
 
-    /// Carga vocabulario base
-    fn load_base_vocabulary(&mut self) {
-        // === LUGARES ===
-        self.add_word(SemanticEntry {
-            word: "roma".to_string(),
-            category: SemanticCategory::Place {
-                place_type: PlaceType::City,
-                region: Some("italia".to_string()),
-                country: Some("italia".to_string()),
-            },
-            subcategory: Some("capital_historica".to_string()),
-            tags: vec!["arquitectura".to_string(), "historia".to_string(), "imperio_romano".to_string()],
-            related: vec!["coliseo".to_string(), "vaticano".to_string(), "italia".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "coliseo".to_string(),
-            category: SemanticCategory::Place {
-                place_type: PlaceType::Monument,
-                region: Some("italia".to_string()),
-                country: Some("italia".to_string()),
-            },
-            subcategory: Some("anfiteatro".to_string()),
-            tags: vec!["arquitectura".to_string(), "romano".to_string(), "monumento".to_string()],
-            related: vec!["roma".to_string(), "gladiador".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "paris".to_string(),
-            category: SemanticCategory::Place {
-                place_type: PlaceType::City,
-                region: Some("francia".to_string()),
-                country: Some("francia".to_string()),
-            },
-            subcategory: Some("capital".to_string()),
-            tags: vec!["romantico".to_string(), "arte".to_string()],
-            related: vec!["torre_eiffel".to_string(), "louvre".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "madrid".to_string(),
-            category: SemanticCategory::Place {
-                place_type: PlaceType::City,
-                region: Some("espana".to_string()),
-                country: Some("espana".to_string()),
-            },
-            subcategory: Some("capital".to_string()),
-            tags: vec!["espana".to_string()],
-            related: vec!["prado".to_string()],
-        });
-
-        // === EMOCIONES ===
-        self.add_word(SemanticEntry {
-            word: "amor".to_string(),
-            category: SemanticCategory::Emotion {
-                valence: Valence::Positive,
-            },
-            subcategory: Some("afecto".to_string()),
-            tags: vec!["sentimiento".to_string(), "romantico".to_string()],
-            related: vec!["carino".to_string(), "querer".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "odio".to_string(),
-            category: SemanticCategory::Emotion {
-                valence: Valence::Negative,
-            },
-            subcategory: Some("aversion".to_string()),
-            tags: vec!["sentimiento".to_string(), "negativo".to_string()],
-            related: vec!["rencor".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "paz".to_string(),
-            category: SemanticCategory::Concept {
-                domain: Some("estado_social".to_string()),
-            },
-            subcategory: None,
-            tags: vec!["positivo".to_string(), "armonia".to_string()],
-            related: vec!["tranquilidad".to_string()],
-        });
-
-        // === OBJETOS ===
-        self.add_word(SemanticEntry {
-            word: "ramo".to_string(),
-            category: SemanticCategory::Object {
-                object_type: ObjectType::Plant,
-            },
-            subcategory: Some("flores".to_string()),
-            tags: vec!["naturaleza".to_string(), "regalo".to_string()],
-            related: vec!["flor".to_string(), "rosa".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "mora".to_string(),
-            category: SemanticCategory::Object {
-                object_type: ObjectType::Food,
-            },
-            subcategory: Some("fruta".to_string()),
-            tags: vec!["comida".to_string(), "naturaleza".to_string()],
-            related: vec!["fruta".to_string(), "zarzamora".to_string()],
-        });
-
-        self.add_word(SemanticEntry {
-            word: "casa".to_string(),
-            category: SemanticCategory::Place {
-                place_type: PlaceType::Building,
-                region: None,
-                country: None,
-            },
-            subcategory: Some("vivienda".to_string()),
-            tags: vec!["edificio".to_string(), "hogar".to_string()],
-            related: vec!["hogar".to_string(), "edificio".to_string()],
-        });
+    /// Carga un archivo de conocimiento sobre la base actual
+    pub fn load_knowledge_file(&mut self, path: &Path) -> Result<usize, KnowledgeError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| KnowledgeError::IoError(e.to_string()))?;
+        self.load_knowledge(&content)
+    }
 
-        // === PERSONAS ===
-        self.add_word(SemanticEntry {
-            word: "rosita".to_string(),
-            category: SemanticCategory::Person {
-                role: None,
-            },
-            subcategory: Some("nombre_propio".to_string()),
-            tags: vec!["femenino".to_string()],
-            related: vec![],
-        });
+    /// Carga hechos declarados en texto y devuelve cuántos se aplicaron.
+    /// Si algún hecho es inválido no se aplica ninguno.
+    ///
+    /// - `word`: reemplaza la entrada de la palabra
+    /// - `theme`: crea el tema o le añade keywords y categorías compatibles
+    /// - `rule`: reemplaza el score de una regla con el mismo tema y matcher
+    /// - `relation`: añade una relación entre dos palabras
+    pub fn load_knowledge(&mut self, content: &str) -> Result<usize, KnowledgeError> {
+        let facts = knowledge::parse_knowledge(content)?;
+
+        // Las reglas solo pueden referirse a temas declarados
+        for (line, fact) in &facts {
+            if let Fact::Rule(rule) = fact {
+                let declared = self.themes.contains_key(&rule.theme)
+                    || facts.iter().any(|(_, f)| matches!(f, Fact::Theme(t) if t.name == rule.theme));
+                if !declared {
+                    return Err(KnowledgeError::ValidationError(*line, format!("tema no declarado: {}", rule.theme)));
+                }
+            }
+        }
 
-        // === CUALIDADES ===
-        self.add_word(SemanticEntry {
-            word: "azul".to_string(),
-            category: SemanticCategory::Quality,
-            subcategory: Some("color".to_string()),
-            tags: vec!["color".to_string(), "frio".to_string()],
-            related: vec!["celeste".to_string(), "marino".to_string()],
-        });
+        let count = facts.len();
+        for (_, fact) in facts {
+            match fact {
+                Fact::Word(entry) => self.add_word(entry),
+                Fact::Theme(theme) => self.add_theme(theme),
+                Fact::Rule(rule) => self.add_rule(rule),
+                Fact::Relation(relation) => self.add_relation(relation),
+            }
+        }
 
-        self.add_word(SemanticEntry {
-            word: "romano".to_string(),
-            category: SemanticCategory::Quality,
-            subcategory: Some("gentilicio".to_string()),
-            tags: vec!["roma".to_string(), "italia".to_string(), "antiguo".to_string()],
-            related: vec!["roma".to_string(), "imperio".to_string()],
-        });
+        Ok(count)
     }
 
-    /// Carga temas
-    fn load_themes(&mut self) {
-        self.themes.insert("arquitectura_romana".to_string(), ThemeInfo {
-            name: "arquitectura_romana".to_string(),
-            description: "Arquitectura y monumentos del Imperio Romano".to_string(),
-            compatible_categories: vec![
-                CategoryMatcher::PlaceInRegion("italia".to_string()),
-                CategoryMatcher::ConceptInDomain("historia".to_string()),
-            ],
-            keywords: vec![
-                "coliseo".to_string(),
-                "romano".to_string(),
-                "roma".to_string(),
-                "imperio".to_string(),
-                "gladiador".to_string(),
-                "anfiteatro".to_string(),
-            ],
-        });
-
-        self.themes.insert("romance".to_string(), ThemeInfo {
-            name: "romance".to_string(),
-            description: "Temas románticos y emocionales".to_string(),
-            compatible_categories: vec![
-                CategoryMatcher::EmotionWithValence(Valence::Positive),
-                CategoryMatcher::ConceptInDomain("sentimiento".to_string()),
-            ],
-            keywords: vec![
-                "amor".to_string(),
-                "querer".to_string(),
-                "corazon".to_string(),
-                "romantico".to_string(),
-            ],
-        });
+    /// Importa una red léxica tipo WordNet: palabras con categoría según su
+    /// archivo lexicográfico y relaciones entre lemas. Las palabras ya
+    /// presentes conservan su entrada
+    pub fn import_wordnet(&mut self, wordnet: &WordNet) -> ImportStats {
+        let (entries, relations) = wordnet.to_semantic();
+        let mut stats = ImportStats::default();
+
+        for entry in entries {
+            if !self.words.contains_key(&entry.word) {
+                self.add_word(entry);
+                stats.words += 1;
+            }
+        }
 
-        self.themes.insert("naturaleza".to_string(), ThemeInfo {
-            name: "naturaleza".to_string(),
-            description: "Flora, fauna y elementos naturales".to_string(),
-            compatible_categories: vec![
-                CategoryMatcher::ObjectOfType(ObjectType::Plant),
-                CategoryMatcher::ObjectOfType(ObjectType::Animal),
-                CategoryMatcher::ObjectOfType(ObjectType::Natural),
-            ],
-            keywords: vec![
-                "flor".to_string(),
-                "arbol".to_string(),
-                "rio".to_string(),
-                "montana".to_string(),
-            ],
-        });
+        stats.relations = relations.len();
+        for relation in relations {
+            self.add_relation(relation);
+        }
 
-        self.themes.insert("hogar".to_string(), ThemeInfo {
-            name: "hogar".to_string(),
-            description: "Casa, familia, vida doméstica".to_string(),
-            compatible_categories: vec![
-                CategoryMatcher::AnyPlace,
-                CategoryMatcher::Any,
-            ],
-            keywords: vec![
-                "casa".to_string(),
-                "familia".to_string(),
-                "hogar".to_string(),
-            ],
-        });
+        stats
     }
 
-    /// Carga reglas de compatibilidad
-    fn load_compatibility_rules(&mut self) {
-        // === ARQUITECTURA ROMANA ===
+    /// Instala vectores de palabras como señal de contexto
+    pub fn set_vectors(&mut self, vectors: WordVectors) {
+        self.vectors = Some(vectors);
+    }
 
-        // Roma y lugares italianos son MUY compatibles con arquitectura romana
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "arquitectura_romana".to_string(),
-            matcher: CategoryMatcher::PlaceInRegion("italia".to_string()),
-            score: 0.98,  // Muy alto - Roma es perfecto para contexto romano
-        });
+    /// Vectores instalados
+    pub fn vectors(&self) -> Option<&WordVectors> {
+        self.vectors.as_ref()
+    }
 
-        // Emociones son INCOMPATIBLES con arquitectura romana
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "arquitectura_romana".to_string(),
-            matcher: CategoryMatcher::EmotionWithValence(Valence::Positive),
-            score: 0.05,  // Muy bajo - amor no encaja con Coliseo
-        });
+    /// Añade un tema, o amplía uno existente
+    pub fn add_theme(&mut self, theme: ThemeInfo) {
+        let existing = match self.themes.get_mut(&theme.name) {
+            Some(existing) => existing,
+            None => {
+                self.themes.insert(theme.name.clone(), theme);
+                return;
+            }
+        };
 
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "arquitectura_romana".to_string(),
-            matcher: CategoryMatcher::EmotionWithValence(Valence::Negative),
-            score: 0.05,
-        });
+        if !theme.description.is_empty() {
+            existing.description = theme.description;
+        }
+        // Solo una declaración con `prior=` lo cambia
+        if theme.prior.is_some() {
+            existing.prior = theme.prior;
+        }
+        for keyword in theme.keywords {
+            if !existing.keywords.contains(&keyword) {
+                existing.keywords.push(keyword);
+            }
+        }
+        for matcher in theme.compatible_categories {
+            if !existing.compatible_categories.contains(&matcher) {
+                existing.compatible_categories.push(matcher);
+            }
+        }
+    }
 
-        // Objetos naturales son poco compatibles con arquitectura
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "arquitectura_romana".to_string(),
-            matcher: CategoryMatcher::ObjectOfType(ObjectType::Plant),
-            score: 0.15,
-        });
+    /// Añade una regla de compatibilidad (la primera que coincide se aplica)
+    pub fn add_rule(&mut self, rule: CompatibilityRule) {
+        match self.compatibility_rules.iter_mut().find(|r| r.theme == rule.theme && r.matcher == rule.matcher) {
+            Some(existing) => existing.score = rule.score,
+            None => self.compatibility_rules.push(rule),
+        }
+    }
 
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "arquitectura_romana".to_string(),
-            matcher: CategoryMatcher::ObjectOfType(ObjectType::Food),
-            score: 0.10,
-        });
+    /// Añade una relación ("perro" Hyponym "animal": perro es un animal)
+    pub fn add_relation(&mut self, mut relation: SemanticRelation) {
+        relation.word1 = relation.word1.to_lowercase();
+        relation.word2 = relation.word2.to_lowercase();
+        let index = self.relations.len();
+        self.relation_index.entry(relation.word1.clone()).or_default().push(index);
+        if relation.word2 != relation.word1 {
+            self.relation_index.entry(relation.word2.clone()).or_default().push(index);
+        }
+        self.relations.push(relation);
+    }
 
-        // === ROMANCE ===
+    /// Relaciones en las que participa una palabra (como word1 o word2)
+    pub fn relations_of(&self, word: &str) -> Vec<&SemanticRelation> {
+        self.relation_index.get(&word.to_lowercase())
+            .map(|indices| indices.iter().map(|&i| &self.relations[i]).collect())
+            .unwrap_or_default()
+    }
 
-        // Emociones positivas son MUY compatibles con romance
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "romance".to_string(),
-            matcher: CategoryMatcher::EmotionWithValence(Valence::Positive),
-            score: 0.98,  // amor encaja perfecto en contexto romántico
-        });
+    /// Número de relaciones en la base
+    pub fn relation_count(&self) -> usize {
+        self.relations.len()
+    }
 
-        // Lugares son menos compatibles con romance (a menos que sea París)
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "romance".to_string(),
-            matcher: CategoryMatcher::PlaceInRegion("italia".to_string()),
-            score: 0.30,  // Roma no encaja bien en "te quiero con todo mi ___"
-        });
+    /// Hiperónimos directos con la fuerza de la relación.
+    /// "a Hyponym b" y "b Hypernym a" significan lo mismo
+    fn direct_hypernyms(&self, word: &str) -> Vec<(&str, f64)> {
+        self.relations_of(word).into_iter()
+            .filter_map(|r| match r.relation_type {
+                RelationType::Hyponym if r.word1 == word => Some((r.word2.as_str(), r.strength)),
+                RelationType::Hypernym if r.word2 == word => Some((r.word1.as_str(), r.strength)),
+                _ => None,
+            })
+            .collect()
+    }
 
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "romance".to_string(),
-            matcher: CategoryMatcher::PlaceInRegion("francia".to_string()),
-            score: 0.60,  // París es más romántico
-        });
+    /// Cierre transitivo de la cadena de hiperónimos, del más cercano al más
+    /// lejano. La fuerza es el producto de las relaciones del camino
+    /// ("anfiteatro" → ("edificio", 0.9) → ("construccion", 0.81))
+    pub fn hypernyms(&self, word: &str) -> Vec<(String, f64)> {
+        let word = word.to_lowercase();
+        let mut closure: Vec<(String, f64)> = Vec::new();
+        let mut frontier = vec![(word.clone(), 1.0)];
+
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for (current, strength) in frontier {
+                for (hypernym, s) in self.direct_hypernyms(&current) {
+                    let total = strength * s;
+                    if hypernym == word {
+                        continue;
+                    }
+                    // Ciclos o caminos alternativos: se conserva el más fuerte
+                    match closure.iter_mut().find(|(w, _)| w == hypernym) {
+                        Some(existing) if existing.1 >= total => {}
+                        Some(existing) => {
+                            existing.1 = total;
+                            next.push((hypernym.to_string(), total));
+                        }
+                        None => {
+                            closure.push((hypernym.to_string(), total));
+                            next.push((hypernym.to_string(), total));
+                        }
+                    }
+                }
+            }
+            frontier = next;
+        }
 
-        // === NATURALEZA ===
+        closure
+    }
 
-        // Naturaleza es compatible con plantas
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "naturaleza".to_string(),
-            matcher: CategoryMatcher::ObjectOfType(ObjectType::Plant),
-            score: 0.90,
-        });
+    /// Sinónimos directos con su fuerza
+    pub fn synonyms(&self, word: &str) -> Vec<(String, f64)> {
+        let word = word.to_lowercase();
+        self.relations_of(&word).into_iter()
+            .filter(|r| r.relation_type == RelationType::Synonym)
+            .map(|r| {
+                let other = if r.word1 == word { &r.word2 } else { &r.word1 };
+                (other.clone(), r.strength)
+            })
+            .collect()
+    }
 
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "naturaleza".to_string(),
-            matcher: CategoryMatcher::ObjectOfType(ObjectType::Food),
-            score: 0.70,  // frutas también son naturaleza
-        });
+    /// Similitud entre dos palabras (0.0 - 1.0): sinónimos, hiperónimo común
+    /// más cercano o relación genérica (a mitad de fuerza)
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b) = (a.to_lowercase(), b.to_lowercase());
+        if a == b {
+            return 1.0;
+        }
 
-        // === GEOGRAFÍA/VIAJES ===
-
-        self.themes.insert("viajes".to_string(), ThemeInfo {
-            name: "viajes".to_string(),
-            description: "Viajes y geografía".to_string(),
-            compatible_categories: vec![
-                CategoryMatcher::AnyPlace,
-            ],
-            keywords: vec![
-                "viajé".to_string(),
-                "visité".to_string(),
-                "fui".to_string(),
-                "desde".to_string(),
-                "hacia".to_string(),
-                "madrid".to_string(),
-                "paris".to_string(),
-                "roma".to_string(),
-            ],
-        });
+        let mut best = 0.0_f64;
+        for r in self.relations_of(&a) {
+            let connects = (r.word1 == a && r.word2 == b) || (r.word1 == b && r.word2 == a);
+            match r.relation_type {
+                RelationType::Synonym if connects => best = best.max(r.strength),
+                RelationType::Related if connects => best = best.max(r.strength * 0.5),
+                _ => {}
+            }
+        }
 
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "viajes".to_string(),
-            matcher: CategoryMatcher::AnyPlace,
-            score: 0.95,  // Lugares son perfectos para viajes
-        });
+        // Ancestros de cada palabra, incluida ella misma
+        let ancestors = |w: &String| {
+            let mut list = self.hypernyms(w);
+            list.push((w.clone(), 1.0));
+            list
+        };
+        let ancestors_b = ancestors(&b);
+        for (ancestor, sa) in ancestors(&a) {
+            if let Some((_, sb)) = ancestors_b.iter().find(|(w, _)| *w == ancestor) {
+                best = best.max(sa * sb);
+            }
+        }
 
-        self.compatibility_rules.push(CompatibilityRule {
-            theme: "viajes".to_string(),
-            matcher: CategoryMatcher::EmotionWithValence(Valence::Positive),
-            score: 0.20,  // Emociones no encajan bien en "viajé a ___"
-        });
+        best
     }
 
     /// Añade una palabra
@@ -549,67 +481,156 @@ impl SemanticDB {
         self.words.get(&word.to_lowercase())
     }
 
-    /// Infiere el tema del contexto basado en palabras
+    /// Infiere el tema más probable del contexto, con su probabilidad
     pub fn infer_theme(&self, context_words: &[String]) -> Option<(String, f64)> {
-        let mut theme_scores: HashMap<&str, f64> = HashMap::new();
+        self.infer_themes(context_words).into_iter().next()
+    }
 
-        for word in context_words {
-            let lower = word.to_lowercase();
+    /// Distribución de temas del contexto: P(tema) ∝ prior · evidencia,
+    /// solo temas con evidencia, de más a menos probable (empates por
+    /// prior y luego por nombre). Vacía si ninguna palabra sugiere un tema.
+    ///
+    /// Cada keyword pesa como un IDF, ln(1 + temas / temas con esa keyword):
+    /// "roma" (arquitectura y viajes) pesa menos que "coliseo". Los
+    /// hiperónimos y tags que son keywords aportan la mitad.
+    pub fn infer_themes(&self, context_words: &[String]) -> Vec<(String, f64)> {
+        let theme_count = self.themes.len() as f64;
+        let idf = |keyword: &str| {
+            let df = self.themes.values().filter(|t| t.keywords.iter().any(|k| k == keyword)).count();
+            (1.0 + theme_count / df.max(1) as f64).ln()
+        };
 
-            // Verificar keywords de cada tema
+        let mut evidence: HashMap<&str, f64> = HashMap::new();
+        let mut add = |keyword: &str, weight: f64| {
             for (theme_name, theme_info) in &self.themes {
-                if theme_info.keywords.contains(&lower) {
-                    *theme_scores.entry(theme_name.as_str()).or_insert(0.0) += 1.0;
+                if theme_info.keywords.iter().any(|k| k == keyword) {
+                    *evidence.entry(theme_name.as_str()).or_insert(0.0) += weight * idf(keyword);
                 }
             }
+        };
+
+        for word in context_words {
+            let lower = word.to_lowercase();
+
+            // Keywords de cada tema
+            add(&lower, 1.0);
+
+            // Hiperónimos que son keywords ("anfiteatro" → "edificio")
+            for (hypernym, strength) in self.hypernyms(&lower) {
+                add(&hypernym, 0.5 * strength);
+            }
 
-            // Verificar tags de palabras conocidas
+            // Tags de palabras conocidas
             if let Some(entry) = self.words.get(&lower) {
-                for (theme_name, theme_info) in &self.themes {
-                    for keyword in &theme_info.keywords {
-                        if entry.tags.contains(keyword) {
-                            *theme_scores.entry(theme_name.as_str()).or_insert(0.0) += 0.5;
-                        }
-                    }
+                for tag in &entry.tags {
+                    add(tag, 0.5);
                 }
             }
         }
 
-        // Encontrar tema con mayor score
-        theme_scores
+        let mut distribution: Vec<(String, f64, f64)> = evidence
             .into_iter()
-            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
-            .map(|(theme, score)| (theme.to_string(), score))
+            .map(|(name, e)| {
+                let prior = self.themes[name].prior.unwrap_or(1.0);
+                (name.to_string(), prior * e, prior)
+            })
+            .collect();
+
+        // Orden fijo antes de sumar: el total (y cada probabilidad) no
+        // depende del orden de iteración del HashMap
+        distribution.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        let total: f64 = distribution.iter().map(|(_, w, _)| w).sum();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+
+        distribution.into_iter().map(|(name, w, _)| (name, w / total)).collect()
     }
 
     /// Calcula compatibilidad de una palabra con un tema
     pub fn compatibility_score(&self, word: &str, theme: &str) -> f64 {
+        // Palabra o tema desconocidos = neutral
+        self.knowledge_score(word, theme).unwrap_or(0.5)
+    }
+
+    /// Compatibilidad esperada sobre una distribución de temas:
+    /// Σ P(tema) · compatibilidad(palabra, tema); neutral sin temas
+    pub fn expected_compatibility(&self, word: &str, themes: &[(String, f64)]) -> f64 {
+        self.expected_knowledge_score(word, themes).unwrap_or(0.5)
+    }
+
+    fn expected_knowledge_score(&self, word: &str, themes: &[(String, f64)]) -> Option<f64> {
+        let mut expected = 0.0;
+        let mut mass = 0.0;
+        for (theme, probability) in themes {
+            if let Some(score) = self.knowledge_score(word, theme) {
+                expected += probability * score;
+                mass += probability;
+            }
+        }
+        (mass > 0.0).then(|| expected / mass)
+    }
+
+    /// Score de contexto de una palabra: compatibilidad esperada sobre la
+    /// distribución de temas si la base la conoce (a ella o a un
+    /// hiperónimo), si no, similitud de vectores con
+    /// el resto del contexto; neutral (0.5) sin ninguna de las dos
+    pub fn context_score(&self, word: &str, themes: &[(String, f64)], context: &[String]) -> f64 {
+        if let Some(score) = self.expected_knowledge_score(word, themes) {
+            return score;
+        }
+
+        self.vectors.as_ref()
+            .and_then(|vectors| vectors.context_score(word, context))
+            .unwrap_or(0.5)
+    }
+
+    /// Compatibilidad según la base (None si no hay conocimiento aplicable)
+    fn knowledge_score(&self, word: &str, theme: &str) -> Option<f64> {
+        let theme_info = self.themes.get(theme)?;
+
         let entry = match self.lookup(word) {
             Some(e) => e,
-            None => return 0.5,  // Palabra desconocida = neutral
-        };
-
-        let theme_info = match self.themes.get(theme) {
-            Some(t) => t,
-            None => return 0.5,  // Tema desconocido = neutral
+            // Palabra desconocida: hereda de su sinónimo o hiperónimo conocido
+            // más fuerte, atenuado hacia neutral (0.5)
+            None => {
+                let (ancestor, strength) = self.known_ancestor(word)?;
+                return Some(0.5 + (self.compatibility_score(&ancestor, theme) - 0.5) * strength);
+            }
         };
 
         // Buscar regla de compatibilidad
         for rule in &self.compatibility_rules {
             if rule.theme == theme && self.category_matches(&entry.category, &rule.matcher) {
-                return rule.score;
+                return Some(rule.score);
             }
         }
 
         // Si no hay regla específica, verificar compatibilidad genérica
         for matcher in &theme_info.compatible_categories {
             if self.category_matches(&entry.category, matcher) {
-                return 0.7;  // Compatible genéricamente
+                return Some(0.7);  // Compatible genéricamente
             }
         }
 
         // Sin match = baja compatibilidad
-        0.2
+        Some(0.2)
+    }
+
+    /// Sinónimo o hiperónimo con entrada en la base, el de mayor fuerza
+    fn known_ancestor(&self, word: &str) -> Option<(String, f64)> {
+        self.synonyms(word).into_iter()
+            .chain(self.hypernyms(word))
+            .filter(|(w, _)| self.words.contains_key(w))
+            .fold(None, |best: Option<(String, f64)>, (w, s)| match best {
+                Some(b) if b.1 >= s => Some(b),
+                _ => Some((w, s)),
+            })
     }
 
     /// Verifica si una categoría coincide con un matcher
@@ -642,11 +663,12 @@ impl SemanticDB {
     /// Análisis semántico completo de una palabra en contexto
     pub fn analyze(&self, word: &str, context_words: &[String]) -> SemanticAnalysis {
         let entry = self.lookup(word);
-        let inferred_theme = self.infer_theme(context_words);
+        let themes = self.infer_themes(context_words);
+        let inferred_theme = themes.first();
 
-        let (theme_name, context_score, explanation) = match (&entry, &inferred_theme) {
+        let (theme_name, context_score, explanation) = match (&entry, inferred_theme) {
             (Some(e), Some((theme, _))) => {
-                let score = self.compatibility_score(word, theme);
+                let score = self.expected_compatibility(word, &themes);
                 let exp = format!(
                     "'{}' es {:?}, tema inferido '{}', compatibilidad: {:.0}%",
                     word, e.category, theme, score * 100.0
@@ -739,4 +761,117 @@ mod tests {
         let analysis_amor = db.analyze("amor", &context);
         assert!(analysis_amor.context_score < 0.5);
     }
+
+    #[test]
+    fn test_hypernym_closure() {
+        let mut db = SemanticDB::new();
+        db.load_knowledge("
+            relation(odeon, anfiteatro, hyponym, 0.8).
+            relation(edificio, anfiteatro, hypernym, 0.9).
+            relation(edificio, construccion, hyponym).
+            relation(construccion, edificio, hyponym).
+        ").unwrap();
+
+        let chain = db.hypernyms("odeon");
+        assert_eq!(chain[0], ("anfiteatro".to_string(), 0.8));
+        assert!(chain.iter().any(|(w, s)| w == "edificio" && (s - 0.72).abs() < 1e-9));
+        assert!(chain.iter().any(|(w, _)| w == "construccion"));
+        // Ciclo edificio ↔ construccion: termina sin incluir la palabra de partida
+        assert!(!db.hypernyms("edificio").iter().any(|(w, _)| w == "edificio"));
+
+        assert!((db.similarity("odeon", "anfiteatro") - 0.8).abs() < 1e-9);
+        assert!(db.similarity("vivienda", "casa") > 0.8);
+        assert_eq!(db.similarity("odeon", "amor"), 0.0);
+    }
+
+    #[test]
+    fn test_inherited_compatibility() {
+        let mut db = SemanticDB::new();
+        db.load_knowledge("
+            theme(arquitectura, keywords=[edificio]).
+            word(edificio, place(building), tags=[arquitectura]).
+            rule(arquitectura, any_place, 0.9).
+            relation(teatro, edificio, hyponym, 0.9).
+            relation(odeon, teatro, hyponym, 0.9).
+        ").unwrap();
+
+        // "teatro" no tiene entrada: hereda de "edificio" en vez de 0.5
+        let score = db.compatibility_score("teatro", "arquitectura");
+        assert!((score - 0.86).abs() < 1e-9);
+        assert!(db.compatibility_score("odeon", "arquitectura") > 0.5);
+        assert!(db.compatibility_score("odeon", "arquitectura") < score);
+        assert_eq!(db.compatibility_score("zzz", "arquitectura"), 0.5);
+
+        // El tema también se propaga por la cadena de hiperónimos
+        assert_eq!(db.infer_theme(&["odeon".to_string()]).unwrap().0, "arquitectura");
+    }
+
+    #[test]
+    fn test_context_score_with_vectors() {
+        let mut db = SemanticDB::new();
+        let context = vec!["visité".to_string(), "coliseo".to_string()];
+        let roman = vec![("arquitectura_romana".to_string(), 1.0)];
+
+        // Sin vectores, una palabra desconocida es neutral
+        assert_eq!(db.context_score("anfiteatro", &roman, &context), 0.5);
+
+        db.set_vectors(WordVectors::parse("coliseo 0.9 0.1
+anfiteatro 0.8 0.2
+roma 0.0 1.0
+").unwrap());
+        assert!(db.context_score("anfiteatro", &roman, &context) > 0.9);
+        assert!(db.context_score("anfiteatro", &[], &context) > 0.9);
+
+        // Si la base tiene regla, los vectores no intervienen
+        assert_eq!(db.context_score("roma", &roman, &context), 0.98);
+    }
+
+    #[test]
+    fn test_theme_distribution() {
+        let mut db = SemanticDB::new();
+        let words = |text: &str| -> Vec<String> { text.split(' ').map(String::from).collect() };
+
+        // Dos temas a la vez: romance (amor) y los de "roma"
+        let themes = db.infer_themes(&words("cenamos en Roma con mucho amor"));
+        let total: f64 = themes.iter().map(|(_, p)| p).sum();
+
+        // Determinista bit a bit entre llamadas
+        let bits = |themes: &[(String, f64)]| -> Vec<(String, u64)> {
+            themes.iter().map(|(t, p)| (t.clone(), p.to_bits())).collect()
+        };
+        let mixed = words("amor casa flor roma madrid coliseo rosa familia");
+        let first = bits(&db.infer_themes(&mixed));
+        assert_eq!(first.len(), 5);
+        for _ in 0..2000 {
+            assert_eq!(bits(&db.infer_themes(&mixed)), first);
+        }
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(themes[0].0, "romance");
+        assert!(themes.iter().any(|(t, _)| t == "viajes"));
+        assert!(themes.iter().any(|(t, _)| t == "arquitectura_romana"));
+
+        // "roma" sola reparte entre arquitectura_romana y viajes: empate por nombre
+        let roma = db.infer_themes(&words("roma"));
+        assert_eq!(roma[0].0, "arquitectura_romana");
+        assert!((roma[0].1 - roma[1].1).abs() < 1e-9);
+
+        // El prior desempata
+        db.load_knowledge("theme(viajes, prior=2.0).").unwrap();
+        let roma = db.infer_themes(&words("roma"));
+        assert_eq!(roma[0].0, "viajes");
+        assert!((roma[0].1 - 2.0 / 3.0).abs() < 1e-9);
+
+        // Ampliar el tema sin `prior=` conserva el declarado
+        db.load_knowledge("theme(viajes, keywords=[mochila]).").unwrap();
+        let again = db.infer_themes(&words("roma"));
+        assert_eq!(again[0].0, "viajes");
+        assert!((again[0].1 - 2.0 / 3.0).abs() < 1e-9);
+
+        // Compatibilidad esperada entre los dos temas
+        let expected = db.expected_compatibility("madrid", &roma);
+        let by_theme = 2.0 / 3.0 * db.compatibility_score("madrid", "viajes")
+            + 1.0 / 3.0 * db.compatibility_score("madrid", "arquitectura_romana");
+        assert!((expected - by_theme).abs() < 1e-9);
+        assert!(db.infer_themes(&words("nada que ver")).is_empty());
+    }
 }
@@ -0,0 +1,681 @@
+//! # WordNet Module
+//!
+//! Importa redes léxicas tipo WordNet en español para poblar `SemanticDB`:
+//! - Open Multilingual WordNet, formato tab (`wn-data-spa.tab`):
+//!   `02084071-n<TAB>spa:lemma<TAB>perro`
+//! - WN-LMF XML (OMW 1.4+, GWA): `LexicalEntry`/`Sense` y `Synset` con
+//!   `SynsetRelation`; el archivo lexicográfico viene en `lexfile` o `dc:subject`
+//! - Multilingual Central Repository 3.0: `wei_spa-30_variant.tsv`,
+//!   `wei_spa-30_relation.tsv` y los nombres de `wei_relations.tsv`
+//!
+//! Los synsets se identifican por offset y categoría ("02084071-n") sin
+//! importar el prefijo de cada recurso, así se pueden combinar fuentes
+//! (p. ej. lemas OMW + archivos lexicográficos de `load_lexnames`).
+//!
+//! El archivo lexicográfico ("noun.animal", "noun.location", "verb.motion")
+//! determina la categoría semántica de cada palabra.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use super::{
+    ActionType, ObjectType, PlaceType, RelationType, SemanticCategory, SemanticEntry,
+    SemanticRelation, TimeType, Valence,
+};
+
+/// Fuerza de las relaciones importadas
+const SYNONYM_STRENGTH: f64 = 0.9;
+const HYPERNYM_STRENGTH: f64 = 0.9;
+const OTHER_STRENGTH: f64 = 0.8;
+
+/// Saltos máximos al heredar el archivo lexicográfico de un hiperónimo
+const MAX_LEXFILE_DEPTH: usize = 10;
+
+/// Synset: conjunto de sinónimos con sus relaciones
+#[derive(Debug, Clone, Default)]
+pub struct Synset {
+    /// Identificador normalizado ("02084071-n")
+    pub id: String,
+    /// Archivo lexicográfico ("noun.animal")
+    pub lexfile: Option<String>,
+    /// Lemas en español, el primero es el más representativo
+    pub lemmas: Vec<String>,
+    /// Relaciones salientes (tipo, synset destino)
+    pub relations: Vec<(RelationType, String)>,
+}
+
+/// Red léxica cargada desde uno o más archivos
+#[derive(Debug, Clone, Default)]
+pub struct WordNet {
+    synsets: HashMap<String, Synset>,
+    /// Lema -> synsets en orden de sentido (el primero es el principal)
+    senses: HashMap<String, Vec<String>>,
+}
+
+/// Error de lectura de WordNet
+#[derive(Debug)]
+pub enum WordNetError {
+    IoError(String),
+    /// Línea o elemento mal formado (línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for WordNetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordNetError::IoError(e) => write!(f, "IO error: {}", e),
+            WordNetError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for WordNetError {}
+
+/// Resultado de importar una red en `SemanticDB`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportStats {
+    /// Palabras nuevas (las existentes se conservan)
+    pub words: usize,
+    pub relations: usize,
+}
+
+impl WordNet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Número de synsets
+    pub fn synset_count(&self) -> usize {
+        self.synsets.len()
+    }
+
+    /// Número de lemas distintos
+    pub fn lemma_count(&self) -> usize {
+        self.senses.len()
+    }
+
+    /// Synset por identificador (acepta "spa-30-02084071-n", "omw-es-02084071-n"...)
+    pub fn synset(&self, id: &str) -> Option<&Synset> {
+        self.synsets.get(&normalize_synset_id(id))
+    }
+
+    /// Synsets de un lema en orden de sentido
+    pub fn synsets_of(&self, lemma: &str) -> Vec<&Synset> {
+        self.senses.get(&lemma.to_lowercase())
+            .map(|ids| ids.iter().filter_map(|id| self.synsets.get(id)).collect())
+            .unwrap_or_default()
+    }
+
+    // ------------------------------------------------------------------------
+    // Carga
+    // ------------------------------------------------------------------------
+
+    /// Lee el formato tab de OMW
+    pub fn load_omw_tab(&mut self, path: &Path) -> Result<(), WordNetError> {
+        let content = read(path)?;
+        self.parse_omw_tab(&content)
+    }
+
+    /// Parsea el formato tab de OMW (solo `*:lemma`; definiciones y ejemplos se ignoran)
+    pub fn parse_omw_tab(&mut self, content: &str) -> Result<(), WordNetError> {
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 3 {
+                return Err(WordNetError::ParseError(line_no + 1, "se esperaban 3 columnas".to_string()));
+            }
+            if fields[1].ends_with(":lemma") || fields[1] == "lemma" {
+                self.add_sense(fields[2], fields[0]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lee archivos lexicográficos: `synset<TAB>lexfile` por línea
+    /// ("02084071-n<TAB>noun.animal"), para fuentes que no los incluyen
+    pub fn load_lexnames(&mut self, path: &Path) -> Result<(), WordNetError> {
+        let content = read(path)?;
+        self.parse_lexnames(&content)
+    }
+
+    pub fn parse_lexnames(&mut self, content: &str) -> Result<(), WordNetError> {
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                (Some(id), Some(lexfile)) => {
+                    self.synset_mut(id).lexfile = Some(lexfile.to_string());
+                }
+                _ => return Err(WordNetError::ParseError(line_no + 1, "se esperaba synset y lexfile".to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    /// Lee WN-LMF XML
+    pub fn load_lmf(&mut self, path: &Path) -> Result<(), WordNetError> {
+        let content = read(path)?;
+        self.parse_lmf(&content)
+    }
+
+    /// Parsea WN-LMF XML: lemas de `LexicalEntry`, synsets con su archivo
+    /// lexicográfico y `SynsetRelation`
+    pub fn parse_lmf(&mut self, content: &str) -> Result<(), WordNetError> {
+        let mut lemma: Option<String> = None;
+        let mut synset: Option<String> = None;
+
+        for tag in XmlTags::new(content) {
+            let tag = tag?;
+            match (tag.name.as_str(), tag.closing) {
+                ("LexicalEntry", true) => lemma = None,
+                ("Lemma", false) => lemma = tag.attr("writtenForm").map(str::to_string),
+                ("Sense", false) => {
+                    if let (Some(lemma), Some(target)) = (&lemma, tag.attr("synset")) {
+                        self.add_sense(lemma, target);
+                    }
+                }
+                ("Synset", false) => {
+                    let id = tag.attr("id")
+                        .ok_or_else(|| WordNetError::ParseError(tag.line, "Synset sin id".to_string()))?;
+                    let lexfile = tag.attr("lexfile").or_else(|| tag.attr("dc:subject")).map(str::to_string);
+                    let entry = self.synset_mut(id);
+                    if lexfile.is_some() {
+                        entry.lexfile = lexfile;
+                    }
+                    synset = if tag.self_closing { None } else { Some(normalize_synset_id(id)) };
+                }
+                ("Synset", true) => synset = None,
+                ("SynsetRelation", false) => {
+                    let (Some(source), Some(rel), Some(target)) = (&synset, tag.attr("relType"), tag.attr("target")) else {
+                        continue;
+                    };
+                    if let Some(relation_type) = relation_from_name(rel) {
+                        let target = normalize_synset_id(target);
+                        self.synset_mut(source).relations.push((relation_type, target));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Lee el MCR 3.0 desde su directorio (`wei_spa-30_variant.tsv`,
+    /// `wei_spa-30_relation.tsv` y, si existe, `wei_relations.tsv`)
+    pub fn load_mcr_dir(&mut self, dir: &Path) -> Result<(), WordNetError> {
+        let names = match fs::read_to_string(dir.join("wei_relations.tsv")) {
+            Ok(content) => parse_mcr_relation_names(&content),
+            Err(_) => HashMap::new(),
+        };
+        self.parse_mcr_variants(&read(&dir.join("wei_spa-30_variant.tsv"))?)?;
+        self.parse_mcr_relations(&read(&dir.join("wei_spa-30_relation.tsv"))?, &names)
+    }
+
+    /// Variantes del MCR: palabra, sentido, synset, categoría...
+    pub fn parse_mcr_variants(&mut self, content: &str) -> Result<(), WordNetError> {
+        let mut variants: Vec<(String, u32, String)> = Vec::new();
+        for (line_no, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 3 {
+                return Err(WordNetError::ParseError(line_no + 1, "se esperaban al menos 3 columnas".to_string()));
+            }
+            let sense = fields[1].trim().parse().unwrap_or(u32::MAX);
+            variants.push((fields[0].to_string(), sense, fields[2].to_string()));
+        }
+
+        // El número de sentido fija el orden, no la posición en el archivo
+        variants.sort_by_key(|(_, sense, _)| *sense);
+        for (word, _, synset) in variants {
+            self.add_sense(&word, &synset);
+        }
+        Ok(())
+    }
+
+    /// Relaciones del MCR: tipo (número o nombre), synset origen, categoría, synset destino...
+    pub fn parse_mcr_relations(&mut self, content: &str, names: &HashMap<String, String>) -> Result<(), WordNetError> {
+        for (line_no, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 4 {
+                return Err(WordNetError::ParseError(line_no + 1, "se esperaban al menos 4 columnas".to_string()));
+            }
+            let name = names.get(fields[0]).map(String::as_str).unwrap_or(fields[0]);
+            if let Some(relation_type) = relation_from_name(name) {
+                let target = normalize_synset_id(fields[3]);
+                self.synset_mut(fields[1]).relations.push((relation_type, target));
+            }
+        }
+        Ok(())
+    }
+
+    fn synset_mut(&mut self, id: &str) -> &mut Synset {
+        let id = normalize_synset_id(id);
+        self.synsets.entry(id.clone()).or_insert_with(|| Synset { id, ..Default::default() })
+    }
+
+    fn add_sense(&mut self, lemma: &str, synset: &str) {
+        let lemma = lemma.trim().to_lowercase().replace(' ', "_");
+        if lemma.is_empty() {
+            return;
+        }
+        let id = normalize_synset_id(synset);
+
+        let entry = self.synset_mut(&id);
+        if !entry.lemmas.contains(&lemma) {
+            entry.lemmas.push(lemma.clone());
+        }
+        let senses = self.senses.entry(lemma).or_default();
+        if !senses.contains(&id) {
+            senses.push(id);
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Conversión a la base semántica
+    // ------------------------------------------------------------------------
+
+    /// Archivo lexicográfico del synset o del hiperónimo más cercano que lo tenga
+    fn lexfile_of(&self, id: &str) -> Option<&str> {
+        let mut current = self.synsets.get(id)?;
+        for _ in 0..MAX_LEXFILE_DEPTH {
+            if let Some(lexfile) = &current.lexfile {
+                return Some(lexfile);
+            }
+            let parent = current.relations.iter()
+                .find(|(t, _)| *t == RelationType::Hyponym)
+                .and_then(|(_, target)| self.synsets.get(target))?;
+            current = parent;
+        }
+        None
+    }
+
+    /// Entradas y relaciones entre palabras. Cada lema toma la categoría y
+    /// las relaciones de su sentido principal; las relaciones apuntan al
+    /// lema representativo del synset destino
+    pub fn to_semantic(&self) -> (Vec<SemanticEntry>, Vec<SemanticRelation>) {
+        let mut entries = Vec::new();
+        let mut relations = Vec::new();
+
+        let mut lemmas: Vec<&String> = self.senses.keys().collect();
+        lemmas.sort();
+
+        for lemma in lemmas {
+            let primary = match self.senses[lemma].first().and_then(|id| self.synsets.get(id)) {
+                Some(s) => s,
+                None => continue,
+            };
+            let head = |synset: &Synset| synset.lemmas.first().cloned();
+
+            // Sinónimos: cada lema se enlaza con el representativo
+            if let Some(representative) = head(primary) {
+                if representative != *lemma {
+                    relations.push(relation(lemma, &representative, RelationType::Synonym, SYNONYM_STRENGTH));
+                }
+            }
+
+            let mut hypernyms = Vec::new();
+            for (relation_type, target) in &primary.relations {
+                let Some(target_lemma) = self.synsets.get(target).and_then(head) else {
+                    continue;
+                };
+                if target_lemma == *lemma {
+                    continue;
+                }
+                let strength = match relation_type {
+                    RelationType::Hyponym | RelationType::Hypernym => HYPERNYM_STRENGTH,
+                    RelationType::Antonym => 1.0,
+                    _ => OTHER_STRENGTH,
+                };
+                if *relation_type == RelationType::Hyponym {
+                    hypernyms.push(target_lemma.clone());
+                }
+                relations.push(relation(lemma, &target_lemma, relation_type.clone(), strength));
+            }
+
+            // Sin archivo lexicográfico no hay categoría: solo relaciones
+            if let Some(lexfile) = self.lexfile_of(&primary.id) {
+                if let Some(category) = category_from_lexfile(lexfile) {
+                    let domain = lexfile.split('.').nth(1).unwrap_or(lexfile).to_string();
+                    entries.push(SemanticEntry {
+                        word: lemma.clone(),
+                        category,
+                        subcategory: Some(lexfile.to_string()),
+                        tags: vec![domain],
+                        related: hypernyms,
+                    });
+                }
+            }
+        }
+
+        (entries, relations)
+    }
+}
+
+fn relation(word1: &str, word2: &str, relation_type: RelationType, strength: f64) -> SemanticRelation {
+    SemanticRelation {
+        word1: word1.to_string(),
+        word2: word2.to_string(),
+        relation_type,
+        strength,
+    }
+}
+
+fn read(path: &Path) -> Result<String, WordNetError> {
+    fs::read_to_string(path).map_err(|e| WordNetError::IoError(format!("{}: {}", path.display(), e)))
+}
+
+/// "spa-30-02084071-n", "omw-es-02084071-n", "ENG30-02084071-n" → "02084071-n"
+pub fn normalize_synset_id(id: &str) -> String {
+    let id = id.trim();
+    // `get` descarta cortes dentro de un carácter multibyte
+    let tail = id.len().checked_sub(10).and_then(|start| id.get(start..));
+    if let Some((offset, pos)) = tail.and_then(|t| Some((t.get(..8)?, t.get(8..)?))) {
+        if offset.bytes().all(|b| b.is_ascii_digit()) && pos.len() == 2 && pos.starts_with('-') {
+            // El satélite adjetival "s" se unifica con "a"
+            let pos = if pos == "-s" { "-a" } else { pos };
+            return format!("{}{}", offset, pos);
+        }
+    }
+    id.to_string()
+}
+
+/// Relación desde el synset origen al destino, según el nombre en WN-LMF,
+/// OMW o MCR ("hypernym", "has_hyperonym", "mero_part"...)
+fn relation_from_name(name: &str) -> Option<RelationType> {
+    let name = name.to_lowercase();
+    let name = name.strip_prefix("has_").unwrap_or(&name);
+
+    if matches!(name, "hypernym" | "hyperonym" | "instance_hypernym" | "instance_hyperonym") {
+        Some(RelationType::Hyponym)
+    } else if matches!(name, "hyponym" | "instance_hyponym") {
+        Some(RelationType::Hypernym)
+    } else if name.contains("antonym") {
+        Some(RelationType::Antonym)
+    } else if name.starts_with("mero") {
+        // El destino es parte del origen
+        Some(RelationType::Holonym)
+    } else if name.starts_with("holo") {
+        Some(RelationType::Meronym)
+    } else if matches!(name, "near_synonym" | "similar" | "similar_to" | "eq_synonym") {
+        Some(RelationType::Synonym)
+    } else if matches!(name, "also" | "also_see" | "related_to" | "see_also") {
+        Some(RelationType::Related)
+    } else {
+        None
+    }
+}
+
+/// `wei_relations.tsv`: identificador numérico y nombre en las dos primeras columnas
+fn parse_mcr_relation_names(content: &str) -> HashMap<String, String> {
+    content.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some((fields.next()?.trim().to_string(), fields.next()?.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Categoría semántica según el archivo lexicográfico de WordNet
+pub fn category_from_lexfile(lexfile: &str) -> Option<SemanticCategory> {
+    let (pos, domain) = lexfile.split_once('.')?;
+
+    let category = match (pos, domain) {
+        ("noun", "location") => SemanticCategory::Place { place_type: PlaceType::Generic, region: None, country: None },
+        ("noun", "person") => SemanticCategory::Person { role: None },
+        ("noun", "animal") => SemanticCategory::Object { object_type: ObjectType::Animal },
+        ("noun", "plant") => SemanticCategory::Object { object_type: ObjectType::Plant },
+        ("noun", "food") => SemanticCategory::Object { object_type: ObjectType::Food },
+        ("noun", "artifact") => SemanticCategory::Object { object_type: ObjectType::Artifact },
+        ("noun", "object" | "substance" | "body" | "phenomenon") => SemanticCategory::Object { object_type: ObjectType::Natural },
+        ("noun", "feeling") | ("verb", "emotion") => SemanticCategory::Emotion { valence: Valence::Neutral },
+        ("noun", "time") => SemanticCategory::Time { time_type: TimeType::Point },
+        ("noun", "quantity") => SemanticCategory::Quantity,
+        ("noun", "attribute") | ("adj", _) | ("adv", _) => SemanticCategory::Quality,
+        ("noun", "act" | "event") => SemanticCategory::Action { action_type: ActionType::Physical },
+        ("verb", "motion") => SemanticCategory::Action { action_type: ActionType::Movement },
+        ("verb", "cognition" | "perception") => SemanticCategory::Action { action_type: ActionType::Mental },
+        ("verb", "social" | "communication" | "competition" | "possession") => SemanticCategory::Action { action_type: ActionType::Social },
+        ("verb", _) => SemanticCategory::Action { action_type: ActionType::Physical },
+        ("noun", "tops") => return None,
+        ("noun", domain) => SemanticCategory::Concept { domain: Some(domain.to_string()) },
+        _ => return None,
+    };
+
+    Some(category)
+}
+
+// ============================================================================
+// XML mínimo (etiquetas y atributos; el texto se ignora)
+// ============================================================================
+
+struct XmlTag {
+    name: String,
+    attrs: Vec<(String, String)>,
+    closing: bool,
+    self_closing: bool,
+    line: usize,
+}
+
+impl XmlTag {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+struct XmlTags<'a> {
+    content: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> XmlTags<'a> {
+    fn new(content: &'a str) -> Self {
+        Self { content, pos: 0, line: 1 }
+    }
+
+    fn advance(&mut self, to: usize) {
+        self.line += self.content[self.pos..to].matches('\n').count();
+        self.pos = to;
+    }
+}
+
+impl Iterator for XmlTags<'_> {
+    type Item = Result<XmlTag, WordNetError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.pos + self.content[self.pos..].find('<')?;
+            self.advance(start);
+            let rest = &self.content[start..];
+
+            // Comentarios, declaraciones y DOCTYPE
+            let skip_to = if rest.starts_with("<!--") {
+                Some("-->")
+            } else if rest.starts_with("<?") {
+                Some("?>")
+            } else if rest.starts_with("<!") {
+                Some(">")
+            } else {
+                None
+            };
+            if let Some(end) = skip_to {
+                let close = rest.find(end).map(|i| start + i + end.len()).unwrap_or(self.content.len());
+                self.advance(close);
+                continue;
+            }
+
+            let end = match rest.find('>') {
+                Some(i) => start + i,
+                None => return Some(Err(WordNetError::ParseError(self.line, "etiqueta sin cerrar".to_string()))),
+            };
+            let line = self.line;
+            let inner = &self.content[start + 1..end];
+            self.advance(end + 1);
+
+            let closing = inner.starts_with('/');
+            let self_closing = inner.ends_with('/');
+            let inner = inner.trim_start_matches('/').trim_end_matches('/');
+            let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+
+            return Some(parse_attrs(&inner[name_end..], line).map(|attrs| XmlTag {
+                name: inner[..name_end].to_string(),
+                attrs,
+                closing,
+                self_closing,
+                line,
+            }));
+        }
+    }
+}
+
+fn parse_attrs(mut text: &str, line: usize) -> Result<Vec<(String, String)>, WordNetError> {
+    let mut attrs = Vec::new();
+    loop {
+        text = text.trim_start();
+        let Some(eq) = text.find('=') else {
+            return Ok(attrs);
+        };
+        let key = text[..eq].trim().to_string();
+        let after = text[eq + 1..].trim_start();
+        let quote = match after.chars().next() {
+            Some(q @ ('"' | '\'')) => q,
+            _ => return Err(WordNetError::ParseError(line, format!("atributo {} sin comillas", key))),
+        };
+        let close = after[1..].find(quote)
+            .ok_or_else(|| WordNetError::ParseError(line, format!("atributo {} sin cerrar", key)))?;
+        attrs.push((key, decode_entities(&after[1..1 + close])));
+        text = &after[close + 2..];
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::SemanticDB;
+
+    const LMF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE LexicalResource SYSTEM "http://globalwordnet.github.io/schemas/WN-LMF-1.1.dtd">
+<LexicalResource xmlns:dc="https://globalwordnet.github.io/schemas/dc/">
+  <Lexicon id="omw-es" label="Spanish WordNet" language="es" version="1.4">
+    <LexicalEntry id="omw-es-perro-n">
+      <Lemma writtenForm="perro" partOfSpeech="n"/>
+      <Sense id="omw-es-perro-n-02084071-n" synset="omw-es-02084071-n"/>
+    </LexicalEntry>
+    <LexicalEntry id="omw-es-can-n">
+      <Lemma writtenForm="can" partOfSpeech="n"/>
+      <Sense id="omw-es-can-n-02084071-n" synset="omw-es-02084071-n"/>
+    </LexicalEntry>
+    <LexicalEntry id="omw-es-cánido-n">
+      <Lemma writtenForm="cánido" partOfSpeech="n"/>
+      <Sense id="omw-es-cánido-n-02083346-n" synset="omw-es-02083346-n"/>
+    </LexicalEntry>
+    <!-- cola: parte del perro -->
+    <LexicalEntry id="omw-es-cola-n">
+      <Lemma writtenForm="cola" partOfSpeech="n"/>
+      <Sense id="omw-es-cola-n-02157557-n" synset="omw-es-02157557-n"/>
+    </LexicalEntry>
+    <Synset id="omw-es-02084071-n" ili="i46360" partOfSpeech="n" lexfile="noun.animal">
+      <SynsetRelation relType="hypernym" target="omw-es-02083346-n"/>
+      <SynsetRelation relType="mero_part" target="omw-es-02157557-n"/>
+    </Synset>
+    <Synset id="omw-es-02083346-n" ili="i46357" partOfSpeech="n" dc:subject="noun.animal"/>
+    <Synset id="omw-es-02157557-n" ili="i47500" partOfSpeech="n"/>
+  </Lexicon>
+</LexicalResource>"#;
+
+    #[test]
+    fn test_parse_lmf() {
+        let mut wn = WordNet::new();
+        wn.parse_lmf(LMF).unwrap();
+
+        assert_eq!(wn.synset_count(), 3);
+        assert_eq!(wn.lemma_count(), 4);
+
+        let perro = wn.synset("spa-30-02084071-n").unwrap();
+        assert_eq!(perro.lemmas, vec!["perro", "can"]);
+        assert_eq!(perro.lexfile.as_deref(), Some("noun.animal"));
+        assert!(perro.relations.contains(&(RelationType::Hyponym, "02083346-n".to_string())));
+        assert_eq!(wn.synset("02083346-n").unwrap().lexfile.as_deref(), Some("noun.animal"));
+    }
+
+    #[test]
+    fn test_omw_tab_with_lexnames() {
+        let mut wn = WordNet::new();
+        wn.parse_omw_tab("# Spanish\tspa\thttp://example.org\n08166552-n\tspa:lemma\tnación\n08166552-n\tspa:lemma\tpaís\n08166552-n\tspa:def\tuna entidad política\n").unwrap();
+        wn.parse_lexnames("08166552-n\tnoun.location\n").unwrap();
+
+        let (entries, relations) = wn.to_semantic();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| matches!(e.category, SemanticCategory::Place { .. })));
+        // "país" se enlaza como sinónimo del lema representativo
+        assert!(relations.iter().any(|r| r.word1 == "país" && r.word2 == "nación" && r.relation_type == RelationType::Synonym));
+
+        assert!(matches!(wn.parse_omw_tab("sin columnas"), Err(WordNetError::ParseError(1, _))));
+    }
+
+    #[test]
+    fn test_mcr_tables() {
+        let mut wn = WordNet::new();
+        wn.parse_mcr_variants("banco\t2\tspa-30-08420278-n\tn\t99\nbanco\t1\tspa-30-04177820-n\tn\t99\nasiento\t1\tspa-30-04161981-n\tn\t99\n").unwrap();
+        let names: HashMap<String, String> = parse_mcr_relation_names("12\thas_hyperonym\t34\n");
+        wn.parse_mcr_relations("12\tspa-30-04177820-n\tn\tspa-30-04161981-n\tn\t99\n", &names).unwrap();
+
+        // El sentido 1 es el principal aunque aparezca después
+        assert_eq!(wn.synsets_of("banco")[0].id, "04177820-n");
+        let (_, relations) = wn.to_semantic();
+        assert!(relations.iter().any(|r| r.word1 == "banco" && r.word2 == "asiento" && r.relation_type == RelationType::Hyponym));
+    }
+
+    #[test]
+    fn test_import_into_semantic_db() {
+        let mut wn = WordNet::new();
+        wn.parse_lmf(LMF).unwrap();
+
+        let mut db = SemanticDB::new();
+        let before = db.word_count();
+        let stats = db.import_wordnet(&wn);
+
+        assert_eq!(stats.words, 3);  // perro, can, cánido (cola no tiene lexfile)
+        assert_eq!(db.word_count(), before + 3);
+        assert!(matches!(db.lookup("can").unwrap().category, SemanticCategory::Object { object_type: ObjectType::Animal }));
+        assert!(db.hypernyms("perro").iter().any(|(w, _)| w == "cánido"));
+        assert!(db.similarity("can", "perro") > 0.8);
+
+        // Las entradas existentes no se reemplazan
+        assert!(matches!(db.lookup("roma").unwrap().category, SemanticCategory::Place { .. }));
+        assert!(matches!(normalize_synset_id("ENG30-01234567-s").as_str(), "01234567-a"));
+    }
+
+    #[test]
+    fn test_normalize_non_ascii_ids() {
+        assert_eq!(normalize_synset_id("spa-30-02084071-n"), "02084071-n");
+        // Identificadores no numéricos con caracteres multibyte se conservan
+        assert_eq!(normalize_synset_id("español-ñandú"), "español-ñandú");
+        assert_eq!(normalize_synset_id("ñandúñandú"), "ñandúñandú");
+        assert_eq!(normalize_synset_id("é1234567-n"), "é1234567-n");
+        // Corte en el límite entre desplazamiento y categoría
+        assert_eq!(normalize_synset_id("x1234567€"), "x1234567€");
+        assert_eq!(normalize_synset_id("spa-30-1234567€"), "spa-30-1234567€");
+    }
+}
@@ -118,6 +118,8 @@ pub enum MessagePayload {
 pub struct GrammaticalStructure {
     /// Tipo de oración
     pub sentence_type: SentenceType,
+    /// Modalidad (enunciativa, interrogativa, exclamativa, imperativa)
+    pub mood: SentenceMood,
     /// Componentes identificados
     pub components: Vec<GrammaticalComponent>,
     /// Tema inferido
@@ -141,6 +143,73 @@ pub enum SentenceType {
     Unknown,
 }
 
+/// Modalidad de la oración (independiente del orden de palabras)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SentenceMood {
+    /// Enunciativa
+    #[default]
+    Declarative,
+    /// Pregunta parcial ("¿dónde está el Coliseo?")
+    Question(Interrogative),
+    /// Pregunta total, de sí o no ("¿visitaste Roma?")
+    YesNoQuestion,
+    /// Exclamativa ("¡qué bonito!")
+    Exclamative,
+    /// Imperativa ("dámelo", "ven aquí")
+    Imperative,
+}
+
+/// Palabra interrogativa de una pregunta parcial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrogative {
+    /// qué
+    What,
+    /// quién, quiénes
+    Who,
+    /// dónde, adónde
+    Where,
+    /// cuándo
+    When,
+    /// cómo
+    How,
+    /// cuánto, cuánta, cuántos, cuántas
+    HowMuch,
+    /// cuál, cuáles
+    Which,
+    /// por qué
+    Why,
+}
+
+impl Interrogative {
+    /// Interrogativo por su forma; exige tilde ("donde" es relativo)
+    pub fn from_word(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "qué" => Some(Interrogative::What),
+            "quién" | "quiénes" => Some(Interrogative::Who),
+            "dónde" | "adónde" => Some(Interrogative::Where),
+            "cuándo" => Some(Interrogative::When),
+            "cómo" => Some(Interrogative::How),
+            "cuánto" | "cuánta" | "cuántos" | "cuántas" => Some(Interrogative::HowMuch),
+            "cuál" | "cuáles" => Some(Interrogative::Which),
+            _ => None,
+        }
+    }
+
+    /// Nombre del tipo de información pedida (para predicados)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interrogative::What => "what",
+            Interrogative::Who => "who",
+            Interrogative::Where => "where",
+            Interrogative::When => "when",
+            Interrogative::How => "how",
+            Interrogative::HowMuch => "how_much",
+            Interrogative::Which => "which",
+            Interrogative::Why => "why",
+        }
+    }
+}
+
 /// Componente gramatical
 #[derive(Debug, Clone)]
 pub struct GrammaticalComponent {
@@ -0,0 +1,369 @@
+//! # User Dictionaries Module
+//!
+//! Diccionarios de usuario por capas, encima del diccionario base:
+//!
+//! ```text
+//! session  → palabras de esta sesión (se descarta al terminar)
+//! tenant   → jerga del cliente (se guarda en archivo)
+//! base     → CharMatcher (RAE/LATAM + vocabulario gramatical)
+//! ```
+//!
+//! Cada capa puede añadir, eliminar o ignorar palabras; manda la capa más
+//! alta que mencione la palabra. Así las palabras de un cliente no se
+//! filtran a las correcciones de otro.
+//!
+//! Formato de archivo (una operación por línea):
+//!
+//! ```text
+//! # Diccionario del cliente
+//! add     kubernetes
+//! remove  amor
+//! ignore  xyzzy
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::dictionary::normalize_word;
+
+/// Qué hace una capa con una palabra
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordAction {
+    /// Palabra válida y candidata a corrección
+    Add,
+    /// Palabra inválida aunque esté en una capa inferior
+    Remove,
+    /// Palabra aceptada tal cual, pero nunca propuesta como corrección
+    Ignore,
+}
+
+/// Capa de la pila de diccionarios
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryLayer {
+    Base,
+    Tenant,
+    Session,
+}
+
+/// Estadísticas de una capa
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerStats {
+    pub layer: DictionaryLayer,
+    pub name: String,
+    /// Palabras añadidas (en la base, tamaño del diccionario)
+    pub added: usize,
+    pub removed: usize,
+    pub ignored: usize,
+}
+
+/// Error de lectura o escritura de un diccionario de usuario
+#[derive(Debug)]
+pub enum UserDictError {
+    IoError(String),
+    /// Línea mal formada (número de línea desde 1, descripción)
+    ParseError(usize, String),
+}
+
+impl fmt::Display for UserDictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserDictError::IoError(e) => write!(f, "IO error: {}", e),
+            UserDictError::ParseError(line, e) => write!(f, "Parse error (línea {}): {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for UserDictError {}
+
+/// Una capa de diccionario de usuario
+#[derive(Debug, Clone, Default)]
+pub struct UserDictionary {
+    name: String,
+    /// Palabra normalizada → (forma escrita, acción)
+    entries: HashMap<String, (String, WordAction)>,
+}
+
+impl UserDictionary {
+    /// Capa vacía con nombre (p. ej. el identificador del cliente)
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), entries: HashMap::new() }
+    }
+
+    /// Lee una capa desde archivo
+    pub fn from_file(name: &str, path: &Path) -> Result<Self, UserDictError> {
+        let content = fs::read_to_string(path).map_err(|e| UserDictError::IoError(e.to_string()))?;
+        Self::parse(name, &content)
+    }
+
+    /// Parsea líneas `acción palabra`
+    pub fn parse(name: &str, content: &str) -> Result<Self, UserDictError> {
+        let mut dict = Self::new(name);
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (action, word) = line.split_once(char::is_whitespace)
+                .ok_or_else(|| UserDictError::ParseError(line_no + 1, "se esperaba: acción palabra".to_string()))?;
+            let action = match action {
+                "add" => WordAction::Add,
+                "remove" => WordAction::Remove,
+                "ignore" => WordAction::Ignore,
+                other => return Err(UserDictError::ParseError(line_no + 1, format!("acción desconocida: {}", other))),
+            };
+
+            dict.set(word.trim(), action);
+        }
+
+        Ok(dict)
+    }
+
+    /// Guarda la capa en disco
+    pub fn save(&self, path: &Path) -> Result<(), UserDictError> {
+        fs::write(path, self.to_text()).map_err(|e| UserDictError::IoError(e.to_string()))
+    }
+
+    /// Serializa la capa (ordenada, salida determinista)
+    pub fn to_text(&self) -> String {
+        let mut lines: Vec<(&str, &str)> = self.entries.values()
+            .map(|(word, action)| {
+                let action = match action {
+                    WordAction::Add => "add",
+                    WordAction::Remove => "remove",
+                    WordAction::Ignore => "ignore",
+                };
+                (action, word.as_str())
+            })
+            .collect();
+        lines.sort();
+
+        let mut out = format!("# Diccionario de usuario: {}\n", self.name);
+        for (action, word) in lines {
+            out.push_str(&format!("{:<7} {}\n", action, word));
+        }
+        out
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Añade una palabra (válida y candidata a corrección)
+    pub fn add(&mut self, word: &str) {
+        self.set(word, WordAction::Add);
+    }
+
+    /// Elimina una palabra (aunque esté en una capa inferior)
+    pub fn remove(&mut self, word: &str) {
+        self.set(word, WordAction::Remove);
+    }
+
+    /// Acepta una palabra sin proponerla nunca como corrección
+    pub fn ignore(&mut self, word: &str) {
+        self.set(word, WordAction::Ignore);
+    }
+
+    /// Olvida lo que esta capa dijera de la palabra
+    pub fn forget(&mut self, word: &str) {
+        self.entries.remove(&normalize_word(word));
+    }
+
+    fn set(&mut self, word: &str, action: WordAction) {
+        let key = normalize_word(word);
+        if key.is_empty() {
+            return;
+        }
+        self.entries.insert(key, (word.to_lowercase(), action));
+    }
+
+    /// Acción de esta capa sobre la palabra, si la menciona
+    pub fn action(&self, word: &str) -> Option<WordAction> {
+        self.entries.get(&normalize_word(word)).map(|(_, action)| *action)
+    }
+
+    /// Palabras añadidas, en la forma en que se escribieron
+    pub fn added_words(&self) -> impl Iterator<Item = &String> {
+        self.entries.values()
+            .filter(|(_, action)| *action == WordAction::Add)
+            .map(|(word, _)| word)
+    }
+
+    fn count(&self, action: WordAction) -> usize {
+        self.entries.values().filter(|(_, a)| *a == action).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Capas de cliente y sesión sobre el diccionario base. La capa del
+/// cliente se comparte (`Arc`) entre sus peticiones: clonar la pila solo
+/// copia la sesión.
+#[derive(Debug, Clone)]
+pub struct LayeredDictionary {
+    tenant: Arc<UserDictionary>,
+    session: UserDictionary,
+}
+
+impl LayeredDictionary {
+    /// Pila sin palabras de cliente ni de sesión
+    pub fn new() -> Self {
+        Self::with_tenant(UserDictionary::new("tenant"))
+    }
+
+    /// Pila con la capa de un cliente y una sesión vacía
+    pub fn with_tenant(tenant: impl Into<Arc<UserDictionary>>) -> Self {
+        Self {
+            tenant: tenant.into(),
+            session: UserDictionary::new("session"),
+        }
+    }
+
+    /// Instala la capa del cliente (reemplaza la anterior)
+    pub fn set_tenant(&mut self, tenant: impl Into<Arc<UserDictionary>>) {
+        self.tenant = tenant.into();
+    }
+
+    /// Capa del cliente, para compartirla con otras pilas
+    pub fn tenant(&self) -> &Arc<UserDictionary> {
+        &self.tenant
+    }
+
+    /// Descarta las palabras de la sesión
+    pub fn clear_session(&mut self) {
+        self.session = UserDictionary::new(self.session.name());
+    }
+
+    /// Capa de usuario (`None` para la base, que vive en el CharMatcher)
+    pub fn layer(&self, layer: DictionaryLayer) -> Option<&UserDictionary> {
+        match layer {
+            DictionaryLayer::Base => None,
+            DictionaryLayer::Tenant => Some(&self.tenant),
+            DictionaryLayer::Session => Some(&self.session),
+        }
+    }
+
+    /// Capa de usuario, mutable
+    pub fn layer_mut(&mut self, layer: DictionaryLayer) -> Option<&mut UserDictionary> {
+        match layer {
+            DictionaryLayer::Base => None,
+            // Si la capa está compartida se copia antes de modificarla
+            DictionaryLayer::Tenant => Some(Arc::make_mut(&mut self.tenant)),
+            DictionaryLayer::Session => Some(&mut self.session),
+        }
+    }
+
+    /// Capa más alta que menciona la palabra y su acción
+    pub fn resolve(&self, word: &str) -> Option<(DictionaryLayer, WordAction)> {
+        [(DictionaryLayer::Session, &self.session), (DictionaryLayer::Tenant, self.tenant.as_ref())]
+            .into_iter()
+            .find_map(|(layer, dict)| dict.action(word).map(|action| (layer, action)))
+    }
+
+    /// Palabras añadidas en alguna capa y no anuladas por una superior
+    pub fn added_words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self.session.added_words()
+            .chain(self.tenant.added_words())
+            .filter(|w| matches!(self.resolve(w), Some((_, WordAction::Add))))
+            .cloned()
+            .collect();
+        words.sort();
+        words.dedup_by(|a, b| normalize_word(a) == normalize_word(b));
+        words
+    }
+
+    /// Estadísticas de las capas de usuario (cliente, sesión)
+    pub fn stats(&self) -> Vec<LayerStats> {
+        [(DictionaryLayer::Tenant, self.tenant.as_ref()), (DictionaryLayer::Session, &self.session)]
+            .into_iter()
+            .map(|(layer, dict)| LayerStats {
+                layer,
+                name: dict.name().to_string(),
+                added: dict.count(WordAction::Add),
+                removed: dict.count(WordAction::Remove),
+                ignored: dict.count(WordAction::Ignore),
+            })
+            .collect()
+    }
+}
+
+impl Default for LayeredDictionary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_operations() {
+        let mut dict = UserDictionary::new("acme");
+        dict.add("Kubernetes");
+        dict.remove("amor");
+        dict.ignore("xyzzy");
+
+        assert_eq!(dict.action("kubernetes"), Some(WordAction::Add));
+        assert_eq!(dict.action("ÁMOR"), Some(WordAction::Remove));
+        assert_eq!(dict.action("xyzzy"), Some(WordAction::Ignore));
+        assert_eq!(dict.action("roma"), None);
+
+        dict.forget("amor");
+        assert_eq!(dict.action("amor"), None);
+        assert_eq!(dict.len(), 2);
+    }
+
+    #[test]
+    fn test_upper_layer_wins() {
+        let mut layers = LayeredDictionary::new();
+        let mut tenant = UserDictionary::new("acme");
+        tenant.add("sprint");
+        tenant.add("backlog");
+        layers.set_tenant(tenant);
+        layers.layer_mut(DictionaryLayer::Session).unwrap().remove("sprint");
+
+        assert_eq!(layers.resolve("sprint"), Some((DictionaryLayer::Session, WordAction::Remove)));
+        assert_eq!(layers.resolve("backlog"), Some((DictionaryLayer::Tenant, WordAction::Add)));
+        assert_eq!(layers.added_words(), vec!["backlog"]);
+
+        layers.clear_session();
+        assert_eq!(layers.added_words(), vec!["backlog", "sprint"]);
+
+        let stats = layers.stats();
+        assert_eq!(stats[0].name, "acme");
+        assert_eq!(stats[0].added, 2);
+        assert_eq!(stats[1].added + stats[1].removed, 0);
+
+        // Otra pila del mismo cliente comparte la capa sin copiarla
+        let mut other = LayeredDictionary::with_tenant(Arc::clone(layers.tenant()));
+        assert!(Arc::ptr_eq(other.tenant(), layers.tenant()));
+        other.layer_mut(DictionaryLayer::Tenant).unwrap().add("epic");
+        assert_eq!(layers.resolve("epic"), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut dict = UserDictionary::new("acme");
+        dict.add("despliegue");
+        dict.remove("amor");
+        dict.ignore("xyzzy");
+
+        let text = dict.to_text();
+        let parsed = UserDictionary::parse("acme", &text).unwrap();
+        assert_eq!(parsed.to_text(), text);
+        assert_eq!(parsed.action("despliegue"), Some(WordAction::Add));
+
+        assert!(matches!(UserDictionary::parse("x", "borrar amor"), Err(UserDictError::ParseError(1, _))));
+    }
+}